@@ -1,5 +1,5 @@
 use crate::forking::cache::ForkCache;
-use crate::state::BlockInfoReader;
+use crate::state::{BlockInfoReader, Cancellation};
 use blockifier::execution::contract_class::{
     ContractClass as ContractClassBlockifier, ContractClassV0, ContractClassV1,
 };
@@ -25,7 +25,10 @@ use starknet_api::deprecated_contract_class::{
 use starknet_api::hash::StarkFelt;
 use starknet_api::state::StorageKey;
 use std::collections::HashMap;
+use std::future::Future;
 use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
 use universal_sierra_compiler_api::{compile_sierra, SierraType};
 use url::Url;
@@ -36,6 +39,23 @@ pub struct ForkStateReader {
     block_number: BlockNumber,
     runtime: Runtime,
     cache: ForkCache,
+    rpc_time: RpcTime,
+    cancellation: Cancellation,
+}
+
+/// Total time spent waiting for RPC responses, shared between clones
+#[derive(Debug, Clone, Default)]
+pub struct RpcTime(Arc<Mutex<Duration>>);
+
+impl RpcTime {
+    #[must_use]
+    pub fn get(&self) -> Duration {
+        *self.0.lock().unwrap()
+    }
+
+    fn add(&self, duration: Duration) {
+        *self.0.lock().unwrap() += duration;
+    }
 }
 
 impl ForkStateReader {
@@ -46,7 +66,34 @@ impl ForkStateReader {
             client: JsonRpcClient::new(HttpTransport::new(url)),
             block_number,
             runtime: Runtime::new().expect("Could not instantiate Runtime"),
+            rpc_time: RpcTime::default(),
+            cancellation: Cancellation::default(),
+        }
+    }
+
+    /// Requests made after the run is cancelled fail without being sent
+    #[must_use]
+    pub fn with_cancellation(mut self, cancellation: Cancellation) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Time spent by the reader waiting for RPC responses, updated as requests are made
+    #[must_use]
+    pub fn rpc_time(&self) -> RpcTime {
+        self.rpc_time.clone()
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> StateResult<F::Output> {
+        if self.cancellation.is_cancelled() {
+            return Err(StateReadError(
+                "Run was cancelled, requests to the fork are not sent".to_string(),
+            ));
         }
+        let start = Instant::now();
+        let output = self.runtime.block_on(future);
+        self.rpc_time.add(start.elapsed());
+        Ok(output)
     }
 
     fn block_id(&self) -> BlockId {
@@ -73,10 +120,7 @@ impl BlockInfoReader for ForkStateReader {
             return Ok(cache_hit);
         }
 
-        match self
-            .runtime
-            .block_on(self.client.get_block_with_tx_hashes(self.block_id()))
-        {
+        match self.block_on(self.client.get_block_with_tx_hashes(self.block_id()))? {
             Ok(MaybePendingBlockWithTxHashes::Block(block)) => {
                 let block_info = BlockInfo {
                     block_number: BlockNumber(block.block_number),
@@ -109,11 +153,11 @@ impl StateReader for ForkStateReader {
             return Ok(cache_hit);
         }
 
-        match self.runtime.block_on(self.client.get_storage_at(
+        match self.block_on(self.client.get_storage_at(
             FieldElement::from_(contract_address),
             FieldElement::from_(*key.0.key()),
             self.block_id(),
-        )) {
+        ))? {
             Ok(value) => {
                 let value_sf: StarkFelt = value.into_();
                 self.cache
@@ -133,10 +177,10 @@ impl StateReader for ForkStateReader {
             return Ok(cache_hit);
         }
 
-        match self.runtime.block_on(
+        match self.block_on(
             self.client
                 .get_nonce(self.block_id(), FieldElement::from_(contract_address)),
-        ) {
+        )? {
             Ok(nonce) => {
                 let nonce = nonce.into_();
                 self.cache.cache_get_nonce_at(contract_address, nonce);
@@ -157,10 +201,10 @@ impl StateReader for ForkStateReader {
             return Ok(cache_hit);
         }
 
-        match self.runtime.block_on(
+        match self.block_on(
             self.client
                 .get_class_hash_at(self.block_id(), FieldElement::from_(contract_address)),
-        ) {
+        )? {
             Ok(class_hash) => {
                 let class_hash: ClassHash = class_hash.into_();
                 self.cache
@@ -185,10 +229,10 @@ impl StateReader for ForkStateReader {
             if let Some(cache_hit) = self.cache.get_compiled_contract_class(class_hash) {
                 Ok(cache_hit)
             } else {
-                match self.runtime.block_on(
+                match self.block_on(
                     self.client
                         .get_class(self.block_id(), FieldElement::from_(*class_hash)),
-                ) {
+                )? {
                     Ok(contract_class) => {
                         self.cache
                             .cache_get_compiled_contract_class(class_hash, &contract_class);
@@ -2,8 +2,12 @@ use crate::runtime_extensions::call_to_blockifier_runtime_extension::RuntimeStat
 use crate::runtime_extensions::cheatable_starknet_runtime_extension::CheatableStarknetRuntimeExtension;
 use blockifier::execution::call_info::CallInfo;
 use blockifier::execution::entry_point_execution::{
-    finalize_execution, initialize_execution_context, prepare_call_arguments, VmExecutionContext,
+    finalize_execution, prepare_call_arguments, VmExecutionContext,
 };
+use blockifier::execution::execution_utils::{
+    write_felt, write_maybe_relocatable, ReadOnlySegments,
+};
+use blockifier::execution::syscalls::hint_processor::SyscallHintProcessor;
 use blockifier::{
     execution::{
         contract_class::{ContractClassV1, EntryPointV1},
@@ -11,19 +15,22 @@ use blockifier::{
             CallEntryPoint, EntryPointExecutionContext, EntryPointExecutionResult,
             ExecutionResources,
         },
-        errors::{EntryPointExecutionError, VirtualMachineExecutionError},
+        errors::{EntryPointExecutionError, PreExecutionError, VirtualMachineExecutionError},
         execution_utils::Args,
     },
     state::state_api::State,
 };
 use cairo_vm::{
     hint_processor::hint_processor_definition::HintProcessor,
+    serde::deserialize_program::BuiltinName,
+    types::relocatable::MaybeRelocatable,
     vm::{
         runners::cairo_runner::{CairoArg, CairoRunner},
         vm_core::VirtualMachine,
     },
 };
 use runtime::{ExtendedRuntime, StarknetRuntime};
+use starknet_api::hash::StarkFelt;
 
 // blockifier/src/execution/cairo1_execution.rs:48 (execute_entry_point_call)
 pub fn execute_entry_point_call_cairo1(
@@ -36,6 +43,10 @@ pub fn execute_entry_point_call_cairo1(
 ) -> EntryPointExecutionResult<CallInfo> {
     let RuntimeState { cheatnet_state } = runtime_state;
 
+    // region: Modified blockifier code
+    let trace_enabled = cheatnet_state.record_executed_pcs;
+    // endregion
+
     let VmExecutionContext {
         mut runner,
         mut vm,
@@ -43,7 +54,14 @@ pub fn execute_entry_point_call_cairo1(
         initial_syscall_ptr,
         entry_point,
         program_extra_data_length,
-    } = initialize_execution_context(call, contract_class, state, resources, context)?;
+    } = initialize_execution_context(
+        call,
+        contract_class,
+        state,
+        resources,
+        context,
+        trace_enabled,
+    )?;
 
     let args = prepare_call_arguments(
         &syscall_handler.call,
@@ -67,14 +85,23 @@ pub fn execute_entry_point_call_cairo1(
     };
 
     // Execute.
-    cheatable_run_entry_point(
+    let run_result = cheatable_run_entry_point(
         &mut vm,
         &mut runner,
         &mut cheatable_runtime,
         &entry_point,
         &args,
         program_extra_data_length,
-    )?;
+    );
+    // Instructions executed before a failure are recorded too
+    if trace_enabled {
+        cheatable_runtime
+            .extension
+            .cheatnet_state
+            .trace_data
+            .set_executed_pcs_for_current_call(executed_pcs(&mut vm));
+    }
+    run_result?;
     // endregion
 
     let call_info = finalize_execution(
@@ -120,3 +147,94 @@ pub fn cheatable_run_entry_point(
 
     Ok(())
 }
+
+// blockifier/src/execution/entry_point_execution.rs (initialize_execution_context)
+fn initialize_execution_context<'a>(
+    call: CallEntryPoint,
+    contract_class: &'a ContractClassV1,
+    state: &'a mut dyn State,
+    resources: &'a mut ExecutionResources,
+    context: &'a mut EntryPointExecutionContext,
+    trace_enabled: bool, // Added parameter
+) -> Result<VmExecutionContext<'a>, PreExecutionError> {
+    let entry_point = contract_class.get_entry_point(&call)?;
+
+    // Instantiate Cairo runner.
+    let proof_mode = false;
+    let mut runner = CairoRunner::new(&contract_class.0.program, "starknet", proof_mode)?;
+
+    // region: Modified blockifier code
+    // Opposite to blockifier, trace is enabled when executed instructions are recorded
+    let mut vm = VirtualMachine::new(trace_enabled);
+    // endregion
+
+    // Initialize program with all builtins.
+    let program_builtins = [
+        BuiltinName::bitwise,
+        BuiltinName::ec_op,
+        BuiltinName::ecdsa,
+        BuiltinName::output,
+        BuiltinName::pedersen,
+        BuiltinName::poseidon,
+        BuiltinName::range_check,
+        BuiltinName::segment_arena,
+    ];
+    runner.initialize_function_runner_cairo_1(&mut vm, &program_builtins)?;
+    let mut read_only_segments = ReadOnlySegments::default();
+    let program_extra_data_length =
+        prepare_program_extra_data(&mut vm, contract_class, &mut read_only_segments)?;
+
+    // Instantiate syscall handler.
+    let initial_syscall_ptr = vm.add_memory_segment();
+    let syscall_handler = SyscallHintProcessor::new(
+        state,
+        resources,
+        context,
+        initial_syscall_ptr,
+        call,
+        &contract_class.hints,
+        read_only_segments,
+    );
+
+    Ok(VmExecutionContext {
+        runner,
+        vm,
+        syscall_handler,
+        initial_syscall_ptr,
+        entry_point,
+        program_extra_data_length,
+    })
+}
+
+// blockifier/src/execution/entry_point_execution.rs (prepare_program_extra_data)
+fn prepare_program_extra_data(
+    vm: &mut VirtualMachine,
+    contract_class: &ContractClassV1,
+    read_only_segments: &mut ReadOnlySegments,
+) -> Result<usize, PreExecutionError> {
+    // Create the builtin cost segment, with dummy values.
+    let data = vec![MaybeRelocatable::from(0); 20];
+    let builtin_cost_segment_start = read_only_segments.allocate(vm, &data)?;
+
+    // Put a pointer to the builtin cost segment at the end of the program (after the
+    // additional `ret` statement).
+    let mut ptr = (vm.get_pc() + contract_class.bytecode_length())?;
+    // Push a `ret` opcode.
+    write_felt(vm, &mut ptr, StarkFelt::from(0x208b_7fff_7fff_7ffe_u128))?;
+    // Push a pointer to the builtin cost segment.
+    write_maybe_relocatable(vm, &mut ptr, builtin_cost_segment_start)?;
+
+    let program_extra_data_length = 2;
+    Ok(program_extra_data_length)
+}
+
+/// Offsets of the executed instructions in the program segment, which holds the contract bytecode
+fn executed_pcs(vm: &mut VirtualMachine) -> Vec<usize> {
+    // Only the program counter is used, which is relocated to start at 1
+    if vm.relocate_trace(&[1, 1]).is_err() {
+        return vec![];
+    }
+    vm.get_relocated_trace()
+        .map(|trace| trace.iter().map(|entry| entry.pc - 1).collect())
+        .unwrap_or_default()
+}
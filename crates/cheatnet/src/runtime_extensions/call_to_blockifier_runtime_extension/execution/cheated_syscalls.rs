@@ -131,6 +131,13 @@ pub fn execute_deployment(
 
     state.set_class_hash_at(deployed_contract_address, ctor_context.class_hash)?;
 
+    // region: Modified blockifier code
+    runtime_state
+        .cheatnet_state
+        .contract_class_hashes
+        .insert(deployed_contract_address, ctor_context.class_hash);
+    // endregion
+
     let call_info = execute_constructor_entry_point(
         state,
         runtime_state,
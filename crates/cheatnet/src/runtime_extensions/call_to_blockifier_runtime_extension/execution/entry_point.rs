@@ -1,8 +1,8 @@
 use super::cairo1_execution::execute_entry_point_call_cairo1;
 use crate::runtime_extensions::call_to_blockifier_runtime_extension::execution::deprecated::cairo0_execution::execute_entry_point_call_cairo0;
 use crate::runtime_extensions::call_to_blockifier_runtime_extension::RuntimeState;
-use crate::state::CheatnetState;
 use blockifier::execution::call_info::{CallExecution, Retdata};
+use blockifier::execution::execution_utils::felt_to_stark_felt;
 use blockifier::{
     execution::{
         call_info::CallInfo,
@@ -24,7 +24,10 @@ use starknet_api::{
     transaction::{Calldata, TransactionVersion},
 };
 use std::collections::HashSet;
-use crate::runtime_extensions::call_to_blockifier_runtime_extension::rpc::AddressOrClassHash;
+use crate::runtime_extensions::call_to_blockifier_runtime_extension::rpc::{
+    AddressOrClassHash, CallResult,
+};
+use crate::runtime_extensions::forge_runtime_extension::cheatcodes::mock_call::MockedResult;
 
 // blockifier/src/execution/entry_point.rs:180 (CallEntryPoint::execute)
 #[allow(clippy::too_many_lines)]
@@ -44,6 +47,20 @@ pub fn execute_call_entry_point(
             .clone()
     } else {
         let contract_address = &entry_point.storage_address;
+        // Cheats targeting classes apply to the class the contract currently runs
+        let class_hash = runtime_state
+            .cheatnet_state
+            .replaced_bytecode_contracts
+            .get(contract_address)
+            .copied()
+            .or_else(|| state.get_class_hash_at(*contract_address).ok())
+            .filter(|class_hash| *class_hash != ClassHash::default());
+        if let Some(class_hash) = class_hash {
+            runtime_state
+                .cheatnet_state
+                .contract_class_hashes
+                .insert(*contract_address, class_hash);
+        }
         let cheated_data_ = runtime_state
             .cheatnet_state
             .create_cheated_data(contract_address);
@@ -64,15 +81,14 @@ pub fn execute_call_entry_point(
         CallType::Delegate => AddressOrClassHash::ClassHash(entry_point.class_hash.unwrap()),
     };
 
-    if let Some(ret_data) =
-        get_ret_data_by_call_entry_point(entry_point, runtime_state.cheatnet_state)
-    {
-        runtime_state.cheatnet_state.trace_data.exit_nested_call(
-            resources,
-            &Ok(CallInfo::default()),
-            &identifier,
-        );
-        return Ok(mocked_call_info(entry_point.clone(), ret_data));
+    if let Some(mocked_result) = runtime_state.cheatnet_state.take_mocked_result(entry_point) {
+        let result = mocked_call_result(entry_point.clone(), mocked_result);
+        runtime_state
+            .cheatnet_state
+            .trace_data
+            .exit_nested_call(resources, &result, &identifier);
+        record_spied_call(runtime_state, entry_point, &result, &identifier);
+        return result;
     }
     // endregion
 
@@ -161,6 +177,7 @@ pub fn execute_call_entry_point(
         .cheatnet_state
         .trace_data
         .exit_nested_call(resources, &result, &identifier);
+    record_spied_call(runtime_state, entry_point, &result, &identifier);
 
     result
     // region: Modified blockifier code
@@ -207,36 +224,40 @@ pub fn execute_constructor_entry_point(
     // endregion
 }
 
-fn get_ret_data_by_call_entry_point(
-    call: &CallEntryPoint,
-    cheatnet_state: &CheatnetState,
-) -> Option<Vec<StarkFelt>> {
-    if let Some(contract_address) = call.code_address {
-        if let Some(contract_functions) = cheatnet_state.mocked_functions.get(&contract_address) {
-            let entrypoint_selector = call.entry_point_selector;
-
-            let ret_data = contract_functions
-                .get(&entrypoint_selector)
-                .map(Clone::clone);
-            return ret_data;
-        }
-    }
-    None
+fn record_spied_call(
+    runtime_state: &mut RuntimeState,
+    entry_point: &CallEntryPoint,
+    result: &EntryPointExecutionResult<CallInfo>,
+    identifier: &AddressOrClassHash,
+) {
+    let call_result = CallResult::from_execution_result(result, identifier);
+    runtime_state
+        .cheatnet_state
+        .record_spied_call(entry_point, &call_result);
 }
 
-fn mocked_call_info(call: CallEntryPoint, ret_data: Vec<StarkFelt>) -> CallInfo {
-    CallInfo {
-        call,
-        execution: CallExecution {
-            retdata: Retdata(ret_data),
-            events: vec![],
-            l2_to_l1_messages: vec![],
-            failed: false,
-            gas_consumed: 0,
-        },
-        vm_resources: VmExecutionResources::default(),
-        inner_calls: vec![],
-        storage_read_values: vec![],
-        accessed_storage_keys: HashSet::new(),
+// Mocked panics fail the same way as panics of executed calls do
+fn mocked_call_result(
+    call: CallEntryPoint,
+    mocked_result: MockedResult,
+) -> EntryPointExecutionResult<CallInfo> {
+    match mocked_result {
+        MockedResult::Return(ret_data) => Ok(CallInfo {
+            call,
+            execution: CallExecution {
+                retdata: Retdata(ret_data.iter().map(felt_to_stark_felt).collect()),
+                events: vec![],
+                l2_to_l1_messages: vec![],
+                failed: false,
+                gas_consumed: 0,
+            },
+            vm_resources: VmExecutionResources::default(),
+            inner_calls: vec![],
+            storage_read_values: vec![],
+            accessed_storage_keys: HashSet::new(),
+        }),
+        MockedResult::Panic(panic_data) => Err(EntryPointExecutionError::ExecutionFailed {
+            error_data: panic_data.iter().map(felt_to_stark_felt).collect(),
+        }),
     }
 }
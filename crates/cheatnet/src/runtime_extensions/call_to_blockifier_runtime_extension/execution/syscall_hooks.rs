@@ -1,9 +1,12 @@
 use crate::{
-    runtime_extensions::forge_runtime_extension::cheatcodes::spy_events::Event,
+    runtime_extensions::forge_runtime_extension::cheatcodes::{
+        spy_events::Event, spy_messages_to_l1::MessageToL1,
+    },
     state::CheatnetState,
 };
 use blockifier::execution::{
-    call_info::OrderedEvent, deprecated_syscalls::hint_processor::DeprecatedSyscallHintProcessor,
+    call_info::{OrderedEvent, OrderedL2ToL1Message},
+    deprecated_syscalls::hint_processor::DeprecatedSyscallHintProcessor,
     syscalls::hint_processor::SyscallHintProcessor,
 };
 use starknet_api::core::ContractAddress;
@@ -11,6 +14,7 @@ use starknet_api::core::ContractAddress;
 pub trait SyscallHintProcessorExt {
     fn contract_address(&self) -> ContractAddress;
     fn last_event(&self) -> &OrderedEvent;
+    fn last_message_to_l1(&self) -> &OrderedL2ToL1Message;
 }
 
 impl SyscallHintProcessorExt for SyscallHintProcessor<'_> {
@@ -20,6 +24,9 @@ impl SyscallHintProcessorExt for SyscallHintProcessor<'_> {
     fn last_event(&self) -> &OrderedEvent {
         self.events.last().unwrap()
     }
+    fn last_message_to_l1(&self) -> &OrderedL2ToL1Message {
+        self.l2_to_l1_messages.last().unwrap()
+    }
 }
 
 impl SyscallHintProcessorExt for DeprecatedSyscallHintProcessor<'_> {
@@ -29,6 +36,9 @@ impl SyscallHintProcessorExt for DeprecatedSyscallHintProcessor<'_> {
     fn last_event(&self) -> &OrderedEvent {
         self.events.last().unwrap()
     }
+    fn last_message_to_l1(&self) -> &OrderedL2ToL1Message {
+        self.l2_to_l1_messages.last().unwrap()
+    }
 }
 
 pub fn emit_event_hook(
@@ -48,3 +58,24 @@ pub fn emit_event_hook(
             .push(Event::from_ordered_event(last_event, contract_address));
     }
 }
+
+pub fn send_message_to_l1_hook(
+    syscall_handler: &impl SyscallHintProcessorExt,
+    cheatnet_state: &mut CheatnetState,
+) {
+    let contract_address = syscall_handler.contract_address();
+    let last_message = syscall_handler.last_message_to_l1();
+    let is_spied_on = cheatnet_state
+        .message_to_l1_spies
+        .iter()
+        .any(|spy_on| spy_on.does_spy(contract_address));
+
+    if is_spied_on {
+        cheatnet_state
+            .detected_messages_to_l1
+            .push(MessageToL1::from_ordered_message(
+                last_message,
+                contract_address,
+            ));
+    }
+}
@@ -89,10 +89,20 @@ impl<'a> ExtensionLogic for CheatableStarknetRuntimeExtension<'a> {
         extended_runtime: &mut Self::Runtime,
     ) {
         let syscall_handler = &extended_runtime.hint_handler;
-        if let SyscallSelector::EmitEvent = selector {
-            syscall_hooks::emit_event_hook(syscall_handler, self.cheatnet_state);
+        match selector {
+            SyscallSelector::EmitEvent => {
+                syscall_hooks::emit_event_hook(syscall_handler, self.cheatnet_state);
+            }
+            SyscallSelector::SendMessageToL1 => {
+                syscall_hooks::send_message_to_l1_hook(syscall_handler, self.cheatnet_state);
+            }
+            _ => {}
         }
     }
+
+    fn is_cancelled(&self) -> bool {
+        self.cheatnet_state.cancellation.is_cancelled()
+    }
 }
 
 pub fn stark_felt_from_ptr_immutable(
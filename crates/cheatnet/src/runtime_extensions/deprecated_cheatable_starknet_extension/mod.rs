@@ -167,8 +167,14 @@ impl<'a> DeprecatedExtensionLogic for DeprecatedCheatableStarknetRuntimeExtensio
         extended_runtime: &mut Self::Runtime,
     ) {
         let syscall_handler = &extended_runtime.hint_handler;
-        if let DeprecatedSyscallSelector::EmitEvent = selector {
-            syscall_hooks::emit_event_hook(syscall_handler, self.cheatnet_state);
+        match selector {
+            DeprecatedSyscallSelector::EmitEvent => {
+                syscall_hooks::emit_event_hook(syscall_handler, self.cheatnet_state);
+            }
+            DeprecatedSyscallSelector::SendMessageToL1 => {
+                syscall_hooks::send_message_to_l1_hook(syscall_handler, self.cheatnet_state);
+            }
+            _ => {}
         }
     }
 }
@@ -11,7 +11,9 @@ impl CheatnetState {
     ) {
         start_cheat(
             &mut self.global_elect,
+            &mut self.elected_classes,
             &mut self.elected_contracts,
+            &self.contract_class_hashes,
             target,
             sequencer_address,
             span,
@@ -23,6 +25,12 @@ impl CheatnetState {
     }
 
     pub fn stop_elect(&mut self, target: CheatTarget) {
-        stop_cheat(&mut self.global_elect, &mut self.elected_contracts, target);
+        stop_cheat(
+            &mut self.global_elect,
+            &mut self.elected_classes,
+            &mut self.elected_contracts,
+            &self.contract_class_hashes,
+            target,
+        );
     }
 }
@@ -0,0 +1,82 @@
+use crate::constants::TEST_ADDRESS;
+use crate::runtime_extensions::call_to_blockifier_runtime_extension::rpc::{
+    call_entry_point, AddressOrClassHash, CallResult,
+};
+use crate::runtime_extensions::call_to_blockifier_runtime_extension::RuntimeState;
+use crate::runtime_extensions::common::create_execute_calldata;
+use crate::state::{CheatSpan, CheatTarget};
+use blockifier::execution::entry_point::{CallEntryPoint, CallType};
+use blockifier::execution::syscalls::hint_processor::SyscallHintProcessor;
+use cairo_felt::Felt252;
+use conversions::IntoConv;
+use starknet_api::core::{ClassHash, ContractAddress, PatriciaKey};
+use starknet_api::deprecated_contract_class::EntryPointType;
+use starknet_api::hash::StarkHash;
+use starknet_api::{contract_address, patricia_key};
+
+/// External call to a target contract, executed by an invariant test between the invariant checks
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvariantCall {
+    pub contract_address: ContractAddress,
+    pub function_selector: Felt252,
+    pub calldata: Vec<Felt252>,
+    pub caller_address: ContractAddress,
+}
+
+/// Provides calls executed by invariant tests
+pub trait CallSequence {
+    /// Registers a deployed contract whose external functions are called
+    fn add_target(&mut self, contract_address: ContractAddress, class_hash: ClassHash);
+
+    /// Registers an address used as the caller, callers are random if none is registered
+    fn add_sender(&mut self, caller_address: ContractAddress);
+
+    /// Returns the next call of the sequence, `None` once the sequence is finished
+    fn next_call(&mut self) -> Option<InvariantCall>;
+}
+
+/// Executes the call from the test contract, with the caller address pranked for this call only.
+/// The prank of the target set by the test, if any, is restored after the call.
+pub fn execute_invariant_call(
+    syscall_handler: &mut SyscallHintProcessor,
+    runtime_state: &mut RuntimeState,
+    call: &InvariantCall,
+) -> CallResult {
+    let previous_prank = runtime_state
+        .cheatnet_state
+        .pranked_contracts
+        .get(&call.contract_address)
+        .cloned();
+    runtime_state.cheatnet_state.prank(
+        CheatTarget::One(call.contract_address),
+        call.caller_address,
+        CheatSpan::Number(1),
+    );
+
+    let entry_point = CallEntryPoint {
+        class_hash: None,
+        code_address: Some(call.contract_address),
+        entry_point_type: EntryPointType::External,
+        entry_point_selector: call.function_selector.clone().into_(),
+        calldata: create_execute_calldata(&call.calldata),
+        storage_address: call.contract_address,
+        caller_address: contract_address!(TEST_ADDRESS),
+        call_type: CallType::Call,
+        initial_gas: u64::MAX,
+    };
+
+    let result = call_entry_point(
+        syscall_handler,
+        runtime_state,
+        entry_point,
+        &AddressOrClassHash::ContractAddress(call.contract_address),
+    );
+
+    let pranked_contracts = &mut runtime_state.cheatnet_state.pranked_contracts;
+    match previous_prank {
+        Some(prank) => pranked_contracts.insert(call.contract_address, prank),
+        None => pranked_contracts.remove(&call.contract_address),
+    };
+
+    result
+}
@@ -1,23 +1,148 @@
+use crate::state::CheatSpan;
 use crate::CheatnetState;
-use blockifier::execution::execution_utils::felt_to_stark_felt;
+use blockifier::execution::entry_point::CallEntryPoint;
+use blockifier::execution::execution_utils::stark_felt_to_felt;
 use cairo_felt::Felt252;
 use conversions::IntoConv;
-use starknet_api::core::ContractAddress;
-use starknet_api::hash::StarkFelt;
-use std::collections::hash_map::Entry;
+use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector};
+use std::collections::{HashMap, VecDeque};
+
+/// Contracts a mock applies to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MockTarget {
+    Contract(ContractAddress),
+    // All contracts of the class, including the ones deployed later
+    Class(ClassHash),
+}
+
+/// Calldata of the calls a mock applies to
+#[derive(Clone, Debug, PartialEq)]
+pub enum MockCalldata {
+    Any,
+    Exact(Vec<Felt252>),
+    Prefix(Vec<Felt252>),
+}
+
+impl MockCalldata {
+    #[must_use]
+    pub fn matches(&self, calldata: &[Felt252]) -> bool {
+        match self {
+            MockCalldata::Any => true,
+            MockCalldata::Exact(expected) => expected.as_slice() == calldata,
+            MockCalldata::Prefix(prefix) => calldata.starts_with(prefix),
+        }
+    }
+
+    /// Exact calldata is the most specific, then longer prefixes, then any calldata
+    fn specificity(&self) -> (u8, usize) {
+        match self {
+            MockCalldata::Any => (0, 0),
+            MockCalldata::Prefix(prefix) => (1, prefix.len()),
+            MockCalldata::Exact(expected) => (2, expected.len()),
+        }
+    }
+}
+
+/// Outcome of a mocked call
+#[derive(Clone, Debug, PartialEq)]
+pub enum MockedResult {
+    Return(Vec<Felt252>),
+    Panic(Vec<Felt252>),
+}
+
+#[derive(Clone, Debug)]
+pub struct FunctionMock {
+    calldata: MockCalldata,
+    uses: MockUses,
+    // Uses of a class mock by each contract of the class, which start from `uses` of the mock.
+    // Every contract has its own span and sequence of results, the same as with cheats of a class
+    contract_uses: HashMap<ContractAddress, MockUses>,
+}
+
+impl FunctionMock {
+    fn uses_of(&self, class_contract: Option<ContractAddress>) -> &MockUses {
+        class_contract
+            .and_then(|contract| self.contract_uses.get(&contract))
+            .unwrap_or(&self.uses)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct MockUses {
+    // Results of the successive calls, the last one is repeated once the others are used
+    results: VecDeque<MockedResult>,
+    span: CheatSpan,
+}
+
+impl MockUses {
+    fn is_used_up(&self) -> bool {
+        matches!(self.span, CheatSpan::Number(0))
+    }
+
+    /// Uses up one call of the span and one entry of the results sequence
+    fn take_result(&mut self) -> MockedResult {
+        let result = if self.results.len() > 1 {
+            self.results.pop_front()
+        } else {
+            self.results.front().cloned()
+        }
+        .unwrap_or(MockedResult::Return(vec![]));
+
+        if let CheatSpan::Number(n) = &mut self.span {
+            *n = n.saturating_sub(1);
+        }
+        result
+    }
+}
 
 impl CheatnetState {
+    /// Mocks the function of the target for the calls with matching calldata,
+    /// successive calls return the successive `ret_data` entries.
+    /// A mock with the same calldata matcher is replaced.
+    pub fn mock_call(
+        &mut self,
+        target: MockTarget,
+        function_selector: Felt252,
+        calldata: MockCalldata,
+        ret_data: &[Vec<Felt252>],
+        span: CheatSpan,
+    ) {
+        let results = ret_data
+            .iter()
+            .map(|ret_data| MockedResult::Return(ret_data.clone()))
+            .collect();
+
+        self.add_mock(target, function_selector, calldata, results, span);
+    }
+
+    /// Mocks the function of the target to panic with `panic_data` for the calls with matching calldata.
+    /// A mock with the same calldata matcher is replaced.
+    pub fn mock_call_panic(
+        &mut self,
+        target: MockTarget,
+        function_selector: Felt252,
+        calldata: MockCalldata,
+        panic_data: &[Felt252],
+        span: CheatSpan,
+    ) {
+        let results = VecDeque::from([MockedResult::Panic(panic_data.to_vec())]);
+
+        self.add_mock(target, function_selector, calldata, results, span);
+    }
+
     pub fn start_mock_call(
         &mut self,
         contract_address: ContractAddress,
         function_selector: Felt252,
         ret_data: &[Felt252],
     ) {
-        let ret_data: Vec<StarkFelt> = ret_data.iter().map(felt_to_stark_felt).collect();
-
-        let contract_mocked_functions = self.mocked_functions.entry(contract_address).or_default();
-
-        contract_mocked_functions.insert(function_selector.into_(), ret_data);
+        self.mock_call(
+            MockTarget::Contract(contract_address),
+            function_selector,
+            MockCalldata::Any,
+            &[ret_data.to_vec()],
+            CheatSpan::Indefinite,
+        );
     }
 
     pub fn stop_mock_call(
@@ -25,9 +150,108 @@ impl CheatnetState {
         contract_address: ContractAddress,
         function_selector: Felt252,
     ) {
-        if let Entry::Occupied(mut e) = self.mocked_functions.entry(contract_address) {
-            let contract_mocked_functions = e.get_mut();
-            contract_mocked_functions.remove(&function_selector.into_());
+        self.stop_mocks(MockTarget::Contract(contract_address), function_selector);
+    }
+
+    /// Cancels all mocks of the function of the target
+    pub fn stop_mocks(&mut self, target: MockTarget, function_selector: Felt252) {
+        let target_mocked_functions = match target {
+            MockTarget::Contract(contract_address) => {
+                self.mocked_functions.get_mut(&contract_address)
+            }
+            MockTarget::Class(class_hash) => self.mocked_class_functions.get_mut(&class_hash),
+        };
+        if let Some(target_mocked_functions) = target_mocked_functions {
+            target_mocked_functions.remove(&function_selector.into_());
+        }
+    }
+
+    fn add_mock(
+        &mut self,
+        target: MockTarget,
+        function_selector: Felt252,
+        calldata: MockCalldata,
+        results: VecDeque<MockedResult>,
+        span: CheatSpan,
+    ) {
+        let target_mocked_functions = match target {
+            MockTarget::Contract(contract_address) => {
+                self.mocked_functions.entry(contract_address).or_default()
+            }
+            MockTarget::Class(class_hash) => {
+                self.mocked_class_functions.entry(class_hash).or_default()
+            }
+        };
+        let function_mocks = target_mocked_functions
+            .entry(function_selector.into_())
+            .or_default();
+
+        function_mocks.retain(|mock| mock.calldata != calldata);
+        function_mocks.push(FunctionMock {
+            calldata,
+            uses: MockUses { results, span },
+            contract_uses: HashMap::new(),
+        });
+    }
+
+    /// Result of the most specific mock matching the call, if there is one.
+    /// Mocks of the contract take precedence over mocks of its class.
+    /// Uses up one call of the mock span and one entry of its results sequence,
+    /// for mocks of a class only the ones of the called contract.
+    pub fn take_mocked_result(&mut self, call: &CallEntryPoint) -> Option<MockedResult> {
+        let contract_address = call.code_address?;
+        let calldata: Vec<Felt252> = call.calldata.0.iter().map(stark_felt_to_felt).collect();
+
+        take_function_mock_result(
+            self.mocked_functions.get_mut(&contract_address),
+            call.entry_point_selector,
+            &calldata,
+            None,
+        )
+        .or_else(|| {
+            let class_hash = self.contract_class_hashes.get(&contract_address)?;
+            take_function_mock_result(
+                self.mocked_class_functions.get_mut(class_hash),
+                call.entry_point_selector,
+                &calldata,
+                Some(contract_address),
+            )
+        })
+    }
+}
+
+/// `class_contract` is the called contract if the mocks are mocks of its class
+fn take_function_mock_result(
+    target_mocked_functions: Option<&mut HashMap<EntryPointSelector, Vec<FunctionMock>>>,
+    function_selector: EntryPointSelector,
+    calldata: &[Felt252],
+    class_contract: Option<ContractAddress>,
+) -> Option<MockedResult> {
+    let function_mocks = target_mocked_functions?.get_mut(&function_selector)?;
+    let index = function_mocks
+        .iter()
+        .enumerate()
+        .filter(|(_, mock)| {
+            mock.calldata.matches(calldata) && !mock.uses_of(class_contract).is_used_up()
+        })
+        .max_by_key(|(_, mock)| mock.calldata.specificity())
+        .map(|(index, _)| index)?;
+
+    let mock = &mut function_mocks[index];
+    match class_contract {
+        // Used up mocks of a class are kept, as they still apply to the other contracts of the class
+        Some(contract) => Some(
+            mock.contract_uses
+                .entry(contract)
+                .or_insert_with(|| mock.uses.clone())
+                .take_result(),
+        ),
+        None => {
+            let result = mock.uses.take_result();
+            if mock.uses.is_used_up() {
+                function_mocks.remove(index);
+            }
+            Some(result)
         }
     }
 }
@@ -7,6 +7,7 @@ pub mod declare;
 pub mod deploy;
 pub mod elect;
 pub mod get_class_hash;
+pub mod invariant;
 pub mod l1_handler_execute;
 pub mod mock_call;
 pub mod prank;
@@ -14,7 +15,9 @@ pub mod precalculate_address;
 pub mod replace_bytecode;
 pub mod roll;
 pub mod spoof;
+pub mod spy_calls;
 pub mod spy_events;
+pub mod spy_messages_to_l1;
 pub mod storage;
 pub mod warp;
 
@@ -6,7 +6,9 @@ impl CheatnetState {
     pub fn prank(&mut self, target: CheatTarget, caller_address: ContractAddress, span: CheatSpan) {
         start_cheat(
             &mut self.global_prank,
+            &mut self.pranked_classes,
             &mut self.pranked_contracts,
+            &self.contract_class_hashes,
             target,
             caller_address,
             span,
@@ -18,6 +20,12 @@ impl CheatnetState {
     }
 
     pub fn stop_prank(&mut self, target: CheatTarget) {
-        stop_cheat(&mut self.global_prank, &mut self.pranked_contracts, target);
+        stop_cheat(
+            &mut self.global_prank,
+            &mut self.pranked_classes,
+            &mut self.pranked_contracts,
+            &self.contract_class_hashes,
+            target,
+        );
     }
 }
@@ -6,7 +6,9 @@ impl CheatnetState {
     pub fn roll(&mut self, target: CheatTarget, block_number: Felt252, span: CheatSpan) {
         start_cheat(
             &mut self.global_roll,
+            &mut self.rolled_classes,
             &mut self.rolled_contracts,
+            &self.contract_class_hashes,
             target,
             block_number,
             span,
@@ -18,6 +20,12 @@ impl CheatnetState {
     }
 
     pub fn stop_roll(&mut self, target: CheatTarget) {
-        stop_cheat(&mut self.global_roll, &mut self.rolled_contracts, target);
+        stop_cheat(
+            &mut self.global_roll,
+            &mut self.rolled_classes,
+            &mut self.rolled_contracts,
+            &self.contract_class_hashes,
+            target,
+        );
     }
 }
@@ -23,7 +23,9 @@ impl CheatnetState {
     pub fn spoof(&mut self, target: CheatTarget, tx_info_mock: TxInfoMock, span: CheatSpan) {
         start_cheat(
             &mut self.global_spoof,
+            &mut self.spoofed_classes,
             &mut self.spoofed_contracts,
+            &self.contract_class_hashes,
             target,
             tx_info_mock,
             span,
@@ -35,6 +37,12 @@ impl CheatnetState {
     }
 
     pub fn stop_spoof(&mut self, target: CheatTarget) {
-        stop_cheat(&mut self.global_spoof, &mut self.spoofed_contracts, target);
+        stop_cheat(
+            &mut self.global_spoof,
+            &mut self.spoofed_classes,
+            &mut self.spoofed_contracts,
+            &self.contract_class_hashes,
+            target,
+        );
     }
 }
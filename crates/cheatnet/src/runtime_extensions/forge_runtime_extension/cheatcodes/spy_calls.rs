@@ -0,0 +1,147 @@
+use crate::runtime_extensions::call_to_blockifier_runtime_extension::rpc::CallResult;
+use crate::runtime_extensions::forge_runtime_extension::cheatcodes::spy_events::SpyTarget;
+use crate::CheatnetState;
+use blockifier::execution::entry_point::{CallEntryPoint, CallType};
+use blockifier::execution::execution_utils::stark_felt_to_felt;
+use cairo_felt::Felt252;
+use cairo_vm::hint_processor::hint_processor_utils::felt_to_usize;
+use conversions::{FromConv, IntoConv};
+use starknet_api::core::ContractAddress;
+use std::fmt::Write;
+
+/// Represents a call made to a spied on contract
+#[derive(Debug, Clone)]
+pub struct SpiedCall {
+    pub to: ContractAddress,
+    pub caller: ContractAddress,
+    pub selector: Felt252,
+    pub calldata: Vec<Felt252>,
+    pub call_type: CallType,
+    pub result: CallResult,
+}
+
+/// Records the calls made to the spied on contracts
+#[derive(Debug)]
+pub struct CallSpy {
+    target: SpyTarget,
+    // Calls of all selectors are recorded if empty
+    selectors: Vec<Felt252>,
+    calls: Vec<SpiedCall>,
+    // Number of calls already returned from `fetch_calls`
+    fetched: usize,
+}
+
+impl CallSpy {
+    fn does_spy(&self, to: ContractAddress, selector: &Felt252) -> bool {
+        self.target.does_spy(to) && (self.selectors.is_empty() || self.selectors.contains(selector))
+    }
+}
+
+impl CheatnetState {
+    pub fn spy_calls(&mut self, spy_on: SpyTarget, selectors: Vec<Felt252>) -> usize {
+        self.call_spies.push(CallSpy {
+            target: spy_on,
+            selectors,
+            calls: vec![],
+            fetched: 0,
+        });
+        self.call_spies.len() - 1
+    }
+
+    /// Records the finished call in the spies it is spied on by
+    pub fn record_spied_call(&mut self, call: &CallEntryPoint, result: &CallResult) {
+        let to = call.storage_address;
+        let selector: Felt252 = call.entry_point_selector.0.into_();
+
+        let mut spies = self
+            .call_spies
+            .iter_mut()
+            .filter(|spy| spy.does_spy(to, &selector))
+            .peekable();
+        if spies.peek().is_none() {
+            return;
+        }
+
+        let spied_call = SpiedCall {
+            to,
+            caller: call.caller_address,
+            selector: selector.clone(),
+            calldata: call.calldata.0.iter().map(stark_felt_to_felt).collect(),
+            call_type: call.call_type,
+            result: result.clone(),
+        };
+        for spy in spies {
+            spy.calls.push(spied_call.clone());
+        }
+    }
+
+    /// Calls recorded by the spy since the previous fetch
+    pub fn fetch_calls(&mut self, id: &Felt252) -> Vec<SpiedCall> {
+        let spy = &mut self.call_spies[felt_to_usize(id).unwrap()];
+        let calls = spy.calls[spy.fetched..].to_vec();
+        spy.fetched = spy.calls.len();
+        calls
+    }
+
+    /// Checks that the spy recorded the call exactly `times` times.
+    /// On mismatch returns a message listing the recorded calls of the function.
+    pub fn assert_called(
+        &self,
+        id: &Felt252,
+        to: ContractAddress,
+        selector: &Felt252,
+        calldata: &[Felt252],
+        times: usize,
+    ) -> Result<(), String> {
+        let spy = &self.call_spies[felt_to_usize(id).unwrap()];
+        let function_calls: Vec<&SpiedCall> = spy
+            .calls
+            .iter()
+            .filter(|call| call.to == to && &call.selector == selector)
+            .collect();
+        let matching_calls_count = function_calls
+            .iter()
+            .filter(|call| call.calldata == calldata)
+            .count();
+
+        if matching_calls_count == times {
+            return Ok(());
+        }
+
+        let mut msg = format!(
+            "Expected {times} call(s) to {} of selector {} with calldata {}, got {matching_calls_count}",
+            format_felt(&Felt252::from_(to)),
+            format_felt(selector),
+            format_calldata(calldata),
+        );
+        if function_calls.is_empty() {
+            msg.push_str("\nNo calls of the selector to the contract were recorded");
+        } else {
+            msg.push_str("\nRecorded calls of the selector to the contract:");
+            for call in function_calls {
+                write!(msg, "\n  calldata {}", format_calldata(&call.calldata)).unwrap();
+                if let Some(index) = first_difference(calldata, &call.calldata) {
+                    write!(msg, " (differs at index {index})").unwrap();
+                }
+            }
+        }
+        Err(msg)
+    }
+}
+
+fn first_difference(expected: &[Felt252], actual: &[Felt252]) -> Option<usize> {
+    expected
+        .iter()
+        .zip(actual)
+        .position(|(expected, actual)| expected != actual)
+        .or_else(|| (expected.len() != actual.len()).then_some(expected.len().min(actual.len())))
+}
+
+fn format_felt(felt: &Felt252) -> String {
+    format!("0x{}", felt.to_str_radix(16))
+}
+
+fn format_calldata(calldata: &[Felt252]) -> String {
+    let felts: Vec<String> = calldata.iter().map(format_felt).collect();
+    format!("[{}]", felts.join(", "))
+}
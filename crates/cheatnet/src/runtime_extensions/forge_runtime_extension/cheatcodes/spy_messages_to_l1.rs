@@ -0,0 +1,72 @@
+use crate::runtime_extensions::forge_runtime_extension::cheatcodes::spy_events::SpyTarget;
+use crate::CheatnetState;
+use blockifier::execution::call_info::OrderedL2ToL1Message;
+use blockifier::execution::execution_utils::stark_felt_to_felt;
+use cairo_felt::Felt252;
+use cairo_vm::hint_processor::hint_processor_utils::felt_to_usize;
+use conversions::FromConv;
+use starknet_api::core::ContractAddress;
+
+/// Represents a message sent to L1. It is used in the `CheatnetState` to keep track of messages
+/// sent with `send_message_to_l1_syscall`
+#[derive(Debug, PartialEq, Clone)]
+pub struct MessageToL1 {
+    pub from_address: ContractAddress,
+    pub to_address: Felt252,
+    pub payload: Vec<Felt252>,
+}
+
+impl MessageToL1 {
+    #[must_use]
+    pub fn from_ordered_message(
+        ordered_message: &OrderedL2ToL1Message,
+        from_address: ContractAddress,
+    ) -> Self {
+        Self {
+            from_address,
+            to_address: Felt252::from_bytes_be(ordered_message.message.to_address.0.as_bytes()),
+            payload: ordered_message
+                .message
+                .payload
+                .0
+                .iter()
+                .map(stark_felt_to_felt)
+                .collect(),
+        }
+    }
+}
+
+impl CheatnetState {
+    pub fn spy_messages_to_l1(&mut self, spy_on: SpyTarget) -> usize {
+        self.message_to_l1_spies.push(spy_on);
+        self.message_to_l1_spies.len() - 1
+    }
+
+    pub fn fetch_messages_to_l1(&mut self, id: &Felt252) -> (usize, Vec<Felt252>) {
+        let spy_on = &self.message_to_l1_spies[felt_to_usize(id).unwrap()];
+        let mut spied_messages_len = 0;
+        let mut unconsumed_sent_messages: Vec<MessageToL1> = vec![];
+
+        let serialized_messages: Vec<Felt252> = self
+            .detected_messages_to_l1
+            .iter()
+            .flat_map(|message| {
+                let mut flattened_message = vec![];
+                if spy_on.does_spy(message.from_address) {
+                    flattened_message.push(Felt252::from_(message.from_address));
+                    flattened_message.push(message.to_address.clone());
+                    flattened_message.push(Felt252::from(message.payload.len()));
+                    flattened_message.append(&mut message.payload.clone());
+
+                    spied_messages_len += 1;
+                } else {
+                    unconsumed_sent_messages.push(message.clone());
+                }
+                flattened_message
+            })
+            .collect();
+
+        self.detected_messages_to_l1 = unconsumed_sent_messages;
+        (spied_messages_len, serialized_messages)
+    }
+}
@@ -6,7 +6,9 @@ impl CheatnetState {
     pub fn warp(&mut self, target: CheatTarget, timestamp: Felt252, span: CheatSpan) {
         start_cheat(
             &mut self.global_warp,
+            &mut self.warped_classes,
             &mut self.warped_contracts,
+            &self.contract_class_hashes,
             target,
             timestamp,
             span,
@@ -18,6 +20,12 @@ impl CheatnetState {
     }
 
     pub fn stop_warp(&mut self, target: CheatTarget) {
-        stop_cheat(&mut self.global_warp, &mut self.warped_contracts, target);
+        stop_cheat(
+            &mut self.global_warp,
+            &mut self.warped_classes,
+            &mut self.warped_contracts,
+            &self.contract_class_hashes,
+            target,
+        );
     }
 }
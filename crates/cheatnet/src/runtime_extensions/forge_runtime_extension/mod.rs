@@ -5,8 +5,8 @@ use crate::runtime_extensions::call_to_blockifier_runtime_extension::rpc::{
 };
 use crate::runtime_extensions::forge_runtime_extension::cheatcodes::deploy::{deploy, deploy_at};
 use crate::runtime_extensions::forge_runtime_extension::cheatcodes::CheatcodeError;
-use crate::state::{CallTrace, CheatTarget};
-use anyhow::{Context, Result};
+use crate::state::{CallTrace, CheatSpan, CheatTarget};
+use anyhow::{anyhow, Context, Result};
 use blockifier::execution::call_info::{CallExecution, CallInfo};
 use blockifier::execution::deprecated_syscalls::DeprecatedSyscallSelector;
 use blockifier::execution::entry_point::{CallEntryPoint, CallType};
@@ -20,11 +20,18 @@ use num_traits::ToPrimitive;
 use scarb_api::StarknetContractArtifacts;
 
 use cairo_lang_runner::short_string::as_cairo_short_string;
-use starknet_api::core::ContractAddress;
+use starknet_api::core::{ClassHash, ContractAddress};
 
 use crate::runtime_extensions::forge_runtime_extension::cheatcodes::declare::declare;
 use crate::runtime_extensions::forge_runtime_extension::cheatcodes::get_class_hash::get_class_hash;
+use crate::runtime_extensions::forge_runtime_extension::cheatcodes::invariant::{
+    execute_invariant_call, CallSequence,
+};
 use crate::runtime_extensions::forge_runtime_extension::cheatcodes::l1_handler_execute::l1_handler_execute;
+use crate::runtime_extensions::forge_runtime_extension::cheatcodes::mock_call::{
+    MockCalldata, MockTarget,
+};
+use crate::runtime_extensions::forge_runtime_extension::cheatcodes::spy_calls::SpiedCall;
 use crate::runtime_extensions::forge_runtime_extension::cheatcodes::spy_events::SpyTarget;
 use crate::runtime_extensions::forge_runtime_extension::cheatcodes::storage::{
     calculate_variable_address, load, store,
@@ -51,10 +58,24 @@ pub type ForgeRuntime<'a> = ExtendedRuntime<ForgeExtension<'a>>;
 pub struct ForgeExtension<'a> {
     pub environment_variables: &'a HashMap<String, String>,
     pub contracts: &'a HashMap<String, StarknetContractArtifacts>,
+    /// Calls executed by invariant tests, `None` for other tests
+    pub call_sequence: Option<&'a mut dyn CallSequence>,
+}
+
+impl<'a> ForgeExtension<'a> {
+    fn call_sequence(&mut self) -> Result<&mut (dyn CallSequence + 'a)> {
+        self.call_sequence.as_deref_mut().ok_or_else(|| {
+            anyhow!("Invariant cheatcodes can only be used in tests listed in `invariant_tests` in Scarb.toml")
+        })
+    }
 }
 
 trait BufferReaderExt {
     fn read_cheat_target(&mut self) -> CheatTarget;
+    fn read_cheat_span(&mut self) -> CheatSpan;
+    fn read_mock_target(&mut self) -> MockTarget;
+    fn read_mock_calldata(&mut self) -> MockCalldata;
+    fn read_spy_target(&mut self) -> SpyTarget;
 }
 
 impl BufferReaderExt for BufferReader<'_> {
@@ -71,9 +92,55 @@ impl BufferReaderExt for BufferReader<'_> {
                     .collect();
                 CheatTarget::Multiple(contract_addresses)
             }
+            Some(3) => CheatTarget::Class(self.read_felt().into_()),
             _ => unreachable!("Invalid CheatTarget variant"),
         }
     }
+
+    fn read_cheat_span(&mut self) -> CheatSpan {
+        let cheat_span_variant = self.read_felt().to_u8();
+        match cheat_span_variant {
+            Some(0) => CheatSpan::Indefinite,
+            Some(1) => CheatSpan::Number(self.read_felt().to_usize().unwrap()),
+            _ => unreachable!("Invalid CheatSpan variant"),
+        }
+    }
+
+    fn read_mock_target(&mut self) -> MockTarget {
+        let mock_target_variant = self.read_felt().to_u8();
+        match mock_target_variant {
+            Some(0) => MockTarget::Contract(self.read_felt().into_()),
+            Some(1) => MockTarget::Class(self.read_felt().into_()),
+            _ => unreachable!("Invalid MockTarget variant"),
+        }
+    }
+
+    fn read_mock_calldata(&mut self) -> MockCalldata {
+        let mock_calldata_variant = self.read_felt().to_u8();
+        match mock_calldata_variant {
+            Some(0) => MockCalldata::Any,
+            Some(1) => MockCalldata::Exact(self.read_vec()),
+            Some(2) => MockCalldata::Prefix(self.read_vec()),
+            _ => unreachable!("Invalid MockCalldata variant"),
+        }
+    }
+
+    fn read_spy_target(&mut self) -> SpyTarget {
+        let spy_target_variant = self.read_felt().to_u8().expect("Invalid spy_target length");
+        match spy_target_variant {
+            0 => SpyTarget::All,
+            1 => SpyTarget::One(self.read_felt().into_()),
+            _ => {
+                let addresses = self
+                    .read_vec()
+                    .iter()
+                    .map(|el| ContractAddress::from_(el.clone()))
+                    .collect();
+
+                SpyTarget::Multiple(addresses)
+            }
+        }
+    }
 }
 
 // This runtime extension provides an implementation logic for functions from snforge_std library.
@@ -172,28 +239,45 @@ impl<'a> ExtensionLogic for ForgeExtension<'a> {
                     .stop_prank(target);
                 Ok(CheatcodeHandlingResult::Handled(vec![]))
             }
-            "start_mock_call" => {
-                let contract_address = input_reader.read_felt().into_();
+            "mock_call" => {
+                let target = input_reader.read_mock_target();
                 let function_selector = input_reader.read_felt();
+                let calldata = input_reader.read_mock_calldata();
 
-                let ret_data = input_reader.read_vec();
+                let ret_data_len = input_reader.read_felt().to_usize().unwrap();
+                let ret_data: Vec<_> = (0..ret_data_len).map(|_| input_reader.read_vec()).collect();
+                let span = input_reader.read_cheat_span();
 
                 extended_runtime
                     .extended_runtime
                     .extension
                     .cheatnet_state
-                    .start_mock_call(contract_address, function_selector, &ret_data);
+                    .mock_call(target, function_selector, calldata, &ret_data, span);
+                Ok(CheatcodeHandlingResult::Handled(vec![]))
+            }
+            "mock_call_panic" => {
+                let target = input_reader.read_mock_target();
+                let function_selector = input_reader.read_felt();
+                let calldata = input_reader.read_mock_calldata();
+                let panic_data = input_reader.read_vec();
+                let span = input_reader.read_cheat_span();
+
+                extended_runtime
+                    .extended_runtime
+                    .extension
+                    .cheatnet_state
+                    .mock_call_panic(target, function_selector, calldata, &panic_data, span);
                 Ok(CheatcodeHandlingResult::Handled(vec![]))
             }
             "stop_mock_call" => {
-                let contract_address = input_reader.read_felt().into_();
+                let target = input_reader.read_mock_target();
                 let function_selector = input_reader.read_felt();
 
                 extended_runtime
                     .extended_runtime
                     .extension
                     .cheatnet_state
-                    .stop_mock_call(contract_address, function_selector);
+                    .stop_mocks(target, function_selector);
                 Ok(CheatcodeHandlingResult::Handled(vec![]))
             }
             "start_spoof" => {
@@ -410,23 +494,7 @@ impl<'a> ExtensionLogic for ForgeExtension<'a> {
                 Ok(CheatcodeHandlingResult::Handled(parsed_content))
             }
             "spy_events" => {
-                let spy_target_variant = input_reader
-                    .read_felt()
-                    .to_u8()
-                    .expect("Invalid spy_target length");
-                let spy_on = match spy_target_variant {
-                    0 => SpyTarget::All,
-                    1 => SpyTarget::One(input_reader.read_felt().into_()),
-                    _ => {
-                        let addresses = input_reader
-                            .read_vec()
-                            .iter()
-                            .map(|el| ContractAddress::from_(el.clone()))
-                            .collect();
-
-                        SpyTarget::Multiple(addresses)
-                    }
-                };
+                let spy_on = input_reader.read_spy_target();
 
                 let id = extended_runtime
                     .extended_runtime
@@ -446,6 +514,71 @@ impl<'a> ExtensionLogic for ForgeExtension<'a> {
                 result.extend(serialized_events);
                 Ok(CheatcodeHandlingResult::Handled(result))
             }
+            "spy_messages_to_l1" => {
+                let spy_on = input_reader.read_spy_target();
+
+                let id = extended_runtime
+                    .extended_runtime
+                    .extension
+                    .cheatnet_state
+                    .spy_messages_to_l1(spy_on);
+                Ok(CheatcodeHandlingResult::Handled(vec![Felt252::from(id)]))
+            }
+            "fetch_messages_to_l1" => {
+                let id = &input_reader.read_felt();
+                let (sent_messages_len, serialized_messages) = extended_runtime
+                    .extended_runtime
+                    .extension
+                    .cheatnet_state
+                    .fetch_messages_to_l1(id);
+                let mut result = vec![Felt252::from(sent_messages_len)];
+                result.extend(serialized_messages);
+                Ok(CheatcodeHandlingResult::Handled(result))
+            }
+            "spy_calls" => {
+                let spy_on = input_reader.read_spy_target();
+                let selectors = input_reader.read_vec();
+
+                let id = extended_runtime
+                    .extended_runtime
+                    .extension
+                    .cheatnet_state
+                    .spy_calls(spy_on, selectors);
+                Ok(CheatcodeHandlingResult::Handled(vec![Felt252::from(id)]))
+            }
+            "fetch_calls" => {
+                let id = &input_reader.read_felt();
+                let calls = extended_runtime
+                    .extended_runtime
+                    .extension
+                    .cheatnet_state
+                    .fetch_calls(id);
+
+                let mut result = vec![Felt252::from(calls.len())];
+                for call in &calls {
+                    serialize_spied_call(call, &mut result);
+                }
+                Ok(CheatcodeHandlingResult::Handled(result))
+            }
+            "assert_called" => {
+                let id = &input_reader.read_felt();
+                let to = input_reader.read_felt().into_();
+                let selector = input_reader.read_felt();
+                let calldata = input_reader.read_vec();
+                let times = input_reader.read_felt().to_usize().unwrap();
+
+                let result = extended_runtime
+                    .extended_runtime
+                    .extension
+                    .cheatnet_state
+                    .assert_called(id, to, &selector, &calldata, times);
+                match result {
+                    Ok(()) => Ok(CheatcodeHandlingResult::Handled(vec![Felt252::from(0)])),
+                    Err(msg) => Ok(CheatcodeHandlingResult::Handled(cheatcode_panic_result(
+                        ByteArray::from(msg.as_str()).serialize_with_magic(),
+                    ))),
+                }
+            }
             "event_name_hash" => {
                 let name = input_reader.read_felt();
                 let hash = starknet_keccak(as_cairo_short_string(&name).unwrap().as_bytes());
@@ -616,6 +749,52 @@ impl<'a> ExtensionLogic for ForgeExtension<'a> {
                 let map_entry_address = calculate_variable_address(map_selector, Some(keys));
                 Ok(CheatcodeHandlingResult::Handled(vec![map_entry_address]))
             }
+            "target_contract" => {
+                let contract_address = input_reader.read_felt().into_();
+                let call_sequence = self.call_sequence()?;
+
+                let state = &mut extended_runtime
+                    .extended_runtime
+                    .extended_runtime
+                    .hint_handler
+                    .state;
+                let class_hash = match get_class_hash(*state, contract_address) {
+                    Ok(class_hash) => class_hash,
+                    Err(CheatcodeError::Recoverable(_)) => unreachable!(),
+                    Err(CheatcodeError::Unrecoverable(err)) => return Err(err),
+                };
+                if class_hash == ClassHash::default() {
+                    let address = contract_address.0.key().to_string();
+                    return Err(anyhow!("Contract not deployed at address: {address}").into());
+                }
+
+                call_sequence.add_target(contract_address, class_hash);
+                Ok(CheatcodeHandlingResult::Handled(vec![]))
+            }
+            "target_sender" => {
+                let caller_address = input_reader.read_felt().into_();
+
+                self.call_sequence()?.add_sender(caller_address);
+                Ok(CheatcodeHandlingResult::Handled(vec![]))
+            }
+            "next_call" => {
+                let Some(call) = self.call_sequence()?.next_call() else {
+                    return Ok(CheatcodeHandlingResult::Handled(vec![Felt252::from(0)]));
+                };
+
+                let cheatnet_runtime = &mut extended_runtime.extended_runtime;
+                let syscall_handler = &mut cheatnet_runtime.extended_runtime.hint_handler;
+
+                // Reverted calls are a regular part of a random sequence, only the invariants matter
+                execute_invariant_call(
+                    syscall_handler,
+                    &mut RuntimeState {
+                        cheatnet_state: cheatnet_runtime.extension.cheatnet_state,
+                    },
+                    &call,
+                );
+                Ok(CheatcodeHandlingResult::Handled(vec![Felt252::from(1)]))
+            }
             _ => Ok(CheatcodeHandlingResult::Forwarded),
         }
     }
@@ -693,6 +872,21 @@ fn serialize_call_entry_point(call_entry_point: &CallEntryPoint, output: &mut Ve
     output.push(Felt252::from(call_type));
 }
 
+fn serialize_spied_call(spied_call: &SpiedCall, output: &mut Vec<Felt252>) {
+    let call_type = match spied_call.call_type {
+        CallType::Call => 0,
+        CallType::Delegate => 1,
+    };
+
+    output.push(spied_call.to.into_());
+    output.push(spied_call.caller.into_());
+    output.push(spied_call.selector.clone());
+    output.push(Felt252::from(spied_call.calldata.len()));
+    output.extend(spied_call.calldata.iter().cloned());
+    output.push(Felt252::from(call_type));
+    serialize_call_result(&spied_call.result, output);
+}
+
 fn serialize_call_result(call_result: &CallResult, output: &mut Vec<Felt252>) {
     match call_result {
         CallResult::Success { ret_data } => {
@@ -2,10 +2,13 @@ use crate::forking::state::ForkStateReader;
 use crate::runtime_extensions::call_to_blockifier_runtime_extension::rpc::{
     subtract_execution_resources, AddressOrClassHash, CallResult,
 };
+use crate::runtime_extensions::forge_runtime_extension::cheatcodes::mock_call::FunctionMock;
 use crate::runtime_extensions::forge_runtime_extension::cheatcodes::spoof::TxInfoMock;
+use crate::runtime_extensions::forge_runtime_extension::cheatcodes::spy_calls::CallSpy;
 use crate::runtime_extensions::forge_runtime_extension::cheatcodes::spy_events::{
     Event, SpyTarget,
 };
+use crate::runtime_extensions::forge_runtime_extension::cheatcodes::spy_messages_to_l1::MessageToL1;
 use blockifier::execution::entry_point::{
     CallEntryPoint, EntryPointExecutionResult, ExecutionResources,
 };
@@ -33,6 +36,8 @@ use std::cell::{Ref, RefCell};
 use std::collections::HashMap;
 use std::hash::BuildHasher;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use trace_data::L1Resources;
 
 // Specifies which contracts to target
@@ -41,6 +46,8 @@ pub enum CheatTarget {
     All,
     One(ContractAddress),
     Multiple(Vec<ContractAddress>),
+    // All contracts of the class, including the ones deployed later
+    Class(ClassHash),
 }
 
 // Specifies the duration of the cheat
@@ -134,7 +141,7 @@ impl StateReader for ExtendedStateReader {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum CheatStatus<T> {
     Cheated(T, CheatSpan),
     Uncheated,
@@ -160,6 +167,11 @@ pub struct CallTrace {
     pub used_l1_resources: L1Resources,
     pub nested_calls: Vec<Rc<RefCell<CallTrace>>>,
     pub result: CallResult,
+    // Cheats applied to the call
+    pub cheated_data: CheatedData,
+    // Offsets of the executed instructions in the bytecode of the called contract,
+    // recorded only if `CheatnetState::record_executed_pcs` is set
+    pub executed_pcs: Vec<usize>,
 }
 
 #[derive(Clone, Debug)]
@@ -239,21 +251,51 @@ pub struct TraceData {
 pub struct CheatnetState {
     pub rolled_contracts: HashMap<ContractAddress, CheatStatus<Felt252>>,
     pub global_roll: Option<(Felt252, CheatSpan)>,
+    pub rolled_classes: HashMap<ClassHash, (Felt252, CheatSpan)>,
     pub pranked_contracts: HashMap<ContractAddress, CheatStatus<ContractAddress>>,
     pub global_prank: Option<(ContractAddress, CheatSpan)>,
+    pub pranked_classes: HashMap<ClassHash, (ContractAddress, CheatSpan)>,
     pub warped_contracts: HashMap<ContractAddress, CheatStatus<Felt252>>,
     pub global_warp: Option<(Felt252, CheatSpan)>,
+    pub warped_classes: HashMap<ClassHash, (Felt252, CheatSpan)>,
     pub elected_contracts: HashMap<ContractAddress, CheatStatus<ContractAddress>>,
     pub global_elect: Option<(ContractAddress, CheatSpan)>,
-    pub mocked_functions: HashMap<ContractAddress, HashMap<EntryPointSelector, Vec<StarkFelt>>>,
+    pub elected_classes: HashMap<ClassHash, (ContractAddress, CheatSpan)>,
+    pub mocked_functions: HashMap<ContractAddress, HashMap<EntryPointSelector, Vec<FunctionMock>>>,
+    pub mocked_class_functions: HashMap<ClassHash, HashMap<EntryPointSelector, Vec<FunctionMock>>>,
     pub spoofed_contracts: HashMap<ContractAddress, CheatStatus<TxInfoMock>>,
     pub global_spoof: Option<(TxInfoMock, CheatSpan)>,
+    pub spoofed_classes: HashMap<ClassHash, (TxInfoMock, CheatSpan)>,
+    // Class hashes of the deployed and called contracts, used to apply cheats targeting classes
+    pub contract_class_hashes: HashMap<ContractAddress, ClassHash>,
     pub replaced_bytecode_contracts: HashMap<ContractAddress, ClassHash>,
     pub spies: Vec<SpyTarget>,
     pub detected_events: Vec<Event>,
+    pub call_spies: Vec<CallSpy>,
+    pub message_to_l1_spies: Vec<SpyTarget>,
+    pub detected_messages_to_l1: Vec<MessageToL1>,
     pub deploy_salt_base: u32,
     pub block_info: BlockInfo,
     pub trace_data: TraceData,
+    pub cancellation: Cancellation,
+    /// Record instructions executed by Cairo 1 contract calls in their call traces, used for coverage
+    pub record_executed_pcs: bool,
+}
+
+/// Set when the result of a run is no longer needed, e.g. when it timed out, shared between clones.
+/// A cancelled run stops before the next step of the VM and fails requests to the fork.
+#[derive(Debug, Clone, Default)]
+pub struct Cancellation(Arc<AtomicBool>);
+
+impl Cancellation {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
 }
 
 impl Default for CheatnetState {
@@ -266,32 +308,121 @@ impl Default for CheatnetState {
             used_l1_resources: Default::default(),
             nested_calls: vec![],
             result: CallResult::Success { ret_data: vec![] },
+            cheated_data: Default::default(),
+            executed_pcs: vec![],
         }));
         Self {
             rolled_contracts: Default::default(),
             global_roll: None,
+            rolled_classes: Default::default(),
             pranked_contracts: Default::default(),
             global_prank: None,
+            pranked_classes: Default::default(),
             warped_contracts: Default::default(),
             global_warp: None,
+            warped_classes: Default::default(),
             elected_contracts: Default::default(),
             global_elect: None,
+            elected_classes: Default::default(),
             mocked_functions: Default::default(),
+            mocked_class_functions: Default::default(),
             spoofed_contracts: Default::default(),
             replaced_bytecode_contracts: Default::default(),
             global_spoof: None,
+            spoofed_classes: Default::default(),
+            contract_class_hashes: Default::default(),
             spies: vec![],
             detected_events: vec![],
+            call_spies: vec![],
+            message_to_l1_spies: vec![],
+            detected_messages_to_l1: vec![],
             deploy_salt_base: 0,
             block_info: Default::default(),
             trace_data: TraceData {
                 current_call_stack: NotEmptyCallStack::from(test_call),
             },
+            cancellation: Cancellation::default(),
+            record_executed_pcs: false,
         }
     }
 }
 
+/// Cheats and deployment salt of a `CheatnetState`, carried over from a setup fixture to the tests using it
+#[derive(Clone, Debug, Default)]
+pub struct CheatsSnapshot {
+    rolled_contracts: HashMap<ContractAddress, CheatStatus<Felt252>>,
+    global_roll: Option<(Felt252, CheatSpan)>,
+    rolled_classes: HashMap<ClassHash, (Felt252, CheatSpan)>,
+    pranked_contracts: HashMap<ContractAddress, CheatStatus<ContractAddress>>,
+    global_prank: Option<(ContractAddress, CheatSpan)>,
+    pranked_classes: HashMap<ClassHash, (ContractAddress, CheatSpan)>,
+    warped_contracts: HashMap<ContractAddress, CheatStatus<Felt252>>,
+    global_warp: Option<(Felt252, CheatSpan)>,
+    warped_classes: HashMap<ClassHash, (Felt252, CheatSpan)>,
+    elected_contracts: HashMap<ContractAddress, CheatStatus<ContractAddress>>,
+    global_elect: Option<(ContractAddress, CheatSpan)>,
+    elected_classes: HashMap<ClassHash, (ContractAddress, CheatSpan)>,
+    mocked_functions: HashMap<ContractAddress, HashMap<EntryPointSelector, Vec<FunctionMock>>>,
+    mocked_class_functions: HashMap<ClassHash, HashMap<EntryPointSelector, Vec<FunctionMock>>>,
+    spoofed_contracts: HashMap<ContractAddress, CheatStatus<TxInfoMock>>,
+    global_spoof: Option<(TxInfoMock, CheatSpan)>,
+    spoofed_classes: HashMap<ClassHash, (TxInfoMock, CheatSpan)>,
+    contract_class_hashes: HashMap<ContractAddress, ClassHash>,
+    replaced_bytecode_contracts: HashMap<ContractAddress, ClassHash>,
+    deploy_salt_base: u32,
+}
+
 impl CheatnetState {
+    #[must_use]
+    pub fn cheats_snapshot(&self) -> CheatsSnapshot {
+        CheatsSnapshot {
+            rolled_contracts: self.rolled_contracts.clone(),
+            global_roll: self.global_roll.clone(),
+            rolled_classes: self.rolled_classes.clone(),
+            pranked_contracts: self.pranked_contracts.clone(),
+            global_prank: self.global_prank.clone(),
+            pranked_classes: self.pranked_classes.clone(),
+            warped_contracts: self.warped_contracts.clone(),
+            global_warp: self.global_warp.clone(),
+            warped_classes: self.warped_classes.clone(),
+            elected_contracts: self.elected_contracts.clone(),
+            global_elect: self.global_elect.clone(),
+            elected_classes: self.elected_classes.clone(),
+            mocked_functions: self.mocked_functions.clone(),
+            mocked_class_functions: self.mocked_class_functions.clone(),
+            spoofed_contracts: self.spoofed_contracts.clone(),
+            global_spoof: self.global_spoof.clone(),
+            spoofed_classes: self.spoofed_classes.clone(),
+            contract_class_hashes: self.contract_class_hashes.clone(),
+            replaced_bytecode_contracts: self.replaced_bytecode_contracts.clone(),
+            deploy_salt_base: self.deploy_salt_base,
+        }
+    }
+
+    pub fn restore_cheats(&mut self, snapshot: &CheatsSnapshot) {
+        let snapshot = snapshot.clone();
+        self.rolled_contracts = snapshot.rolled_contracts;
+        self.global_roll = snapshot.global_roll;
+        self.rolled_classes = snapshot.rolled_classes;
+        self.pranked_contracts = snapshot.pranked_contracts;
+        self.global_prank = snapshot.global_prank;
+        self.pranked_classes = snapshot.pranked_classes;
+        self.warped_contracts = snapshot.warped_contracts;
+        self.global_warp = snapshot.global_warp;
+        self.warped_classes = snapshot.warped_classes;
+        self.elected_contracts = snapshot.elected_contracts;
+        self.global_elect = snapshot.global_elect;
+        self.elected_classes = snapshot.elected_classes;
+        self.mocked_functions = snapshot.mocked_functions;
+        self.mocked_class_functions = snapshot.mocked_class_functions;
+        self.spoofed_contracts = snapshot.spoofed_contracts;
+        self.global_spoof = snapshot.global_spoof;
+        self.spoofed_classes = snapshot.spoofed_classes;
+        self.contract_class_hashes = snapshot.contract_class_hashes;
+        self.replaced_bytecode_contracts = snapshot.replaced_bytecode_contracts;
+        self.deploy_salt_base = snapshot.deploy_salt_base;
+    }
+
     #[must_use]
     pub fn create_cheated_data(&self, contract_address: &ContractAddress) -> CheatedData {
         CheatedData {
@@ -352,12 +483,24 @@ impl CheatnetState {
 
     #[must_use]
     pub fn get_cheated_block_number(&self, address: &ContractAddress) -> Option<Felt252> {
-        get_cheat_for_contract(&self.global_roll, &self.rolled_contracts, address)
+        get_cheat_for_contract(
+            &self.global_roll,
+            &self.rolled_classes,
+            &self.rolled_contracts,
+            self.contract_class_hashes.get(address),
+            address,
+        )
     }
 
     #[must_use]
     pub fn get_cheated_block_timestamp(&self, address: &ContractAddress) -> Option<Felt252> {
-        get_cheat_for_contract(&self.global_warp, &self.warped_contracts, address)
+        get_cheat_for_contract(
+            &self.global_warp,
+            &self.warped_classes,
+            &self.warped_contracts,
+            self.contract_class_hashes.get(address),
+            address,
+        )
     }
 
     #[must_use]
@@ -365,25 +508,74 @@ impl CheatnetState {
         &self,
         address: &ContractAddress,
     ) -> Option<ContractAddress> {
-        get_cheat_for_contract(&self.global_elect, &self.elected_contracts, address)
+        get_cheat_for_contract(
+            &self.global_elect,
+            &self.elected_classes,
+            &self.elected_contracts,
+            self.contract_class_hashes.get(address),
+            address,
+        )
     }
 
     #[must_use]
     pub fn get_cheated_tx_info(&self, address: &ContractAddress) -> Option<TxInfoMock> {
-        get_cheat_for_contract(&self.global_spoof, &self.spoofed_contracts, address)
+        get_cheat_for_contract(
+            &self.global_spoof,
+            &self.spoofed_classes,
+            &self.spoofed_contracts,
+            self.contract_class_hashes.get(address),
+            address,
+        )
     }
 
     #[must_use]
     pub fn get_cheated_caller_address(&self, address: &ContractAddress) -> Option<ContractAddress> {
-        get_cheat_for_contract(&self.global_prank, &self.pranked_contracts, address)
+        get_cheat_for_contract(
+            &self.global_prank,
+            &self.pranked_classes,
+            &self.pranked_contracts,
+            self.contract_class_hashes.get(address),
+            address,
+        )
     }
 
     pub fn update_cheats(&mut self, address: &ContractAddress) {
-        update_cheat_for_contract(&self.global_roll, &mut self.rolled_contracts, address);
-        update_cheat_for_contract(&self.global_warp, &mut self.warped_contracts, address);
-        update_cheat_for_contract(&self.global_prank, &mut self.pranked_contracts, address);
-        update_cheat_for_contract(&self.global_elect, &mut self.elected_contracts, address);
-        update_cheat_for_contract(&self.global_spoof, &mut self.spoofed_contracts, address);
+        let class_hash = self.contract_class_hashes.get(address);
+        update_cheat_for_contract(
+            &self.global_roll,
+            &self.rolled_classes,
+            &mut self.rolled_contracts,
+            class_hash,
+            address,
+        );
+        update_cheat_for_contract(
+            &self.global_warp,
+            &self.warped_classes,
+            &mut self.warped_contracts,
+            class_hash,
+            address,
+        );
+        update_cheat_for_contract(
+            &self.global_prank,
+            &self.pranked_classes,
+            &mut self.pranked_contracts,
+            class_hash,
+            address,
+        );
+        update_cheat_for_contract(
+            &self.global_elect,
+            &self.elected_classes,
+            &mut self.elected_contracts,
+            class_hash,
+            address,
+        );
+        update_cheat_for_contract(
+            &self.global_spoof,
+            &self.spoofed_classes,
+            &mut self.spoofed_contracts,
+            class_hash,
+            address,
+        );
     }
 }
 
@@ -400,6 +592,8 @@ impl TraceData {
             used_l1_resources: Default::default(),
             nested_calls: vec![],
             result: CallResult::Success { ret_data: vec![] },
+            cheated_data: cheated_data.clone(),
+            executed_pcs: vec![],
         }));
         let current_call = self.current_call_stack.top();
 
@@ -417,6 +611,11 @@ impl TraceData {
         current_call.borrow_mut().entry_point.class_hash = Some(class_hash);
     }
 
+    pub fn set_executed_pcs_for_current_call(&mut self, executed_pcs: Vec<usize>) {
+        let current_call = self.current_call_stack.top();
+        current_call.borrow_mut().executed_pcs = executed_pcs;
+    }
+
     pub fn exit_nested_call(
         &mut self,
         resources_used_after_call: &ExecutionResources,
@@ -448,9 +647,12 @@ impl TraceData {
     }
 }
 
+// Cheats of the contract take precedence over cheats of its class, which take precedence over global cheats
 fn get_cheat_for_contract<T: Clone>(
     global_cheat: &Option<(T, CheatSpan)>,
+    class_cheats: &HashMap<ClassHash, (T, CheatSpan)>,
     contract_cheats: &HashMap<ContractAddress, CheatStatus<T>>,
+    class_hash: Option<&ClassHash>,
     contract: &ContractAddress,
 ) -> Option<T> {
     if let Some(cheat_status) = contract_cheats.get(contract) {
@@ -459,18 +661,26 @@ fn get_cheat_for_contract<T: Clone>(
             CheatStatus::Uncheated => None,
         }
     } else {
-        global_cheat.as_ref().map(|(cheat, _)| cheat.clone())
+        class_hash
+            .and_then(|class_hash| class_cheats.get(class_hash))
+            .or(global_cheat.as_ref())
+            .map(|(cheat, _)| cheat.clone())
     }
 }
 
 fn update_cheat_for_contract<T: Clone>(
     global_cheat: &Option<(T, CheatSpan)>,
+    class_cheats: &HashMap<ClassHash, (T, CheatSpan)>,
     contract_cheats: &mut HashMap<ContractAddress, CheatStatus<T>>,
+    class_hash: Option<&ClassHash>,
     contract: &ContractAddress,
 ) {
     if let Some(cheat_status) = contract_cheats.get_mut(contract) {
         cheat_status.decrement_cheat_span();
-    } else if let Some((cheat, span)) = global_cheat {
+    } else if let Some((cheat, span)) = class_hash
+        .and_then(|class_hash| class_cheats.get(class_hash))
+        .or(global_cheat.as_ref())
+    {
         let mut cheat_status = CheatStatus::Cheated(cheat.clone(), span.clone());
         cheat_status.decrement_cheat_span();
         contract_cheats.insert(*contract, cheat_status);
@@ -479,7 +689,9 @@ fn update_cheat_for_contract<T: Clone>(
 
 pub fn start_cheat<T: Clone, S: BuildHasher>(
     global_cheat: &mut Option<(T, CheatSpan)>,
+    class_cheats: &mut HashMap<ClassHash, (T, CheatSpan)>,
     contract_cheats: &mut HashMap<ContractAddress, CheatStatus<T>, S>,
+    contract_class_hashes: &HashMap<ContractAddress, ClassHash>,
     target: CheatTarget,
     cheat_value: T,
     span: CheatSpan,
@@ -487,9 +699,18 @@ pub fn start_cheat<T: Clone, S: BuildHasher>(
     match target {
         CheatTarget::All => {
             *global_cheat = Some((cheat_value, span));
-            // Clear individual cheats so that `All`
+            // Clear individual and class cheats so that `All`
             // contracts are affected by this cheat
             contract_cheats.clear();
+            class_cheats.clear();
+        }
+        CheatTarget::Class(class_hash) => {
+            class_cheats.insert(class_hash, (cheat_value, span));
+            // Clear individual cheats of the contracts of the class
+            // so that they are affected by this cheat
+            contract_cheats.retain(|contract_address, _| {
+                contract_class_hashes.get(contract_address) != Some(&class_hash)
+            });
         }
         CheatTarget::One(contract_address) => {
             (*contract_cheats).insert(contract_address, CheatStatus::Cheated(cheat_value, span));
@@ -507,13 +728,22 @@ pub fn start_cheat<T: Clone, S: BuildHasher>(
 
 pub fn stop_cheat<T, S: BuildHasher>(
     global_cheat: &mut Option<(T, CheatSpan)>,
+    class_cheats: &mut HashMap<ClassHash, (T, CheatSpan)>,
     contract_cheats: &mut HashMap<ContractAddress, CheatStatus<T>, S>,
+    contract_class_hashes: &HashMap<ContractAddress, ClassHash>,
     target: CheatTarget,
 ) {
     match target {
         CheatTarget::All => {
             *global_cheat = None;
             contract_cheats.clear();
+            class_cheats.clear();
+        }
+        CheatTarget::Class(class_hash) => {
+            class_cheats.remove(&class_hash);
+            contract_cheats.retain(|contract_address, _| {
+                contract_class_hashes.get(contract_address) != Some(&class_hash)
+            });
         }
         CheatTarget::One(contract_address) => {
             (*contract_cheats).insert(contract_address, CheatStatus::Uncheated);
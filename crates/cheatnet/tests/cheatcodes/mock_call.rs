@@ -2,12 +2,16 @@ use crate::common::state::{build_runtime_state, create_cached_state};
 use crate::common::{call_contract, deploy_wrapper};
 use crate::common::{felt_selector_from_name, recover_data};
 use crate::{
-    common::assertions::assert_success,
+    common::assertions::{assert_panic, assert_success},
     common::{deploy_contract, get_contracts},
 };
 use cairo_felt::Felt252;
+use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::CallResult;
 use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::declare::declare;
-use cheatnet::state::CheatnetState;
+use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::mock_call::{
+    MockCalldata, MockTarget,
+};
+use cheatnet::state::{CheatSpan, CheatnetState};
 use conversions::IntoConv;
 use starknet_api::core::ContractAddress;
 
@@ -627,3 +631,314 @@ fn mock_call_nonexisting_contract() {
 
     assert_success(output, &ret_data);
 }
+
+#[test]
+fn mock_call_exact_calldata() {
+    let mut cached_state = create_cached_state();
+    let mut cheatnet_state = CheatnetState::default();
+    let mut runtime_state = build_runtime_state(&mut cheatnet_state);
+
+    let selector = felt_selector_from_name("get_balance");
+    let contract_address = ContractAddress::from(218_u8);
+
+    runtime_state.cheatnet_state.mock_call(
+        MockTarget::Contract(contract_address),
+        selector.clone(),
+        MockCalldata::Exact(vec![Felt252::from(1), Felt252::from(2)]),
+        &[vec![Felt252::from(123)]],
+        CheatSpan::Indefinite,
+    );
+
+    let output = call_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        &contract_address,
+        &selector,
+        &[Felt252::from(1), Felt252::from(2)],
+    );
+    assert_success(output, &[Felt252::from(123)]);
+
+    let output = call_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        &contract_address,
+        &selector,
+        &[Felt252::from(1), Felt252::from(2), Felt252::from(3)],
+    );
+    assert!(matches!(output, CallResult::Failure(_)));
+}
+
+#[test]
+fn mock_call_most_specific_calldata() {
+    let mut cached_state = create_cached_state();
+    let mut cheatnet_state = CheatnetState::default();
+    let mut runtime_state = build_runtime_state(&mut cheatnet_state);
+
+    let selector = felt_selector_from_name("get_balance");
+    let contract_address = ContractAddress::from(218_u8);
+
+    for (calldata, ret_data) in [
+        (MockCalldata::Any, 1),
+        (MockCalldata::Prefix(vec![Felt252::from(7)]), 2),
+        (
+            MockCalldata::Exact(vec![Felt252::from(7), Felt252::from(8)]),
+            3,
+        ),
+    ] {
+        runtime_state.cheatnet_state.mock_call(
+            MockTarget::Contract(contract_address),
+            selector.clone(),
+            calldata,
+            &[vec![Felt252::from(ret_data)]],
+            CheatSpan::Indefinite,
+        );
+    }
+
+    for (calldata, expected) in [
+        (vec![Felt252::from(7), Felt252::from(8)], 3),
+        (vec![Felt252::from(7), Felt252::from(9)], 2),
+        (vec![Felt252::from(6)], 1),
+    ] {
+        let output = call_contract(
+            &mut cached_state,
+            &mut runtime_state,
+            &contract_address,
+            &selector,
+            &calldata,
+        );
+        assert_success(output, &[Felt252::from(expected)]);
+    }
+}
+
+#[test]
+fn mock_call_span() {
+    let mut cached_state = create_cached_state();
+    let mut cheatnet_state = CheatnetState::default();
+    let mut runtime_state = build_runtime_state(&mut cheatnet_state);
+
+    let contract_address = deploy_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        "MockChecker",
+        &[Felt252::from(420)],
+    );
+
+    let selector = felt_selector_from_name("get_thing");
+
+    runtime_state.cheatnet_state.mock_call(
+        MockTarget::Contract(contract_address),
+        selector.clone(),
+        MockCalldata::Any,
+        &[vec![Felt252::from(123)]],
+        CheatSpan::Number(2),
+    );
+
+    for expected in [123, 123, 420] {
+        let output = call_contract(
+            &mut cached_state,
+            &mut runtime_state,
+            &contract_address,
+            &selector,
+            &[],
+        );
+        assert_success(output, &[Felt252::from(expected)]);
+    }
+}
+
+#[test]
+fn mock_call_sequence() {
+    let mut cached_state = create_cached_state();
+    let mut cheatnet_state = CheatnetState::default();
+    let mut runtime_state = build_runtime_state(&mut cheatnet_state);
+
+    let contract_address = deploy_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        "MockChecker",
+        &[Felt252::from(420)],
+    );
+
+    let selector = felt_selector_from_name("get_thing");
+
+    runtime_state.cheatnet_state.mock_call(
+        MockTarget::Contract(contract_address),
+        selector.clone(),
+        MockCalldata::Any,
+        &[
+            vec![Felt252::from(1)],
+            vec![Felt252::from(2)],
+            vec![Felt252::from(3)],
+        ],
+        CheatSpan::Indefinite,
+    );
+
+    for expected in [1, 2, 3, 3] {
+        let output = call_contract(
+            &mut cached_state,
+            &mut runtime_state,
+            &contract_address,
+            &selector,
+            &[],
+        );
+        assert_success(output, &[Felt252::from(expected)]);
+    }
+}
+
+#[test]
+fn mock_call_panic() {
+    let mut cached_state = create_cached_state();
+    let mut cheatnet_state = CheatnetState::default();
+    let mut runtime_state = build_runtime_state(&mut cheatnet_state);
+
+    let contract_address = deploy_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        "MockChecker",
+        &[Felt252::from(420)],
+    );
+
+    let selector = felt_selector_from_name("get_thing");
+    let panic_data = [Felt252::from_bytes_be(b"Mocked panic"), Felt252::from(1)];
+
+    runtime_state.cheatnet_state.mock_call_panic(
+        MockTarget::Contract(contract_address),
+        selector.clone(),
+        MockCalldata::Any,
+        &panic_data,
+        CheatSpan::Number(1),
+    );
+
+    let output = call_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        &contract_address,
+        &selector,
+        &[],
+    );
+    assert_panic(output, &panic_data);
+
+    let output = call_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        &contract_address,
+        &selector,
+        &[],
+    );
+    assert_success(output, &[Felt252::from(420)]);
+}
+
+#[test]
+fn mock_call_class() {
+    let mut cached_state = create_cached_state();
+    let mut cheatnet_state = CheatnetState::default();
+    let mut runtime_state = build_runtime_state(&mut cheatnet_state);
+
+    let contracts = get_contracts();
+    let class_hash = declare(&mut cached_state, "MockChecker", &contracts).unwrap();
+    let contract_address_1 = deploy_wrapper(
+        &mut cached_state,
+        &mut runtime_state,
+        &class_hash,
+        &[Felt252::from(420)],
+    )
+    .unwrap();
+
+    let selector = felt_selector_from_name("get_thing");
+
+    runtime_state.cheatnet_state.mock_call(
+        MockTarget::Class(class_hash),
+        selector.clone(),
+        MockCalldata::Any,
+        &[vec![Felt252::from(123)]],
+        CheatSpan::Indefinite,
+    );
+
+    // Deployed after the mock was started
+    let contract_address_2 = deploy_wrapper(
+        &mut cached_state,
+        &mut runtime_state,
+        &class_hash,
+        &[Felt252::from(420)],
+    )
+    .unwrap();
+
+    runtime_state.cheatnet_state.mock_call(
+        MockTarget::Contract(contract_address_2),
+        selector.clone(),
+        MockCalldata::Any,
+        &[vec![Felt252::from(321)]],
+        CheatSpan::Indefinite,
+    );
+
+    for (contract_address, expected) in [(contract_address_1, 123), (contract_address_2, 321)] {
+        let output = call_contract(
+            &mut cached_state,
+            &mut runtime_state,
+            &contract_address,
+            &selector,
+            &[],
+        );
+        assert_success(output, &[Felt252::from(expected)]);
+    }
+
+    runtime_state
+        .cheatnet_state
+        .stop_mocks(MockTarget::Class(class_hash), selector.clone());
+
+    let output = call_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        &contract_address_1,
+        &selector,
+        &[],
+    );
+    assert_success(output, &[Felt252::from(420)]);
+}
+
+#[test]
+fn mock_call_class_span_applies_to_each_contract() {
+    let mut cached_state = create_cached_state();
+    let mut cheatnet_state = CheatnetState::default();
+    let mut runtime_state = build_runtime_state(&mut cheatnet_state);
+
+    let contracts = get_contracts();
+    let class_hash = declare(&mut cached_state, "MockChecker", &contracts).unwrap();
+    let contract_address_1 = deploy_wrapper(
+        &mut cached_state,
+        &mut runtime_state,
+        &class_hash,
+        &[Felt252::from(420)],
+    )
+    .unwrap();
+    let contract_address_2 = deploy_wrapper(
+        &mut cached_state,
+        &mut runtime_state,
+        &class_hash,
+        &[Felt252::from(420)],
+    )
+    .unwrap();
+
+    let selector = felt_selector_from_name("get_thing");
+
+    runtime_state.cheatnet_state.mock_call(
+        MockTarget::Class(class_hash),
+        selector.clone(),
+        MockCalldata::Any,
+        &[vec![Felt252::from(123)], vec![Felt252::from(456)]],
+        CheatSpan::Number(2),
+    );
+
+    // Every contract of the class has its own span and sequence of results
+    for contract_address in [contract_address_1, contract_address_2] {
+        for expected in [123, 456, 420] {
+            let output = call_contract(
+                &mut cached_state,
+                &mut runtime_state,
+                &contract_address,
+                &selector,
+                &[],
+            );
+            assert_success(output, &[Felt252::from(expected)]);
+        }
+    }
+}
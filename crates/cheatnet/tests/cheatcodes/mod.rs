@@ -15,7 +15,9 @@ mod precalculate_address;
 mod replace_bytecode;
 mod roll;
 mod spoof;
+mod spy_calls;
 mod spy_events;
+mod spy_messages_to_l1;
 mod store;
 mod warp;
 
@@ -794,3 +794,89 @@ fn roll_all_span() {
         &[Felt252::from(DEFAULT_BLOCK_NUMBER)],
     );
 }
+
+#[test]
+fn roll_class() {
+    let mut cheatnet_state = CheatnetState::default();
+    let mut test_env = TestEnvironment::new(&mut cheatnet_state);
+
+    let contracts = get_contracts();
+    let class_hash = test_env.declare("RollChecker", &contracts);
+    let contract_address_1 = test_env.deploy_wrapper(&class_hash, &[]);
+    let other_contract_address = test_env.deploy("RollCheckerLibCall", &[]);
+
+    test_env.start_roll(CheatTarget::Class(class_hash), 123);
+
+    // Deployed after the cheat was started
+    let contract_address_2 = test_env.deploy_wrapper(&class_hash, &[]);
+
+    for contract_address in [contract_address_1, contract_address_2] {
+        assert_success(
+            test_env.call_contract(&contract_address, "get_block_number", &[]),
+            &[Felt252::from(123)],
+        );
+    }
+    assert_success(
+        test_env.call_contract(&other_contract_address, "get_block_number", &[]),
+        &[Felt252::from(DEFAULT_BLOCK_NUMBER)],
+    );
+
+    test_env
+        .runtime_state
+        .cheatnet_state
+        .stop_roll(CheatTarget::Class(class_hash));
+
+    for contract_address in [contract_address_1, contract_address_2] {
+        assert_success(
+            test_env.call_contract(&contract_address, "get_block_number", &[]),
+            &[Felt252::from(DEFAULT_BLOCK_NUMBER)],
+        );
+    }
+}
+
+#[test]
+fn roll_class_span() {
+    let mut cheatnet_state = CheatnetState::default();
+    let mut test_env = TestEnvironment::new(&mut cheatnet_state);
+
+    let contracts = get_contracts();
+    let class_hash = test_env.declare("RollChecker", &contracts);
+    let contract_address_1 = test_env.deploy_wrapper(&class_hash, &[]);
+    let contract_address_2 = test_env.deploy_wrapper(&class_hash, &[]);
+
+    test_env.roll(CheatTarget::Class(class_hash), 123, CheatSpan::Number(1));
+
+    for contract_address in [contract_address_1, contract_address_2] {
+        assert_success(
+            test_env.call_contract(&contract_address, "get_block_number", &[]),
+            &[Felt252::from(123)],
+        );
+        assert_success(
+            test_env.call_contract(&contract_address, "get_block_number", &[]),
+            &[Felt252::from(DEFAULT_BLOCK_NUMBER)],
+        );
+    }
+}
+
+#[test]
+fn roll_one_overrides_class() {
+    let mut cheatnet_state = CheatnetState::default();
+    let mut test_env = TestEnvironment::new(&mut cheatnet_state);
+
+    let contracts = get_contracts();
+    let class_hash = test_env.declare("RollChecker", &contracts);
+    let contract_address_1 = test_env.deploy_wrapper(&class_hash, &[]);
+    let contract_address_2 = test_env.deploy_wrapper(&class_hash, &[]);
+
+    test_env.start_roll(CheatTarget::Class(class_hash), 123);
+    test_env.start_roll(CheatTarget::One(contract_address_1), 321);
+
+    assert_success(
+        test_env.call_contract(&contract_address_1, "get_block_number", &[]),
+        &[Felt252::from(321)],
+    );
+    assert_success(
+        test_env.call_contract(&contract_address_2, "get_block_number", &[]),
+        &[Felt252::from(123)],
+    );
+}
@@ -0,0 +1,153 @@
+use crate::common::state::{build_runtime_state, create_cached_state};
+use crate::common::{call_contract, deploy_contract, felt_selector_from_name};
+use blockifier::execution::entry_point::CallType;
+use cairo_felt::Felt252;
+use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::CallResult;
+use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::spy_events::SpyTarget;
+use cheatnet::state::CheatnetState;
+use conversions::IntoConv;
+
+#[test]
+fn spy_calls_fetch() {
+    let mut cached_state = create_cached_state();
+    let mut cheatnet_state = CheatnetState::default();
+    let mut runtime_state = build_runtime_state(&mut cheatnet_state);
+
+    let contract_address = deploy_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        "MockChecker",
+        &[Felt252::from(420)],
+    );
+    let proxy_address = deploy_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        "MockCheckerProxy",
+        &[],
+    );
+
+    let id = runtime_state
+        .cheatnet_state
+        .spy_calls(SpyTarget::One(contract_address), vec![]);
+
+    call_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        &proxy_address,
+        &felt_selector_from_name("get_thing_from_contract"),
+        &[contract_address.into_()],
+    );
+
+    let calls = runtime_state.cheatnet_state.fetch_calls(&Felt252::from(id));
+
+    assert_eq!(calls.len(), 1, "There should be one call");
+    let call = &calls[0];
+    assert_eq!(call.to, contract_address);
+    assert_eq!(call.caller, proxy_address);
+    assert_eq!(call.selector, felt_selector_from_name("get_thing"));
+    assert!(call.calldata.is_empty());
+    assert_eq!(call.call_type, CallType::Call);
+    assert!(
+        matches!(&call.result, CallResult::Success { ret_data } if ret_data == &[Felt252::from(420)])
+    );
+
+    let calls = runtime_state.cheatnet_state.fetch_calls(&Felt252::from(id));
+    assert!(calls.is_empty(), "Calls should be fetched only once");
+}
+
+#[test]
+fn spy_calls_selectors() {
+    let mut cached_state = create_cached_state();
+    let mut cheatnet_state = CheatnetState::default();
+    let mut runtime_state = build_runtime_state(&mut cheatnet_state);
+
+    let contract_address = deploy_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        "MockChecker",
+        &[Felt252::from(420)],
+    );
+    let proxy_address = deploy_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        "MockCheckerProxy",
+        &[],
+    );
+
+    let id = runtime_state
+        .cheatnet_state
+        .spy_calls(SpyTarget::All, vec![felt_selector_from_name("get_thing")]);
+
+    call_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        &proxy_address,
+        &felt_selector_from_name("get_thing_from_contract"),
+        &[contract_address.into_()],
+    );
+
+    let calls = runtime_state.cheatnet_state.fetch_calls(&Felt252::from(id));
+
+    assert_eq!(
+        calls.len(),
+        1,
+        "Only the call of the spied selector should be recorded"
+    );
+    assert_eq!(calls[0].to, contract_address);
+}
+
+#[test]
+fn spy_calls_assert_called() {
+    let mut cached_state = create_cached_state();
+    let mut cheatnet_state = CheatnetState::default();
+    let mut runtime_state = build_runtime_state(&mut cheatnet_state);
+
+    let contract_address = deploy_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        "MockChecker",
+        &[Felt252::from(420)],
+    );
+
+    let id = Felt252::from(
+        runtime_state
+            .cheatnet_state
+            .spy_calls(SpyTarget::All, vec![]),
+    );
+    let selector = felt_selector_from_name("get_thing");
+
+    call_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        &contract_address,
+        &selector,
+        &[],
+    );
+
+    let cheatnet_state = &runtime_state.cheatnet_state;
+    assert!(cheatnet_state
+        .assert_called(&id, contract_address, &selector, &[], 1)
+        .is_ok());
+
+    let msg = cheatnet_state
+        .assert_called(&id, contract_address, &selector, &[], 2)
+        .unwrap_err();
+    assert!(msg.contains("Expected 2 call(s)"), "{msg}");
+    assert!(msg.contains("got 1"), "{msg}");
+
+    let msg = cheatnet_state
+        .assert_called(&id, contract_address, &selector, &[Felt252::from(1)], 1)
+        .unwrap_err();
+    assert!(msg.contains("calldata [] (differs at index 0)"), "{msg}");
+
+    let msg = cheatnet_state
+        .assert_called(
+            &id,
+            contract_address,
+            &felt_selector_from_name("get_constant_thing"),
+            &[],
+            1,
+        )
+        .unwrap_err();
+    assert!(msg.contains("No calls of the selector"), "{msg}");
+}
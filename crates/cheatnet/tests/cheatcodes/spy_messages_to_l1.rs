@@ -0,0 +1,127 @@
+use crate::common::state::{build_runtime_state, create_cached_state};
+use crate::common::{
+    call_contract, deploy_contract, deploy_wrapper, felt_selector_from_name, get_contracts,
+};
+use cairo_felt::Felt252;
+use cairo_vm::hint_processor::hint_processor_utils::felt_to_usize;
+use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::declare::declare;
+use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::spy_events::SpyTarget;
+use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::spy_messages_to_l1::MessageToL1;
+use cheatnet::state::CheatnetState;
+use conversions::IntoConv;
+
+fn felt_vec_to_message_vec(felts: &[Felt252]) -> Vec<MessageToL1> {
+    let mut messages = vec![];
+    let mut i = 0;
+    while i < felts.len() {
+        let from_address = felts[i].clone().into_();
+        let to_address = felts[i + 1].clone();
+        let payload_length = felt_to_usize(&felts[i + 2]).unwrap();
+        let payload = &felts[i + 3..i + 3 + payload_length];
+
+        messages.push(MessageToL1 {
+            from_address,
+            to_address,
+            payload: Vec::from(payload),
+        });
+
+        i = i + 3 + payload_length;
+    }
+
+    messages
+}
+
+#[test]
+fn spy_messages_to_l1_simple() {
+    let mut cached_state = create_cached_state();
+    let mut cheatnet_state = CheatnetState::default();
+    let mut runtime_state = build_runtime_state(&mut cheatnet_state);
+
+    let contract_address = deploy_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        "MessagesToL1Checker",
+        &[],
+    );
+
+    let id = runtime_state
+        .cheatnet_state
+        .spy_messages_to_l1(SpyTarget::All);
+
+    let selector = felt_selector_from_name("send_message");
+    call_contract(
+        &mut cached_state,
+        &mut runtime_state,
+        &contract_address,
+        &selector,
+        &[
+            Felt252::from(123),
+            Felt252::from(2),
+            Felt252::from(321),
+            Felt252::from(420),
+        ],
+    );
+
+    let (length, serialized_messages) = runtime_state
+        .cheatnet_state
+        .fetch_messages_to_l1(&Felt252::from(id));
+    let messages = felt_vec_to_message_vec(&serialized_messages);
+
+    assert_eq!(length, 1, "There should be one message");
+    assert_eq!(
+        messages.len(),
+        length,
+        "Length after serialization should be the same"
+    );
+    assert_eq!(
+        messages[0],
+        MessageToL1 {
+            from_address: contract_address,
+            to_address: Felt252::from(123),
+            payload: vec![Felt252::from(321), Felt252::from(420)],
+        },
+        "Wrong message"
+    );
+
+    let (length, _) = runtime_state
+        .cheatnet_state
+        .fetch_messages_to_l1(&Felt252::from(id));
+    assert_eq!(length, 0, "There should be no new messages");
+}
+
+#[test]
+fn check_messages_captured_only_for_spied_contracts() {
+    let mut cached_state = create_cached_state();
+    let mut cheatnet_state = CheatnetState::default();
+    let mut runtime_state = build_runtime_state(&mut cheatnet_state);
+
+    let contracts = get_contracts();
+    let class_hash = declare(&mut cached_state, "MessagesToL1Checker", &contracts).unwrap();
+    let contract_address_1 =
+        deploy_wrapper(&mut cached_state, &mut runtime_state, &class_hash, &[]).unwrap();
+    let contract_address_2 =
+        deploy_wrapper(&mut cached_state, &mut runtime_state, &class_hash, &[]).unwrap();
+
+    let id = runtime_state
+        .cheatnet_state
+        .spy_messages_to_l1(SpyTarget::One(contract_address_2));
+
+    let selector = felt_selector_from_name("send_message");
+    for contract_address in [contract_address_1, contract_address_2] {
+        call_contract(
+            &mut cached_state,
+            &mut runtime_state,
+            &contract_address,
+            &selector,
+            &[Felt252::from(123), Felt252::from(1), Felt252::from(321)],
+        );
+    }
+
+    let (length, serialized_messages) = runtime_state
+        .cheatnet_state
+        .fetch_messages_to_l1(&Felt252::from(id));
+    let messages = felt_vec_to_message_vec(&serialized_messages);
+
+    assert_eq!(length, 1, "There should be one message");
+    assert_eq!(messages[0].from_address, contract_address_2);
+}
@@ -1,5 +1,6 @@
 mod common;
 mod events;
+mod messages_to_l1;
 mod get_class_hash;
 mod mock;
 mod prank;
@@ -0,0 +1 @@
+mod messages_to_l1_checker;
@@ -0,0 +1,19 @@
+#[starknet::interface]
+trait IMessagesToL1Checker<TContractState> {
+    fn send_message(ref self: TContractState, to_address: felt252, payload: Array<felt252>);
+}
+
+#[starknet::contract]
+mod MessagesToL1Checker {
+    use starknet::SyscallResultTrait;
+
+    #[storage]
+    struct Storage {}
+
+    #[abi(embed_v0)]
+    impl IMessagesToL1Checker of super::IMessagesToL1Checker<ContractState> {
+        fn send_message(ref self: ContractState, to_address: felt252, payload: Array<felt252>) {
+            starknet::send_message_to_l1_syscall(to_address, payload.span()).unwrap_syscall();
+        }
+    }
+}
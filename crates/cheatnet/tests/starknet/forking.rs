@@ -5,13 +5,14 @@ use crate::common::state::{
 };
 use crate::common::{call_contract, deploy_contract, deploy_wrapper, felt_selector_from_name};
 use blockifier::state::cached_state::{CachedState, GlobalContractCache};
+use blockifier::state::state_api::StateReader;
 use cairo_felt::Felt252;
 use cairo_vm::vm::errors::hint_errors::HintError;
 use cheatnet::constants::build_testing_state;
 use cheatnet::forking::state::ForkStateReader;
 use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::RuntimeState;
 use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::CheatcodeError;
-use cheatnet::state::{BlockInfoReader, CheatnetState, ExtendedStateReader};
+use cheatnet::state::{BlockInfoReader, Cancellation, CheatnetState, ExtendedStateReader};
 use conversions::{IntoConv, TryIntoConv};
 use num_bigint::BigUint;
 use num_traits::Num;
@@ -739,3 +740,25 @@ fn test_calling_nonexistent_url() {
         "Unable to reach the node. Check your internet connection and node url",
     );
 }
+
+#[test]
+fn cancelled_fork_reader_does_not_send_requests() {
+    let temp_dir = TempDir::new().unwrap();
+    let nonexistent_url = "http://188.34.188.184:9546".parse().unwrap();
+    let cancellation = Cancellation::default();
+    let mut fork_state_reader = ForkStateReader::new(
+        nonexistent_url,
+        BlockNumber(1),
+        temp_dir.path().to_str().unwrap(),
+    )
+    .with_cancellation(cancellation.clone());
+
+    cancellation.cancel();
+    let err = fork_state_reader
+        .get_nonce_at(ContractAddress::default())
+        .unwrap_err();
+
+    assert!(err
+        .to_string()
+        .contains("Run was cancelled, requests to the fork are not sent"));
+}
@@ -0,0 +1,351 @@
+use crate::call_trace::{contract_name, function_name};
+use crate::contracts_data::ContractsData;
+use crate::expected_result::decode_panic_data;
+use cairo_felt::Felt252;
+use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::{
+    CallFailure, CallResult,
+};
+use cheatnet::state::CallTrace;
+use conversions::FromConv;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+/// Failed contract call in a backtrace
+#[derive(Debug, Clone, PartialEq)]
+struct Frame {
+    contract_name: String,
+    function_name: String,
+    contract_address: Felt252,
+    caller_address: Felt252,
+    failure: String,
+}
+
+impl Frame {
+    fn new(call_trace: &CallTrace, failure: &CallFailure, contracts_data: &ContractsData) -> Self {
+        let entry_point = &call_trace.entry_point;
+        let failure = match failure {
+            CallFailure::Panic { panic_data } => {
+                format!("panicked with {}", decode_panic_data(panic_data))
+            }
+            CallFailure::Error { msg } => format!("failed with {msg}"),
+        };
+
+        Self {
+            contract_name: contract_name(entry_point, contracts_data),
+            function_name: function_name(entry_point, contracts_data),
+            contract_address: Felt252::from_(entry_point.storage_address),
+            caller_address: Felt252::from_(entry_point.caller_address),
+            failure,
+        }
+    }
+}
+
+/// Chain of failed contract calls, from the call made by the test to the call which originally failed
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Backtrace(Vec<Frame>);
+
+impl Backtrace {
+    /// A failure of a call is propagated to its caller right away, so only the last call
+    /// made at each level of the call trace can be a part of the backtrace.
+    /// The call is a part of it only if its failure caused the failure of its caller,
+    /// calls whose failures were handled, e.g. with a safe dispatcher, are skipped.
+    /// Returns `None` if the test did not fail because of a contract call.
+    pub(crate) fn from_call_trace(
+        call_trace: &Rc<RefCell<CallTrace>>,
+        test_failure: &CallFailure,
+        contracts_data: &ContractsData,
+    ) -> Option<Self> {
+        let mut frames = vec![];
+        let mut caller_failure = test_failure.clone();
+        let mut current = call_trace.borrow().nested_calls.last().cloned();
+
+        while let Some(call) = current {
+            let call = call.borrow();
+            let CallResult::Failure(failure) = &call.result else {
+                break;
+            };
+            if !is_propagated(failure, &caller_failure) {
+                break;
+            }
+            frames.push(Frame::new(&call, failure, contracts_data));
+            caller_failure = failure.clone();
+            current = call.nested_calls.last().cloned();
+        }
+
+        (!frames.is_empty()).then_some(Self(frames))
+    }
+}
+
+/// Whether the failure of a call was propagated to its caller.
+/// Errors cannot be handled, while the panic data of a propagated panic is a part of the panic data of the caller.
+fn is_propagated(failure: &CallFailure, caller_failure: &CallFailure) -> bool {
+    match (failure, caller_failure) {
+        (CallFailure::Error { .. }, _) => true,
+        (CallFailure::Panic { .. }, CallFailure::Error { .. }) => false,
+        (
+            CallFailure::Panic { panic_data },
+            CallFailure::Panic {
+                panic_data: caller_panic_data,
+            },
+        ) => {
+            panic_data.is_empty()
+                || caller_panic_data
+                    .windows(panic_data.len())
+                    .any(|window| window == panic_data.as_slice())
+        }
+    }
+}
+
+impl fmt::Display for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n    Backtrace (outermost call first):")?;
+        for (index, frame) in self.0.iter().enumerate() {
+            let origin = if index + 1 == self.0.len() {
+                " <- failed here"
+            } else {
+                ""
+            };
+            writeln!(
+                f,
+                "        {}::{} (contract address: {:#x}, caller address: {:#x}){origin}",
+                frame.contract_name,
+                frame.function_name,
+                frame.contract_address.to_biguint(),
+                frame.caller_address.to_biguint()
+            )?;
+            writeln!(f, "            {}", frame.failure)?;
+        }
+        Ok(())
+    }
+}
+
+/// Appends the backtrace of the failed contract calls to the failure message of the test
+pub(crate) fn with_backtrace(
+    msg: Option<String>,
+    test_failure: &CallFailure,
+    call_trace: &Rc<RefCell<CallTrace>>,
+    contracts_data: &ContractsData,
+) -> Option<String> {
+    match Backtrace::from_call_trace(call_trace, test_failure, contracts_data) {
+        Some(backtrace) => Some(format!("{}{backtrace}", msg.unwrap_or_default())),
+        None => msg,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bimap::BiMap;
+    use blockifier::execution::entry_point::CallEntryPoint;
+    use cheatnet::constants::build_test_entry_point;
+    use conversions::IntoConv;
+    use starknet::core::utils::get_selector_from_name;
+    use starknet_api::core::{ClassHash, ContractAddress, PatriciaKey};
+    use starknet_api::hash::StarkHash;
+    use starknet_api::{class_hash, contract_address, patricia_key};
+    use std::collections::HashMap;
+
+    fn call(
+        class_hash: ClassHash,
+        function_name: &str,
+        contract_address: ContractAddress,
+        caller_address: ContractAddress,
+        result: CallResult,
+        nested_calls: Vec<Rc<RefCell<CallTrace>>>,
+    ) -> Rc<RefCell<CallTrace>> {
+        Rc::new(RefCell::new(CallTrace {
+            entry_point: CallEntryPoint {
+                class_hash: Some(class_hash),
+                entry_point_selector: get_selector_from_name(function_name).unwrap().into_(),
+                storage_address: contract_address,
+                caller_address,
+                ..build_test_entry_point()
+            },
+            used_execution_resources: Default::default(),
+            used_l1_resources: Default::default(),
+            nested_calls,
+            result,
+            cheated_data: Default::default(),
+            executed_pcs: vec![],
+        }))
+    }
+
+    fn contracts_data() -> ContractsData {
+        ContractsData {
+            contracts: HashMap::new(),
+            class_hashes: BiMap::from_iter([
+                ("Proxy".to_string(), class_hash!("0x10")),
+                ("Bank".to_string(), class_hash!("0x20")),
+            ]),
+            selectors: ["forward", "withdraw", "deposit"]
+                .into_iter()
+                .map(|name| {
+                    (
+                        get_selector_from_name(name).unwrap().into_(),
+                        name.to_string(),
+                    )
+                })
+                .collect(),
+            functions: HashMap::new(),
+            abis: HashMap::new(),
+        }
+    }
+
+    fn panicked(data: &[u8]) -> CallResult {
+        CallResult::Failure(CallFailure::Panic {
+            panic_data: vec![Felt252::from_bytes_be(data)],
+        })
+    }
+
+    #[test]
+    fn follows_failed_calls_to_the_origin() {
+        let test_address = contract_address!("0x1");
+        let proxy_address = contract_address!("0x2");
+        let bank_address = contract_address!("0x3");
+        let trace = call(
+            class_hash!("0x1"),
+            "test",
+            test_address,
+            contract_address!("0x0"),
+            CallResult::Success { ret_data: vec![] },
+            vec![
+                call(
+                    class_hash!("0x20"),
+                    "deposit",
+                    bank_address,
+                    test_address,
+                    CallResult::Success { ret_data: vec![] },
+                    vec![],
+                ),
+                call(
+                    class_hash!("0x10"),
+                    "forward",
+                    proxy_address,
+                    test_address,
+                    panicked(b"Insufficient balance"),
+                    vec![call(
+                        class_hash!("0x20"),
+                        "withdraw",
+                        bank_address,
+                        proxy_address,
+                        panicked(b"Insufficient balance"),
+                        vec![],
+                    )],
+                ),
+            ],
+        );
+
+        let test_failure = CallFailure::Panic {
+            panic_data: vec![Felt252::from_bytes_be(b"Insufficient balance")],
+        };
+        let backtrace =
+            Backtrace::from_call_trace(&trace, &test_failure, &contracts_data()).unwrap();
+
+        assert_eq!(
+            backtrace.to_string(),
+            "
+    Backtrace (outermost call first):
+        Proxy::forward (contract address: 0x2, caller address: 0x1)
+            panicked with Insufficient balance
+        Bank::withdraw (contract address: 0x3, caller address: 0x2) <- failed here
+            panicked with Insufficient balance
+"
+        );
+    }
+
+    #[test]
+    fn no_backtrace_when_calls_succeeded() {
+        let trace = call(
+            class_hash!("0x1"),
+            "test",
+            contract_address!("0x1"),
+            contract_address!("0x0"),
+            CallResult::Success { ret_data: vec![] },
+            vec![call(
+                class_hash!("0x20"),
+                "withdraw",
+                contract_address!("0x3"),
+                contract_address!("0x1"),
+                CallResult::Success { ret_data: vec![] },
+                vec![],
+            )],
+        );
+
+        let test_failure = CallFailure::Panic {
+            panic_data: vec![Felt252::from_bytes_be(b"failing check")],
+        };
+        assert!(Backtrace::from_call_trace(&trace, &test_failure, &contracts_data()).is_none());
+    }
+
+    #[test]
+    fn no_backtrace_when_failure_was_handled() {
+        let trace = call(
+            class_hash!("0x1"),
+            "test",
+            contract_address!("0x1"),
+            contract_address!("0x0"),
+            CallResult::Success { ret_data: vec![] },
+            vec![call(
+                class_hash!("0x20"),
+                "withdraw",
+                contract_address!("0x3"),
+                contract_address!("0x1"),
+                panicked(b"Insufficient balance"),
+                vec![],
+            )],
+        );
+        // Failure of the call was handled with a safe dispatcher, the test failed on its own assertion
+        let test_failure = CallFailure::Panic {
+            panic_data: vec![Felt252::from_bytes_be(b"unexpected balance")],
+        };
+
+        assert!(Backtrace::from_call_trace(&trace, &test_failure, &contracts_data()).is_none());
+    }
+
+    #[test]
+    fn backtrace_stops_at_handled_failure() {
+        let test_address = contract_address!("0x1");
+        let proxy_address = contract_address!("0x2");
+        let trace = call(
+            class_hash!("0x1"),
+            "test",
+            test_address,
+            contract_address!("0x0"),
+            CallResult::Success { ret_data: vec![] },
+            vec![call(
+                class_hash!("0x10"),
+                "forward",
+                proxy_address,
+                test_address,
+                panicked(b"Withdrawal failed"),
+                vec![call(
+                    class_hash!("0x20"),
+                    "withdraw",
+                    contract_address!("0x3"),
+                    proxy_address,
+                    panicked(b"Insufficient balance"),
+                    vec![],
+                )],
+            )],
+        );
+        let test_failure = CallFailure::Panic {
+            panic_data: vec![
+                Felt252::from_bytes_be(b"Withdrawal failed"),
+                Felt252::from_bytes_be(b"ENTRYPOINT_FAILED"),
+            ],
+        };
+
+        let backtrace =
+            Backtrace::from_call_trace(&trace, &test_failure, &contracts_data()).unwrap();
+
+        assert_eq!(
+            backtrace.to_string(),
+            "
+    Backtrace (outermost call first):
+        Proxy::forward (contract address: 0x2, caller address: 0x1) <- failed here
+            panicked with Withdrawal failed
+"
+        );
+    }
+}
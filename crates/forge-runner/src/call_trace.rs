@@ -0,0 +1,475 @@
+use crate::contracts_data::ContractsData;
+use crate::expected_result::decode_panic_data;
+use crate::fuzzer::{deserialize_arguments, CairoType, CairoValue};
+use blockifier::execution::entry_point::{CallEntryPoint, CallType};
+use cairo_felt::Felt252;
+use cairo_lang_utils::byte_array::BYTE_ARRAY_MAGIC;
+use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::{
+    CallFailure, CallResult,
+};
+use cheatnet::state::{CallTrace, CheatedData};
+use conversions::FromConv;
+use num_traits::Num;
+use runtime::utils::try_deserialize_byte_array;
+use starknet::core::types::contract::AbiEntry;
+use starknet_api::deprecated_contract_class::EntryPointType;
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::rc::Rc;
+
+const INDENT: &str = "    ";
+
+/// Name of the contract called at the entry point, its class hash if the contract is not known
+pub(crate) fn contract_name(
+    entry_point: &CallEntryPoint,
+    contracts_data: &ContractsData,
+) -> String {
+    entry_point.class_hash.map_or_else(
+        || "<unknown>".to_string(),
+        |class_hash| {
+            contracts_data
+                .class_hashes
+                .get_by_right(&class_hash)
+                .cloned()
+                .unwrap_or_else(|| format!("{:#x}", Felt252::from_(class_hash).to_biguint()))
+        },
+    )
+}
+
+/// Name of the function called at the entry point, its selector if the function is not known
+pub(crate) fn function_name(
+    entry_point: &CallEntryPoint,
+    contracts_data: &ContractsData,
+) -> String {
+    contracts_data
+        .selectors
+        .get(&entry_point.entry_point_selector)
+        .cloned()
+        .unwrap_or_else(|| {
+            format!(
+                "{:#x}",
+                Felt252::from_(entry_point.entry_point_selector).to_biguint()
+            )
+        })
+}
+
+/// Tree of the contract calls made by the test, with calldata and return data decoded using the contract ABIs
+pub(crate) fn render_call_trace(
+    test_name: &str,
+    call_trace: &Rc<RefCell<CallTrace>>,
+    contracts_data: &ContractsData,
+) -> String {
+    let call_trace = call_trace.borrow();
+    let mut rendered = format!(
+        "\n\nCall trace:\n{INDENT}[steps: {}] {test_name}\n",
+        call_trace.used_execution_resources.vm_resources.n_steps
+    );
+    for call in &call_trace.nested_calls {
+        render_call(&call.borrow(), contracts_data, 2, &mut rendered);
+    }
+    rendered
+}
+
+fn render_call(
+    call: &CallTrace,
+    contracts_data: &ContractsData,
+    depth: usize,
+    rendered: &mut String,
+) {
+    let entry_point = &call.entry_point;
+    let contract_name = contract_name(entry_point, contracts_data);
+    let function_name = function_name(entry_point, contracts_data);
+    let abi = contracts_data
+        .abis
+        .get(&contract_name)
+        .map_or(&[][..], Vec::as_slice);
+    let function = function_abi(abi, &function_name);
+    let calldata: Vec<Felt252> = entry_point
+        .calldata
+        .0
+        .iter()
+        .map(|felt| Felt252::from_(*felt))
+        .collect();
+
+    let inputs = function
+        .as_ref()
+        .and_then(|function| {
+            let (names, types): (Vec<_>, Vec<_>) = function.inputs.iter().copied().unzip();
+            decode(&types, &calldata, abi).map(|values| {
+                names
+                    .iter()
+                    .zip(values)
+                    .map(|(name, value)| format!("{name}: {value}"))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .unwrap_or_else(|| format_felts(&calldata))
+        .join(", ");
+
+    let indent = INDENT.repeat(depth);
+    writeln!(
+        rendered,
+        "{indent}[steps: {}] {contract_name}::{function_name}({inputs}) ({}, contract address: {:#x}, caller address: {:#x}{})",
+        call.used_execution_resources.vm_resources.n_steps,
+        call_kind(entry_point),
+        Felt252::from_(entry_point.storage_address).to_biguint(),
+        Felt252::from_(entry_point.caller_address).to_biguint(),
+        format_cheats(&call.cheated_data)
+    )
+    .unwrap();
+
+    for nested_call in &call.nested_calls {
+        render_call(&nested_call.borrow(), contracts_data, depth + 1, rendered);
+    }
+
+    let result = match &call.result {
+        CallResult::Success { ret_data } => {
+            let outputs = function
+                .as_ref()
+                .and_then(|function| decode(&function.outputs, ret_data, abi))
+                .unwrap_or_else(|| format_felts(ret_data));
+            format!("returned ({})", outputs.join(", "))
+        }
+        CallResult::Failure(CallFailure::Panic { panic_data }) => {
+            format!("panicked with {}", decode_panic_data(panic_data))
+        }
+        CallResult::Failure(CallFailure::Error { msg }) => format!("failed with {msg}"),
+    };
+    writeln!(rendered, "{indent}{INDENT}<- {result}").unwrap();
+}
+
+fn call_kind(entry_point: &CallEntryPoint) -> &'static str {
+    match (entry_point.entry_point_type, entry_point.call_type) {
+        (EntryPointType::Constructor, _) => "constructor",
+        (EntryPointType::L1Handler, _) => "l1 handler",
+        (EntryPointType::External, CallType::Call) => "call",
+        (EntryPointType::External, CallType::Delegate) => "library call",
+    }
+}
+
+fn format_cheats(cheated_data: &CheatedData) -> String {
+    let mut cheats = vec![];
+    if let Some(caller_address) = cheated_data.caller_address {
+        cheats.push(format!(
+            "prank: {:#x}",
+            Felt252::from_(caller_address).to_biguint()
+        ));
+    }
+    if let Some(block_number) = &cheated_data.block_number {
+        cheats.push(format!("roll: {block_number}"));
+    }
+    if let Some(block_timestamp) = &cheated_data.block_timestamp {
+        cheats.push(format!("warp: {block_timestamp}"));
+    }
+    if let Some(sequencer_address) = cheated_data.sequencer_address {
+        cheats.push(format!(
+            "elect: {:#x}",
+            Felt252::from_(sequencer_address).to_biguint()
+        ));
+    }
+    if cheated_data.tx_info.is_some() {
+        cheats.push("spoof".to_string());
+    }
+
+    if cheats.is_empty() {
+        String::new()
+    } else {
+        format!(", cheats: [{}]", cheats.join(", "))
+    }
+}
+
+/// Types of the inputs and outputs of a contract function, as declared in the contract ABI
+struct FunctionAbi<'a> {
+    inputs: Vec<(&'a str, &'a str)>,
+    outputs: Vec<&'a str>,
+}
+
+fn function_abi<'a>(abi: &'a [AbiEntry], function_name: &str) -> Option<FunctionAbi<'a>> {
+    abi.iter()
+        .flat_map(|entry| match entry {
+            AbiEntry::Interface(interface) => interface.items.iter().collect(),
+            _ => vec![entry],
+        })
+        .find_map(|entry| match entry {
+            AbiEntry::Function(function) | AbiEntry::L1Handler(function)
+                if function.name == function_name =>
+            {
+                Some(FunctionAbi {
+                    inputs: function
+                        .inputs
+                        .iter()
+                        .map(|input| (input.name.as_str(), input.r#type.as_str()))
+                        .collect(),
+                    outputs: function
+                        .outputs
+                        .iter()
+                        .map(|output| output.r#type.as_str())
+                        .collect(),
+                })
+            }
+            AbiEntry::Constructor(constructor) if constructor.name == function_name => {
+                Some(FunctionAbi {
+                    inputs: constructor
+                        .inputs
+                        .iter()
+                        .map(|input| (input.name.as_str(), input.r#type.as_str()))
+                        .collect(),
+                    outputs: vec![],
+                })
+            }
+            _ => None,
+        })
+}
+
+/// Values of the given ABI types read from the felts, `None` if the felts do not match the types
+fn decode(types: &[&str], felts: &[Felt252], abi: &[AbiEntry]) -> Option<Vec<String>> {
+    let types = types
+        .iter()
+        .map(|ty| CairoType::from_abi_type(ty, abi))
+        .collect::<Option<Vec<_>>>()?;
+    let values = deserialize_arguments(&types, felts.to_vec())?;
+
+    Some(
+        types
+            .iter()
+            .zip(&values)
+            .map(|(ty, value)| format_value(ty, value))
+            .collect(),
+    )
+}
+
+fn format_value(ty: &CairoType, value: &CairoValue) -> String {
+    match (ty, value) {
+        (CairoType::U256, CairoValue::Struct(limbs)) => match &limbs[..] {
+            [CairoValue::Felt(low), CairoValue::Felt(high)] => {
+                ((high.to_biguint() << 128) + low.to_biguint()).to_string()
+            }
+            _ => unreachable!("u256 is made of two felts"),
+        },
+        (CairoType::ByteArray, _) => {
+            let mut felts = vec![Felt252::from_str_radix(BYTE_ARRAY_MAGIC, 16).unwrap()];
+            felts.extend(value.serialize());
+            try_deserialize_byte_array(&felts).map_or_else(
+                || format!("[{}]", format_felts(&value.serialize()).join(", ")),
+                |string| format!("{string:?}"),
+            )
+        }
+        (CairoType::Array(element), CairoValue::Array(elements)) => format!(
+            "[{}]",
+            elements
+                .iter()
+                .map(|value| format_value(element, value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        (CairoType::Struct(members), CairoValue::Struct(values)) => format!(
+            "({})",
+            members
+                .iter()
+                .zip(values)
+                .map(|(ty, value)| format_value(ty, value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        (CairoType::Enum(variants), CairoValue::Enum { variant, value, .. }) => {
+            match &variants[*variant] {
+                CairoType::Struct(members) if members.is_empty() => format!("variant {variant}"),
+                ty => format!("variant {variant}({})", format_value(ty, value)),
+            }
+        }
+        (
+            CairoType::I8 | CairoType::I16 | CairoType::I32 | CairoType::I64 | CairoType::I128,
+            CairoValue::Felt(felt),
+        ) => felt.to_signed_felt().to_string(),
+        (
+            CairoType::U8 | CairoType::U16 | CairoType::U32 | CairoType::U64 | CairoType::U128,
+            CairoValue::Felt(felt),
+        ) => felt.to_biguint().to_string(),
+        (_, CairoValue::Felt(felt)) => format!("{:#x}", felt.to_biguint()),
+        _ => unreachable!("{value:?} is not a value of {ty:?}"),
+    }
+}
+
+fn format_felts(felts: &[Felt252]) -> Vec<String> {
+    felts
+        .iter()
+        .map(|felt| format!("{:#x}", felt.to_biguint()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TraceVerbosity;
+    use bimap::BiMap;
+    use cheatnet::constants::build_test_entry_point;
+    use conversions::IntoConv;
+    use starknet::core::utils::get_selector_from_name;
+    use starknet_api::core::{ClassHash, ContractAddress, PatriciaKey};
+    use starknet_api::hash::StarkHash;
+    use starknet_api::transaction::Calldata;
+    use starknet_api::{class_hash, contract_address, patricia_key};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn call(
+        class_hash: ClassHash,
+        function_name: &str,
+        calldata: &[u128],
+        caller_address: ContractAddress,
+        result: CallResult,
+        nested_calls: Vec<Rc<RefCell<CallTrace>>>,
+    ) -> Rc<RefCell<CallTrace>> {
+        let mut call_trace = CallTrace {
+            entry_point: CallEntryPoint {
+                class_hash: Some(class_hash),
+                entry_point_selector: get_selector_from_name(function_name).unwrap().into_(),
+                calldata: Calldata(Arc::new(
+                    calldata
+                        .iter()
+                        .map(|felt| Felt252::from(*felt).into_())
+                        .collect(),
+                )),
+                storage_address: contract_address!("0x3"),
+                caller_address,
+                ..build_test_entry_point()
+            },
+            used_execution_resources: Default::default(),
+            used_l1_resources: Default::default(),
+            nested_calls,
+            result,
+            cheated_data: Default::default(),
+            executed_pcs: vec![],
+        };
+        call_trace.used_execution_resources.vm_resources.n_steps = 100;
+        Rc::new(RefCell::new(call_trace))
+    }
+
+    fn contracts_data() -> ContractsData {
+        let abi: Vec<AbiEntry> = serde_json::from_str(
+            r#"[
+                {"type": "interface", "name": "tests::IBank", "items": [
+                    {"type": "function", "name": "transfer", "inputs": [
+                        {"name": "to", "type": "core::starknet::contract_address::ContractAddress"},
+                        {"name": "amount", "type": "core::integer::u256"},
+                        {"name": "memos", "type": "core::array::Span::<core::felt252>"}
+                    ], "outputs": [
+                        {"type": "core::integer::i32"}
+                    ], "state_mutability": "external"}
+                ]}
+            ]"#,
+        )
+        .unwrap();
+
+        ContractsData {
+            contracts: HashMap::new(),
+            class_hashes: BiMap::from_iter([("Bank".to_string(), class_hash!("0x20"))]),
+            selectors: ["transfer", "withdraw"]
+                .into_iter()
+                .map(|name| {
+                    (
+                        get_selector_from_name(name).unwrap().into_(),
+                        name.to_string(),
+                    )
+                })
+                .collect(),
+            functions: HashMap::new(),
+            abis: HashMap::from([("Bank".to_string(), abi)]),
+        }
+    }
+
+    #[test]
+    fn renders_decoded_calls() {
+        let transfer = call(
+            class_hash!("0x20"),
+            "transfer",
+            &[0x5, 7, 1, 2, 0x10, 0x11],
+            contract_address!("0x1"),
+            CallResult::Success {
+                ret_data: vec![Felt252::from(-2)],
+            },
+            vec![],
+        );
+        transfer.borrow_mut().cheated_data = CheatedData {
+            block_number: Some(Felt252::from(10)),
+            caller_address: Some(contract_address!("0x123")),
+            ..Default::default()
+        };
+        let withdraw = call(
+            class_hash!("0x20"),
+            "withdraw",
+            &[0x64],
+            contract_address!("0x1"),
+            CallResult::Failure(CallFailure::Panic {
+                panic_data: vec![Felt252::from_bytes_be(b"Insufficient balance")],
+            }),
+            vec![],
+        );
+        withdraw.borrow_mut().entry_point.call_type = CallType::Delegate;
+        let trace = call(
+            class_hash!("0x1"),
+            "test",
+            &[],
+            contract_address!("0x0"),
+            CallResult::Success { ret_data: vec![] },
+            vec![transfer, withdraw],
+        );
+
+        assert_eq!(
+            render_call_trace("tests::test_transfer", &trace, &contracts_data()),
+            "
+
+Call trace:
+    [steps: 100] tests::test_transfer
+        [steps: 100] Bank::transfer(to: 0x5, amount: 340282366920938463463374607431768211463, memos: [0x10, 0x11]) (call, contract address: 0x3, caller address: 0x1, cheats: [prank: 0x123, roll: 10])
+            <- returned (-2)
+        [steps: 100] Bank::withdraw(0x64) (library call, contract address: 0x3, caller address: 0x1)
+            <- panicked with Insufficient balance
+"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_raw_felts() {
+        let trace = call(
+            class_hash!("0x1"),
+            "test",
+            &[],
+            contract_address!("0x0"),
+            CallResult::Success { ret_data: vec![] },
+            vec![call(
+                class_hash!("0x30"),
+                "transfer",
+                &[0x5],
+                contract_address!("0x1"),
+                CallResult::Success {
+                    ret_data: vec![Felt252::from(1)],
+                },
+                vec![],
+            )],
+        );
+
+        assert_eq!(
+            render_call_trace("tests::test_transfer", &trace, &contracts_data()),
+            "
+
+Call trace:
+    [steps: 100] tests::test_transfer
+        [steps: 100] 0x30::transfer(0x5) (call, contract address: 0x3, caller address: 0x1)
+            <- returned (0x1)
+"
+        );
+    }
+
+    #[test]
+    fn prints_traces_from_third_verbosity_level() {
+        assert_eq!(TraceVerbosity::from_verbosity(0), None);
+        assert_eq!(TraceVerbosity::from_verbosity(2), None);
+        assert_eq!(
+            TraceVerbosity::from_verbosity(3),
+            Some(TraceVerbosity::Failed)
+        );
+        assert_eq!(TraceVerbosity::from_verbosity(4), Some(TraceVerbosity::All));
+        assert_eq!(TraceVerbosity::from_verbosity(5), Some(TraceVerbosity::All));
+    }
+}
@@ -1,13 +1,26 @@
 use crate::expected_result::ExpectedTestResult;
+use cairo_felt::Felt252;
 use cairo_lang_sierra::{ids::GenericTypeId, program::Program};
 use serde::Deserialize;
 use starknet_api::block::BlockNumber;
+use std::time::Duration;
 use url::Url;
 
 #[derive(Debug, Clone)]
 pub struct CompiledTestCrateRunnable {
     pub sierra_program: Program,
     pub test_cases: Vec<TestCaseRunnable>,
+    pub tests_location: CrateLocation,
+    /// Name of the package the test crate belongs to
+    pub package_name: String,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Deserialize)]
+pub enum CrateLocation {
+    /// Main crate in a package
+    Lib,
+    /// Crate in the `tests/` directory
+    Tests,
 }
 
 #[derive(Debug, Clone)]
@@ -19,6 +32,34 @@ pub struct TestCaseRunnable {
     pub fork_config: Option<ValidatedForkConfig>,
     pub fuzzer_config: Option<FuzzerConfig>,
     pub test_details: TestDetails,
+    /// Arguments of a case of a parametrized test, the test is fuzzed if it takes arguments and has none
+    pub fixed_arguments: Option<FixedArguments>,
+    /// Timeout of the test, overrides `RunnerConfig::timeout`
+    pub timeout: Option<Duration>,
+    /// Whether the test executes random sequences of calls to target contracts
+    pub invariant: bool,
+    /// Setup fixture the test starts from the state of, `None` if it starts from the clean state
+    pub setup_fixture: Option<String>,
+    /// Whether the test is a setup fixture, it is run before the tests using it and not reported as a test
+    pub is_setup_fixture: bool,
+}
+
+impl TestCaseRunnable {
+    /// Name of the test function, without the name of the case for parametrized tests
+    #[must_use]
+    pub fn function_name(&self) -> &str {
+        self.fixed_arguments
+            .as_ref()
+            .map_or(&self.name, |arguments| &arguments.function_name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedArguments {
+    /// Name of the parametrized test function
+    pub function_name: String,
+    /// Serialized values of all arguments of the test function
+    pub calldata: Vec<Felt252>,
 }
 
 #[derive(Debug, PartialEq, Clone, Deserialize, Default)]
@@ -15,6 +15,10 @@ pub struct ContractsData {
     pub contracts: HashMap<String, StarknetContractArtifacts>,
     pub class_hashes: BiMap<String, ClassHash>,
     pub selectors: HashMap<EntryPointSelector, String>,
+    /// Names of functions from the ABI of each contract
+    pub functions: HashMap<String, Vec<String>>,
+    /// ABI of each contract
+    pub abis: HashMap<String, Vec<AbiEntry>>,
 }
 
 impl ContractsData {
@@ -29,26 +33,45 @@ impl ContractsData {
             .map(|(name, sierra_class)| Ok((name.clone(), get_class_hash(sierra_class)?)))
             .collect::<Result<_>>()?;
 
-        let selectors = parsed_contracts
+        let abis: HashMap<String, Vec<AbiEntry>> = parsed_contracts
             .into_par_iter()
-            .map(|(_, sierra_class)| build_name_selector_map(sierra_class.abi))
-            .flatten()
+            .map(|(name, sierra_class)| (name, sierra_class.abi))
+            .collect();
+
+        let selector_maps: Vec<(String, HashMap<EntryPointSelector, String>)> = abis
+            .par_iter()
+            .map(|(name, abi)| (name.clone(), build_name_selector_map(abi)))
+            .collect();
+
+        let functions = selector_maps
+            .iter()
+            .map(|(name, selector_map)| {
+                let mut functions: Vec<String> = selector_map.values().cloned().collect();
+                functions.sort();
+                (name.clone(), functions)
+            })
+            .collect();
+        let selectors = selector_maps
+            .into_iter()
+            .flat_map(|(_, selector_map)| selector_map)
             .collect();
 
         Ok(ContractsData {
             contracts,
             class_hashes: BiMap::from_iter(class_hashes),
             selectors,
+            functions,
+            abis,
         })
     }
 }
 
-fn build_name_selector_map(abi: Vec<AbiEntry>) -> HashMap<EntryPointSelector, String> {
+fn build_name_selector_map(abi: &[AbiEntry]) -> HashMap<EntryPointSelector, String> {
     let mut selector_map = HashMap::new();
     for abi_entry in abi {
         match abi_entry {
             AbiEntry::Interface(abi_interface) => {
-                for abi_entry in abi_interface.items {
+                for abi_entry in &abi_interface.items {
                     add_simple_abi_entry_to_mapping(abi_entry, &mut selector_map);
                 }
             }
@@ -59,14 +82,14 @@ fn build_name_selector_map(abi: Vec<AbiEntry>) -> HashMap<EntryPointSelector, St
 }
 
 fn add_simple_abi_entry_to_mapping(
-    abi_entry: AbiEntry,
+    abi_entry: &AbiEntry,
     selector_map: &mut HashMap<EntryPointSelector, String>,
 ) {
     match abi_entry {
         AbiEntry::Function(abi_function) | AbiEntry::L1Handler(abi_function) => {
             selector_map.insert(
                 get_selector_from_name(&abi_function.name).unwrap().into_(),
-                abi_function.name,
+                abi_function.name.clone(),
             );
         }
         AbiEntry::Constructor(abi_constructor) => {
@@ -74,7 +97,7 @@ fn add_simple_abi_entry_to_mapping(
                 get_selector_from_name(&abi_constructor.name)
                     .unwrap()
                     .into_(),
-                abi_constructor.name,
+                abi_constructor.name.clone(),
             );
         }
         _ => {}
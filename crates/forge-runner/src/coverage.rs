@@ -0,0 +1,498 @@
+use crate::contracts_data::ContractsData;
+use anyhow::{ensure, Context, Result};
+use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra::program::Program;
+use cairo_lang_sierra_to_casm::compiler::compile;
+use cairo_lang_sierra_to_casm::metadata::{calc_metadata, MetadataComputationConfig};
+use cairo_lang_starknet::casm_contract_class::CasmContractClass;
+use cairo_lang_starknet::contract_class::ContractClass;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cheatnet::state::CallTrace;
+use scarb_api::StarknetContractArtifacts;
+use serde::Serialize;
+use shared::print::print_as_warning;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::Mutex;
+use universal_sierra_compiler_api::AssembledProgramWithDebugInfo;
+
+/// Gas cost of a contract entry point, the same as used by the compiler of contract classes
+const ENTRY_POINT_COST: i32 = 10000;
+
+/// Coverage of a single Sierra function
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct FunctionCoverage {
+    /// Number of Sierra statements of the function
+    pub statements: usize,
+    /// Indexes (relative to the function entry point) of executed statements
+    pub executed_statements: BTreeSet<usize>,
+    /// How many times the function was entered
+    pub calls: usize,
+}
+
+impl FunctionCoverage {
+    #[must_use]
+    pub fn is_executed(&self) -> bool {
+        !self.executed_statements.is_empty() || self.calls > 0
+    }
+}
+
+/// Coverage of a contract executed with `call_contract` or `library_call`
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct ContractCoverage {
+    /// Number of calls for each function from the contract ABI
+    pub entry_points: BTreeMap<String, usize>,
+    /// Functions of the contract class, keyed by their Sierra debug names.
+    /// Empty if statements of the contract could not be collected.
+    pub functions: BTreeMap<String, FunctionCoverage>,
+}
+
+/// Coverage collected from all tests of a package
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct CoverageData {
+    /// Test crate functions, keyed by their Sierra debug names
+    pub functions: BTreeMap<String, FunctionCoverage>,
+    /// Contracts, keyed by their names
+    pub contracts: BTreeMap<String, ContractCoverage>,
+}
+
+/// Maps compiled code of a Sierra program to statements and functions they belong to
+#[derive(Debug, Default)]
+struct StatementsMap {
+    /// Offset of the compiled code of every Sierra statement
+    code_offsets: Vec<usize>,
+    /// Length of the compiled program
+    code_len: usize,
+    /// Entry point and debug name of every function (empty if not tracked), sorted by entry point
+    functions: Vec<(usize, String)>,
+}
+
+impl StatementsMap {
+    fn new(
+        program: &Program,
+        code_offsets: Vec<usize>,
+        code_len: usize,
+        is_tracked: impl Fn(&str) -> bool,
+    ) -> Self {
+        let mut functions: Vec<(usize, String)> = program
+            .funcs
+            .iter()
+            .map(|function| {
+                let name = function
+                    .id
+                    .debug_name
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                // Untracked functions stay in the map with an empty name,
+                // so their statements are not attributed to the tracked ones
+                let name = if is_tracked(&name) {
+                    name
+                } else {
+                    String::new()
+                };
+                (function.entry_point.0, name)
+            })
+            .collect();
+        functions.sort();
+
+        Self {
+            code_offsets,
+            code_len,
+            functions,
+        }
+    }
+
+    fn statement_at(&self, offset: usize) -> Option<usize> {
+        if offset >= self.code_len {
+            return None;
+        }
+        // Statements compiled to no instructions share the offset with the next one
+        self.code_offsets
+            .partition_point(|code_offset| *code_offset <= offset)
+            .checked_sub(1)
+    }
+
+    fn function_of(&self, statement: usize) -> Option<(usize, &str)> {
+        if statement >= self.code_offsets.len() {
+            return None;
+        }
+        let idx = self
+            .functions
+            .partition_point(|(entry_point, _)| *entry_point <= statement);
+        let (entry_point, name) = self.functions.get(idx.checked_sub(1)?)?;
+
+        Some((*entry_point, name))
+    }
+
+    /// Coverage of tracked functions with no statements executed
+    fn tracked_functions(&self) -> impl Iterator<Item = (&str, FunctionCoverage)> {
+        self.functions
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, name))| !name.is_empty())
+            .map(|(idx, (entry_point, name))| {
+                let next_entry_point = self
+                    .functions
+                    .get(idx + 1)
+                    .map_or(self.code_offsets.len(), |(entry_point, _)| *entry_point);
+                let function = FunctionCoverage {
+                    statements: next_entry_point - entry_point,
+                    ..Default::default()
+                };
+                (name.as_str(), function)
+            })
+    }
+
+    /// Records code offsets executed by a run of the program
+    fn record_executed_offsets(
+        &self,
+        functions: &mut BTreeMap<String, FunctionCoverage>,
+        offsets: impl IntoIterator<Item = usize>,
+    ) {
+        let mut previous_statement = None;
+        for offset in offsets {
+            let Some(statement) = self.statement_at(offset) else {
+                continue;
+            };
+            // Statements are usually compiled to several instructions
+            let is_new_statement = previous_statement != Some(statement);
+            previous_statement = Some(statement);
+
+            let Some((entry_point, name)) = self.function_of(statement) else {
+                continue;
+            };
+            let Some(function) = functions.get_mut(name) else {
+                continue;
+            };
+
+            function.executed_statements.insert(statement - entry_point);
+            if statement == entry_point && is_new_statement {
+                function.calls += 1;
+            }
+        }
+    }
+}
+
+/// Functions coming from dependencies (e.g. `core`), generated by the compiler (e.g. contract wrappers)
+/// and test functions themselves are not tracked
+fn is_tracked(name: &str, tracked_crates: &[&str], test_names: &HashSet<&str>) -> bool {
+    let mut segments = name.split("::");
+    segments
+        .next()
+        .is_some_and(|crate_name| tracked_crates.contains(&crate_name))
+        && !segments.any(|segment| segment.starts_with("__"))
+        && !test_names.contains(name)
+}
+
+/// Compiles the Sierra program of a contract class the same way as its CASM is compiled,
+/// to map instructions executed by contract calls to Sierra statements
+fn contract_statements_map(
+    artifacts: &StarknetContractArtifacts,
+    tracked_crates: &[&str],
+) -> Result<StatementsMap> {
+    let contract_class: ContractClass = serde_json::from_str(&artifacts.sierra)?;
+    let casm_contract_class: CasmContractClass = serde_json::from_str(&artifacts.casm)?;
+    let program = contract_class
+        .extract_sierra_program()
+        .context("Failed to extract Sierra program")?;
+
+    let entry_points = &contract_class.entry_points_by_type;
+    let function_set_costs = entry_points
+        .constructor
+        .iter()
+        .chain(&entry_points.external)
+        .chain(&entry_points.l1_handler)
+        .filter_map(|entry_point| program.funcs.get(entry_point.function_idx))
+        .map(|function| {
+            (
+                function.id.clone(),
+                OrderedHashMap::from_iter([(CostTokenType::Const, ENTRY_POINT_COST)]),
+            )
+        })
+        .collect();
+    let metadata = calc_metadata(
+        &program,
+        MetadataComputationConfig {
+            function_set_costs,
+            linear_gas_solver: true,
+            linear_ap_change_solver: true,
+        },
+    )
+    .context("Failed to calculate metadata")?;
+    let cairo_program = compile(&program, &metadata, true).context("Failed to compile")?;
+
+    let code_len = cairo_program.assemble().bytecode.len();
+    ensure!(
+        code_len == casm_contract_class.bytecode.len(),
+        "Compiled code does not match the contract CASM"
+    );
+    let code_offsets = cairo_program
+        .debug_info
+        .sierra_statement_info
+        .iter()
+        .map(|statement_info| statement_info.code_offset)
+        .collect();
+
+    Ok(StatementsMap::new(
+        &program,
+        code_offsets,
+        code_len,
+        |name| is_tracked(name, tracked_crates, &HashSet::new()),
+    ))
+}
+
+/// Gathers coverage of tests run in parallel
+#[derive(Debug, Default)]
+pub struct CoverageCollector {
+    statements_map: Mutex<StatementsMap>,
+    /// Statements of contract classes, keyed by the names of the contracts
+    contract_statements_maps: Mutex<HashMap<String, StatementsMap>>,
+    data: Mutex<CoverageData>,
+}
+
+impl CoverageCollector {
+    /// Sets the test crate which tests are going to be run next
+    pub fn set_test_crate(
+        &self,
+        sierra_program: &Program,
+        casm_program: &AssembledProgramWithDebugInfo,
+        tracked_crates: &[&str],
+        test_names: &HashSet<&str>,
+    ) {
+        let statements_map = StatementsMap::new(
+            sierra_program,
+            casm_program
+                .debug_info
+                .iter()
+                .map(|(code_offset, _)| *code_offset)
+                .collect(),
+            casm_program.assembled_cairo_program.bytecode.len(),
+            |name| is_tracked(name, tracked_crates, test_names),
+        );
+
+        let mut data = self.data.lock().unwrap();
+        for (name, function) in statements_map.tracked_functions() {
+            data.functions.entry(name.to_string()).or_insert(function);
+        }
+
+        *self.statements_map.lock().unwrap() = statements_map;
+    }
+
+    /// Sets contracts of the package, statements executed by their calls are recorded
+    /// for functions coming from the tracked crates
+    pub fn set_contracts(&self, contracts_data: &ContractsData, tracked_crates: &[&str]) {
+        let mut contract_statements_maps = self.contract_statements_maps.lock().unwrap();
+        let mut data = self.data.lock().unwrap();
+
+        for (contract_name, artifacts) in &contracts_data.contracts {
+            if data.contracts.contains_key(contract_name) {
+                continue;
+            }
+            let contract = data
+                .contracts
+                .entry(contract_name.clone())
+                .or_insert_with(|| ContractCoverage {
+                    entry_points: contracts_data
+                        .functions
+                        .get(contract_name)
+                        .into_iter()
+                        .flatten()
+                        .map(|function| (function.clone(), 0))
+                        .collect(),
+                    functions: BTreeMap::new(),
+                });
+
+            match contract_statements_map(artifacts, tracked_crates) {
+                Ok(statements_map) => {
+                    contract.functions = statements_map
+                        .tracked_functions()
+                        .map(|(name, function)| (name.to_string(), function))
+                        .collect();
+                    contract_statements_maps.insert(contract_name.clone(), statements_map);
+                }
+                Err(error) => print_as_warning(&error.context(format!(
+                    "Statements executed by contract = {contract_name} are not collected"
+                ))),
+            }
+        }
+    }
+
+    /// Records code offsets (relative to the beginning of the test crate program) executed by a test
+    pub fn record_executed_offsets(&self, offsets: impl IntoIterator<Item = usize>) {
+        let statements_map = self.statements_map.lock().unwrap();
+        let mut data = self.data.lock().unwrap();
+
+        statements_map.record_executed_offsets(&mut data.functions, offsets);
+    }
+
+    /// Records functions and statements of contracts called during a test
+    pub fn record_call_trace(
+        &self,
+        call_trace: &Rc<RefCell<CallTrace>>,
+        contracts_data: &ContractsData,
+    ) {
+        let contract_statements_maps = self.contract_statements_maps.lock().unwrap();
+        let mut data = self.data.lock().unwrap();
+        record_call(
+            &mut data,
+            &contract_statements_maps,
+            call_trace,
+            contracts_data,
+        );
+    }
+
+    #[must_use]
+    pub fn data(&self) -> CoverageData {
+        self.data.lock().unwrap().clone()
+    }
+}
+
+fn record_call(
+    data: &mut CoverageData,
+    contract_statements_maps: &HashMap<String, StatementsMap>,
+    call_trace: &Rc<RefCell<CallTrace>>,
+    contracts_data: &ContractsData,
+) {
+    let call_trace = call_trace.borrow();
+    let entry_point = &call_trace.entry_point;
+
+    let contract_name = entry_point
+        .class_hash
+        .and_then(|class_hash| contracts_data.class_hashes.get_by_right(&class_hash));
+
+    if let Some(contract) =
+        contract_name.and_then(|contract_name| data.contracts.get_mut(contract_name))
+    {
+        if let Some(function_name) = contracts_data
+            .selectors
+            .get(&entry_point.entry_point_selector)
+        {
+            *contract
+                .entry_points
+                .entry(function_name.clone())
+                .or_default() += 1;
+        }
+
+        if let Some(statements_map) =
+            contract_name.and_then(|contract_name| contract_statements_maps.get(contract_name))
+        {
+            statements_map.record_executed_offsets(
+                &mut contract.functions,
+                call_trace.executed_pcs.iter().copied(),
+            );
+        }
+    }
+
+    for nested_call in &call_trace.nested_calls {
+        record_call(data, contract_statements_maps, nested_call, contracts_data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statements_map() -> StatementsMap {
+        StatementsMap {
+            code_offsets: vec![0, 2, 2, 5, 6, 10],
+            code_len: 11,
+            functions: vec![
+                (0, "pkg::first".to_string()),
+                (3, "pkg::second".to_string()),
+            ],
+        }
+    }
+
+    #[test]
+    fn statement_at_offset() {
+        let map = statements_map();
+
+        assert_eq!(map.statement_at(0), Some(0));
+        assert_eq!(map.statement_at(1), Some(0));
+        assert_eq!(map.statement_at(2), Some(2));
+        assert_eq!(map.statement_at(4), Some(2));
+        assert_eq!(map.statement_at(9), Some(4));
+        assert_eq!(map.statement_at(10), Some(5));
+        assert_eq!(map.statement_at(11), None);
+    }
+
+    #[test]
+    fn function_of_statement() {
+        let map = statements_map();
+
+        assert_eq!(map.function_of(0), Some((0, "pkg::first")));
+        assert_eq!(map.function_of(2), Some((0, "pkg::first")));
+        assert_eq!(map.function_of(3), Some((3, "pkg::second")));
+        assert_eq!(map.function_of(6), None);
+    }
+
+    #[test]
+    fn tracked_functions_with_statements() {
+        let mut map = statements_map();
+        map.functions.push((5, String::new()));
+
+        assert_eq!(
+            map.tracked_functions()
+                .map(|(name, function)| (name, function.statements))
+                .collect::<Vec<_>>(),
+            vec![("pkg::first", 3), ("pkg::second", 2)]
+        );
+    }
+
+    #[test]
+    fn tracks_functions_by_full_names() {
+        let test_names = HashSet::from(["pkg::tests::test_transfer"]);
+
+        assert!(is_tracked("pkg::transfer", &["pkg"], &test_names));
+        assert!(is_tracked(
+            "pkg::helpers::test_transfer",
+            &["pkg"],
+            &test_names
+        ));
+        assert!(!is_tracked(
+            "pkg::tests::test_transfer",
+            &["pkg"],
+            &test_names
+        ));
+        assert!(!is_tracked(
+            "core::integer::u256_add",
+            &["pkg"],
+            &test_names
+        ));
+        assert!(!is_tracked(
+            "pkg::Contract::__wrapper__ContractImpl__transfer",
+            &["pkg"],
+            &test_names
+        ));
+    }
+
+    #[test]
+    fn records_executed_statements_and_calls() {
+        let map = statements_map();
+        let mut functions: BTreeMap<String, FunctionCoverage> = map
+            .tracked_functions()
+            .map(|(name, function)| (name.to_string(), function))
+            .collect();
+
+        map.record_executed_offsets(&mut functions, [0, 1, 2, 5, 6, 7, 0, 5]);
+
+        assert_eq!(
+            functions["pkg::first"],
+            FunctionCoverage {
+                statements: 3,
+                executed_statements: BTreeSet::from([0, 2]),
+                calls: 2,
+            }
+        );
+        assert_eq!(
+            functions["pkg::second"],
+            FunctionCoverage {
+                statements: 3,
+                executed_statements: BTreeSet::from([0, 1]),
+                calls: 2,
+            }
+        );
+    }
+}
@@ -1,15 +1,108 @@
 // Our custom structs used to prevent name changes in structs on side of cairo compiler from breaking the test collector backwards compatibility
 use cairo_felt::Felt252;
+use cairo_lang_runner::short_string::as_cairo_short_string;
 use cairo_lang_test_plugin::test_config::{PanicExpectation, TestExpectation};
+use regex::Regex;
+use runtime::utils::try_deserialize_byte_array;
 use serde::Deserialize;
+use std::fmt;
 
 /// Expectation for a panic case.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub enum ExpectedPanicValue {
     /// Accept any panic value.
     Any,
     /// Accept only this specific vector of panics.
     Exact(Vec<Felt252>),
+    // Variants below are only set with `[[tool.snforge.expected_panic]]` in Scarb.toml,
+    // the test collector emits the ones above
+    /// Accept panics whose data starts with this vector.
+    #[serde(skip)]
+    Prefix(Vec<Felt252>),
+    /// Accept only a `ByteArray` panic message equal to this one.
+    #[serde(skip)]
+    Message(String),
+    /// Accept a `ByteArray` panic message containing this one.
+    #[serde(skip)]
+    MessageContaining(String),
+    /// Accept a `ByteArray` panic message matching this regular expression.
+    #[serde(skip)]
+    MessagePattern(Regex),
+}
+
+/// Patterns are compared by their source, as `Regex` does not implement `PartialEq`
+impl PartialEq for ExpectedPanicValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ExpectedPanicValue::Any, ExpectedPanicValue::Any) => true,
+            (ExpectedPanicValue::Exact(a), ExpectedPanicValue::Exact(b))
+            | (ExpectedPanicValue::Prefix(a), ExpectedPanicValue::Prefix(b)) => a == b,
+            (ExpectedPanicValue::Message(a), ExpectedPanicValue::Message(b))
+            | (
+                ExpectedPanicValue::MessageContaining(a),
+                ExpectedPanicValue::MessageContaining(b),
+            ) => a == b,
+            (ExpectedPanicValue::MessagePattern(a), ExpectedPanicValue::MessagePattern(b)) => {
+                a.as_str() == b.as_str()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl ExpectedPanicValue {
+    #[must_use]
+    pub fn matches(&self, panic_data: &[Felt252]) -> bool {
+        match self {
+            ExpectedPanicValue::Any => true,
+            ExpectedPanicValue::Exact(expected) => panic_data == expected,
+            ExpectedPanicValue::Prefix(prefix) => panic_data.starts_with(prefix),
+            ExpectedPanicValue::Message(expected) => {
+                try_deserialize_byte_array(panic_data).is_some_and(|message| &message == expected)
+            }
+            ExpectedPanicValue::MessageContaining(expected) => {
+                try_deserialize_byte_array(panic_data)
+                    .is_some_and(|message| message.contains(expected.as_str()))
+            }
+            ExpectedPanicValue::MessagePattern(pattern) => try_deserialize_byte_array(panic_data)
+                .is_some_and(|message| pattern.is_match(&message)),
+        }
+    }
+}
+
+impl fmt::Display for ExpectedPanicValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpectedPanicValue::Any => write!(f, "any"),
+            ExpectedPanicValue::Exact(data) => write!(f, "{data:?} ({})", join_short_strings(data)),
+            ExpectedPanicValue::Prefix(data) => {
+                write!(f, "starting with {data:?} ({})", join_short_strings(data))
+            }
+            ExpectedPanicValue::Message(message) => write!(f, "{message:?}"),
+            ExpectedPanicValue::MessageContaining(message) => {
+                write!(f, "message containing {message:?}")
+            }
+            ExpectedPanicValue::MessagePattern(pattern) => {
+                write!(f, "message matching pattern {:?}", pattern.as_str())
+            }
+        }
+    }
+}
+
+/// Panic data decoded as a `ByteArray` message if possible, as short strings otherwise
+#[must_use]
+pub(crate) fn decode_panic_data(panic_data: &[Felt252]) -> String {
+    match try_deserialize_byte_array(panic_data) {
+        Some(message) => format!("{message:?}"),
+        None => join_short_strings(panic_data),
+    }
+}
+
+fn join_short_strings(data: &[Felt252]) -> String {
+    data.iter()
+        .map(|felt| as_cairo_short_string(felt).unwrap_or_default())
+        .collect::<Vec<String>>()
+        .join(", ")
 }
 
 impl From<PanicExpectation> for ExpectedPanicValue {
@@ -40,3 +133,92 @@ impl From<TestExpectation> for ExpectedTestResult {
         }
     }
 }
+
+impl fmt::Display for ExpectedTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpectedTestResult::Success => write!(f, "success"),
+            ExpectedTestResult::Panics(ExpectedPanicValue::Any) => write!(f, "panic"),
+            ExpectedTestResult::Panics(value) => write!(f, "panic with {value}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conversions::byte_array::ByteArray;
+
+    fn panic_message(message: &str) -> Vec<Felt252> {
+        ByteArray::from(message).serialize_with_magic()
+    }
+
+    #[test]
+    fn matches_messages() {
+        let panic_data = panic_message("Insufficient balance: 10 < 100");
+
+        assert!(
+            ExpectedPanicValue::Message("Insufficient balance: 10 < 100".to_string())
+                .matches(&panic_data)
+        );
+        assert!(
+            !ExpectedPanicValue::Message("Insufficient balance".to_string()).matches(&panic_data)
+        );
+        assert!(ExpectedPanicValue::MessageContaining("balance".to_string()).matches(&panic_data));
+        assert!(ExpectedPanicValue::MessagePattern(
+            Regex::new(r"^Insufficient balance: \d+ < \d+$").unwrap()
+        )
+        .matches(&panic_data));
+        assert!(
+            !ExpectedPanicValue::MessagePattern(Regex::new(r"^balance").unwrap())
+                .matches(&panic_data)
+        );
+    }
+
+    #[test]
+    fn messages_do_not_match_short_strings() {
+        let panic_data = vec![Felt252::from_bytes_be(b"balance")];
+
+        assert!(!ExpectedPanicValue::MessageContaining("balance".to_string()).matches(&panic_data));
+    }
+
+    #[test]
+    fn matches_prefix() {
+        let panic_data = vec![Felt252::from(1), Felt252::from(2), Felt252::from(3)];
+
+        assert!(
+            ExpectedPanicValue::Prefix(vec![Felt252::from(1), Felt252::from(2)])
+                .matches(&panic_data)
+        );
+        assert!(ExpectedPanicValue::Prefix(vec![]).matches(&panic_data));
+        assert!(!ExpectedPanicValue::Prefix(vec![Felt252::from(2)]).matches(&panic_data));
+    }
+
+    #[test]
+    fn decodes_panic_data() {
+        assert_eq!(
+            decode_panic_data(&panic_message("Insufficient balance")),
+            r#""Insufficient balance""#
+        );
+        assert_eq!(
+            decode_panic_data(&[Felt252::from_bytes_be(b"failing check")]),
+            "failing check"
+        );
+    }
+
+    #[test]
+    fn displays_expected_results() {
+        assert_eq!(ExpectedTestResult::Success.to_string(), "success");
+        assert_eq!(
+            ExpectedTestResult::Panics(ExpectedPanicValue::Any).to_string(),
+            "panic"
+        );
+        assert_eq!(
+            ExpectedTestResult::Panics(ExpectedPanicValue::Message(
+                "Insufficient balance".to_string()
+            ))
+            .to_string(),
+            r#"panic with "Insufficient balance""#
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use anyhow::{bail, Result};
+use blockifier::state::cached_state::CachedState;
+use blockifier::state::state_api::StateReader;
+use cheatnet::state::{CheatsSnapshot, ExtendedStateReader};
+use runtime::starknet::state::DictStateReader;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// State left by a passed setup fixture, tests using the fixture start from it
+#[derive(Clone, Debug)]
+pub struct StateSnapshot {
+    pub(crate) dict_state_reader: DictStateReader,
+    pub(crate) cheats: CheatsSnapshot,
+}
+
+impl StateSnapshot {
+    /// Applies changes made to the `initial` state during the setup run
+    pub(crate) fn from_setup_run(
+        initial: DictStateReader,
+        cached_state: &mut CachedState<ExtendedStateReader>,
+        cheats: CheatsSnapshot,
+    ) -> Result<Self> {
+        let mut dict_state_reader = initial;
+        let state_diff = cached_state.to_state_diff();
+
+        for (contract_address, storage) in state_diff.storage_updates {
+            for (key, value) in storage {
+                dict_state_reader
+                    .storage_view
+                    .insert((contract_address, key), value);
+            }
+        }
+        dict_state_reader
+            .address_to_nonce
+            .extend(state_diff.address_to_nonce);
+        dict_state_reader
+            .address_to_class_hash
+            .extend(state_diff.address_to_class_hash);
+        for (class_hash, compiled_class_hash) in state_diff.class_hash_to_compiled_class_hash {
+            let contract_class = cached_state.get_compiled_contract_class(&class_hash)?;
+            dict_state_reader
+                .class_hash_to_class
+                .insert(class_hash, contract_class);
+            dict_state_reader
+                .class_hash_to_compiled_class_hash
+                .insert(class_hash, compiled_class_hash);
+        }
+
+        Ok(Self {
+            dict_state_reader,
+            cheats,
+        })
+    }
+}
+
+/// Results of setup fixtures run so far, keyed by their names.
+/// Failed setup fixtures have the message the tests starting from their state fail with.
+#[derive(Debug, Default)]
+pub struct FixtureSnapshots {
+    snapshots: Mutex<HashMap<String, Result<Arc<StateSnapshot>, String>>>,
+}
+
+impl FixtureSnapshots {
+    /// Only the first state of the setup fixture is kept,
+    /// a run finishing in the background after the fixture was marked as failed does not override it
+    pub(crate) fn insert(&self, setup_name: &str, snapshot: StateSnapshot) {
+        self.snapshots
+            .lock()
+            .unwrap()
+            .entry(setup_name.to_string())
+            .or_insert_with(|| Ok(Arc::new(snapshot)));
+    }
+
+    /// Marks the setup fixture as failed, e.g. when it panicked, timed out or was ignored
+    pub(crate) fn insert_failure(&self, setup_name: &str, msg: String) {
+        self.snapshots
+            .lock()
+            .unwrap()
+            .insert(setup_name.to_string(), Err(msg));
+    }
+
+    /// State the test starts from, `None` if the test does not use a setup fixture.
+    /// Fails if the setup fixture did not pass.
+    pub(crate) fn snapshot_for(
+        &self,
+        setup_fixture: Option<&str>,
+    ) -> Result<Option<Arc<StateSnapshot>>> {
+        let Some(setup_name) = setup_fixture else {
+            return Ok(None);
+        };
+        match self.snapshots.lock().unwrap().get(setup_name) {
+            Some(Ok(snapshot)) => Ok(Some(snapshot.clone())),
+            Some(Err(msg)) => bail!("{msg}"),
+            None => bail!("\n    Setup fixture {setup_name} was not run\n"),
+        }
+    }
+}
+
+/// Whether the setup fixture is run before the test, so that the test may start from its state.
+/// A setup fixture is run before the tests of its module and nested modules, excluding itself.
+#[must_use]
+pub fn runs_before(setup_name: &str, test_name: &str) -> bool {
+    setup_name != test_name
+        && setup_name
+            .rsplit_once("::")
+            .is_some_and(|(module, _)| test_name.starts_with(&format!("{module}::")))
+}
+
+/// Setup fixture of the closest module of the test which has one
+#[must_use]
+pub fn setup_fixture_of<'a>(test_name: &str, setup_fixtures: &'a [String]) -> Option<&'a str> {
+    setup_fixtures
+        .iter()
+        .filter(|setup_name| runs_before(setup_name, test_name))
+        .max_by_key(|setup_name| setup_depth(setup_name))
+        .map(String::as_str)
+}
+
+/// Number of modules the setup fixture is nested in, setups are run outermost first
+pub(crate) fn setup_depth(setup_name: &str) -> usize {
+    setup_name.matches("::").count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_fixtures() -> Vec<String> {
+        vec![
+            "pkg::tests::setup".to_string(),
+            "pkg::tests::erc20::deploy_token".to_string(),
+        ]
+    }
+
+    #[test]
+    fn finds_setup_fixture_of_the_closest_module() {
+        let setup_fixtures = setup_fixtures();
+
+        assert_eq!(
+            setup_fixture_of("pkg::tests::erc20::test_transfer", &setup_fixtures),
+            Some("pkg::tests::erc20::deploy_token")
+        );
+        assert_eq!(
+            setup_fixture_of("pkg::tests::erc20::deploy_token", &setup_fixtures),
+            Some("pkg::tests::setup")
+        );
+        assert_eq!(
+            setup_fixture_of("pkg::tests::erc721::test_mint", &setup_fixtures),
+            Some("pkg::tests::setup")
+        );
+        assert_eq!(setup_fixture_of("pkg::tests::setup", &setup_fixtures), None);
+        assert_eq!(
+            setup_fixture_of("pkg::other::test_transfer", &setup_fixtures),
+            None
+        );
+    }
+
+    #[test]
+    fn runs_setups_of_parent_modules_before_tests() {
+        assert!(runs_before(
+            "pkg::tests::setup",
+            "pkg::tests::erc20::test_transfer"
+        ));
+        assert!(runs_before(
+            "pkg::tests::setup",
+            "pkg::tests::erc20::deploy_token"
+        ));
+        assert!(!runs_before("pkg::tests::setup", "pkg::tests::setup"));
+        assert!(!runs_before(
+            "pkg::tests::setup",
+            "pkg::other::test_transfer"
+        ));
+        assert!(!runs_before(
+            "pkg::tests::setup",
+            "pkg::tests_other::test_transfer"
+        ));
+    }
+
+    #[test]
+    fn uses_snapshot_of_the_setup_fixture() {
+        let fixtures = FixtureSnapshots::default();
+        fixtures.insert(
+            "pkg::tests::setup",
+            StateSnapshot {
+                dict_state_reader: DictStateReader::default(),
+                cheats: CheatsSnapshot::default(),
+            },
+        );
+
+        assert!(fixtures
+            .snapshot_for(Some("pkg::tests::setup"))
+            .unwrap()
+            .is_some());
+        assert!(fixtures.snapshot_for(None).unwrap().is_none());
+    }
+
+    #[test]
+    fn fails_when_setup_failed() {
+        let fixtures = FixtureSnapshots::default();
+        fixtures.insert_failure(
+            "pkg::tests::setup",
+            "\n    Setup fixture pkg::tests::setup is ignored\n".to_string(),
+        );
+
+        let err = fixtures
+            .snapshot_for(Some("pkg::tests::setup"))
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "\n    Setup fixture pkg::tests::setup is ignored\n"
+        );
+    }
+
+    #[test]
+    fn keeps_failure_of_setup_finished_in_background() {
+        let fixtures = FixtureSnapshots::default();
+        fixtures.insert_failure(
+            "pkg::tests::setup",
+            "\n    Setup fixture pkg::tests::setup timed out\n".to_string(),
+        );
+        fixtures.insert(
+            "pkg::tests::setup",
+            StateSnapshot {
+                dict_state_reader: DictStateReader::default(),
+                cheats: CheatsSnapshot::default(),
+            },
+        );
+
+        assert!(fixtures.snapshot_for(Some("pkg::tests::setup")).is_err());
+    }
+
+    #[test]
+    fn fails_when_setup_was_not_run() {
+        let fixtures = FixtureSnapshots::default();
+
+        let err = fixtures
+            .snapshot_for(Some("pkg::tests::setup"))
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "\n    Setup fixture pkg::tests::setup was not run\n"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::{Context, Result};
+use cairo_felt::Felt252;
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::Mutex;
+
+/// Directory with fuzz corpus files of all packages, placed in the cache directory
+pub const FUZZ_CORPUS_DIR: &str = "fuzz_corpus";
+
+/// Arguments which made a fuzz test fail
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FuzzCorpusEntry {
+    /// Name of the test case
+    pub test: String,
+    /// Arguments serialized with `Serde`, as hex strings
+    pub arguments: Vec<String>,
+    /// Seed of the fuzzer run which found the failure
+    pub seed: u64,
+}
+
+impl FuzzCorpusEntry {
+    /// Arguments as felts, `None` if any of them is malformed
+    #[must_use]
+    pub fn felts(&self) -> Option<Vec<Felt252>> {
+        self.arguments
+            .iter()
+            .map(|argument| Felt252::parse_bytes(argument.trim_start_matches("0x").as_bytes(), 16))
+            .collect()
+    }
+}
+
+/// Failing fuzz inputs of a single package, replayed before generating new random ones.
+/// Inputs which no longer make the test fail are removed.
+#[derive(Debug, Default)]
+pub struct FuzzCorpus {
+    entries: Mutex<Vec<FuzzCorpusEntry>>,
+}
+
+impl FuzzCorpus {
+    /// Path of the corpus file of the package
+    #[must_use]
+    pub fn path(cache_dir: &Utf8Path, package_name: &str) -> Utf8PathBuf {
+        cache_dir
+            .join(FUZZ_CORPUS_DIR)
+            .join(format!("{package_name}.json"))
+    }
+
+    /// Loads the corpus, which is empty if the file does not exist
+    pub fn load(path: &Utf8Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read fuzz corpus from {path}"))?;
+        let entries = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse fuzz corpus from {path}"))?;
+
+        Ok(Self {
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// Saves the corpus, the file is removed if the corpus is empty
+    pub fn save(&self, path: &Utf8Path) -> Result<()> {
+        let entries = self.entries.lock().unwrap();
+        if entries.is_empty() {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&*entries)?)
+            .with_context(|| format!("Failed to write fuzz corpus to {path}"))
+    }
+
+    #[must_use]
+    pub fn entries(&self) -> Vec<FuzzCorpusEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// Stored failing inputs of the test
+    pub(crate) fn inputs(&self, test: &str) -> Vec<FuzzCorpusEntry> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.test == test)
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn add(&self, test: &str, arguments: &[Felt252], seed: u64) {
+        let arguments = felts_to_hex(arguments);
+        let mut entries = self.entries.lock().unwrap();
+        if !entries
+            .iter()
+            .any(|entry| entry.test == test && entry.arguments == arguments)
+        {
+            entries.push(FuzzCorpusEntry {
+                test: test.to_string(),
+                arguments,
+                seed,
+            });
+        }
+    }
+
+    pub(crate) fn remove(&self, entry: &FuzzCorpusEntry) {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|stored| stored != entry);
+    }
+}
+
+fn felts_to_hex(felts: &[Felt252]) -> Vec<String> {
+    felts
+        .iter()
+        .map(|felt| format!("{:#x}", felt.to_biguint()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stores_unique_inputs() {
+        let corpus = FuzzCorpus::default();
+        corpus.add(
+            "tests::fuzzed",
+            &[Felt252::from(10), Felt252::from(255)],
+            12,
+        );
+        corpus.add(
+            "tests::fuzzed",
+            &[Felt252::from(10), Felt252::from(255)],
+            34,
+        );
+        corpus.add("tests::other", &[Felt252::from(1)], 12);
+
+        let entries = corpus.inputs("tests::fuzzed");
+
+        assert_eq!(
+            entries,
+            vec![FuzzCorpusEntry {
+                test: "tests::fuzzed".to_string(),
+                arguments: vec!["0xa".to_string(), "0xff".to_string()],
+                seed: 12,
+            }]
+        );
+        assert_eq!(
+            entries[0].felts(),
+            Some(vec![Felt252::from(10), Felt252::from(255)])
+        );
+
+        corpus.remove(&entries[0]);
+        assert!(corpus.inputs("tests::fuzzed").is_empty());
+        assert_eq!(corpus.entries().len(), 1);
+    }
+
+    #[test]
+    fn serializes_entries() {
+        let entry = FuzzCorpusEntry {
+            test: "tests::fuzzed".to_string(),
+            arguments: vec!["0xa".to_string()],
+            seed: 12,
+        };
+
+        let json = serde_json::to_string(&entry).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"test":"tests::fuzzed","arguments":["0xa"],"seed":12}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<FuzzCorpusEntry>(&json).unwrap(),
+            entry
+        );
+    }
+}
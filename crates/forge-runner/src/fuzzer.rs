@@ -1,12 +1,16 @@
-use crate::fuzzer::arguments::CairoType;
-use anyhow::Result;
+use cairo_felt::Felt252;
 use rand::rngs::StdRng;
 use rand::Rng;
 
 mod arguments;
+mod calls;
 mod random;
+mod shrinking;
 
+pub(crate) use arguments::{CairoType, CairoValue, TypeDeclarations};
+pub(crate) use calls::{CallSource, InvariantCalls, TargetCall};
 pub use random::RandomFuzzer;
+pub(crate) use shrinking::{shrink_candidates, MAX_SHRINK_RUNS};
 
 #[derive(Debug, Clone)]
 pub struct RunParams {
@@ -27,14 +31,9 @@ pub struct RunParams {
 }
 
 impl RunParams {
-    pub fn from(rng: &mut StdRng, total_runs: u32, arguments: &[&str]) -> Result<Self> {
+    pub fn from(rng: &mut StdRng, total_runs: u32, arguments: &[CairoType]) -> Self {
         assert!(total_runs >= 3);
 
-        let arguments = arguments
-            .iter()
-            .map(|arg| CairoType::from_name(arg))
-            .collect::<Result<Vec<_>>>()?;
-
         let run_with_min_value_for_argument: Vec<u32> = (0..arguments.len())
             .map(|_| rng.gen_range(1..=total_runs))
             .collect();
@@ -50,12 +49,26 @@ impl RunParams {
             })
             .collect();
 
-        Ok(Self {
-            arguments,
+        Self {
+            arguments: arguments.to_vec(),
             total_runs,
             executed_runs: 0,
             run_with_min_value_for_argument,
             run_with_max_value_for_argument,
-        })
+        }
     }
 }
+
+/// Reads arguments serialized with `Serde`, `None` if the felts do not match the argument types
+pub(crate) fn deserialize_arguments(
+    types: &[CairoType],
+    felts: Vec<Felt252>,
+) -> Option<Vec<CairoValue>> {
+    let mut felts = felts.into_iter();
+    let arguments = types
+        .iter()
+        .map(|ty| ty.deserialize(&mut felts))
+        .collect::<Option<Vec<_>>>()?;
+
+    felts.next().is_none().then_some(arguments)
+}
@@ -1,12 +1,27 @@
 use anyhow::{anyhow, Result};
 use cairo_felt::Felt252;
-use num_bigint::{BigUint, RandBigInt};
-use num_integer::Integer;
-use num_traits::{One, Zero};
+use cairo_lang_runner::Arg;
+use cairo_lang_sierra::ids::ConcreteTypeId;
+use cairo_lang_sierra::program::{ConcreteTypeLongId, GenericArg};
+use num_bigint::{BigInt, BigUint, RandBigInt};
+use num_traits::{One, Signed, ToPrimitive, Zero};
 use rand::prelude::StdRng;
-use std::ops::{Add, Shl, Shr, Sub};
+use rand::Rng;
+use starknet::core::types::contract::AbiEntry;
+use std::collections::HashMap;
+use std::ops::Shl;
 
-#[derive(Debug, Clone)]
+/// Maximal number of elements of generated arrays
+pub const MAX_ARRAY_LENGTH: usize = 16;
+/// Maximal number of bytes of generated byte arrays
+pub const MAX_BYTE_ARRAY_LENGTH: usize = 64;
+/// Number of bytes stored in a single `bytes31` word of a byte array
+const BYTES_IN_WORD: usize = 31;
+
+/// Sierra type declarations of the test crate, keyed by their ids
+pub type TypeDeclarations<'a> = HashMap<ConcreteTypeId, &'a ConcreteTypeLongId>;
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum CairoType {
     U8,
     U16,
@@ -14,100 +29,692 @@ pub enum CairoType {
     U64,
     U128,
     U256,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
     Felt252,
+    ContractAddress,
+    Bytes31,
+    ByteArray,
+    Array(Box<CairoType>),
+    Struct(Vec<CairoType>),
+    Enum(Vec<CairoType>),
+}
+
+/// Value generated by the fuzzer
+#[derive(Debug, Clone, PartialEq)]
+pub enum CairoValue {
+    Felt(Felt252),
+    Struct(Vec<CairoValue>),
+    Enum {
+        /// Index of the variant
+        variant: usize,
+        /// Number of variants of the enum
+        variants: usize,
+        /// Number of unused memory cells between the selector and the value
+        padding: usize,
+        value: Box<CairoValue>,
+    },
+    Array(Vec<CairoValue>),
 }
 
 pub trait Argument {
-    fn low(&self) -> BigUint;
-    fn high(&self) -> BigUint;
-    fn gen(&self, rng: &mut StdRng) -> Vec<Felt252>;
-    fn min(&self) -> Vec<Felt252>;
-    fn max(&self) -> Vec<Felt252>;
+    fn gen(&self, rng: &mut StdRng) -> CairoValue;
+    fn min(&self) -> CairoValue;
+    fn max(&self) -> CairoValue;
 }
 
 impl Argument for CairoType {
-    fn low(&self) -> BigUint {
-        BigUint::zero()
+    fn gen(&self, rng: &mut StdRng) -> CairoValue {
+        match self {
+            CairoType::U256 => {
+                CairoValue::Struct(vec![CairoType::U128.gen(rng), CairoType::U128.gen(rng)])
+            }
+            CairoType::ByteArray => {
+                let length = rng.gen_range(0..=MAX_BYTE_ARRAY_LENGTH);
+                let bytes: Vec<u8> = (0..length).map(|_| rng.gen()).collect();
+                byte_array(&bytes)
+            }
+            CairoType::Array(element) => {
+                let length = rng.gen_range(0..=MAX_ARRAY_LENGTH);
+                CairoValue::Array((0..length).map(|_| element.gen(rng)).collect())
+            }
+            CairoType::Struct(members) => {
+                CairoValue::Struct(members.iter().map(|member| member.gen(rng)).collect())
+            }
+            CairoType::Enum(variants) => {
+                let variant = rng.gen_range(0..variants.len());
+                self.enum_value(variant, variants[variant].gen(rng))
+            }
+            _ => {
+                let (low, high) = self.range();
+                CairoValue::Felt(Felt252::from(rng.gen_bigint_range(&low, &high)))
+            }
+        }
     }
 
-    fn high(&self) -> BigUint {
+    fn min(&self) -> CairoValue {
         match self {
-            CairoType::U8 => BigUint::from(u8::MAX).add(BigUint::one()),
-            CairoType::U16 => BigUint::from(u16::MAX).add(BigUint::one()),
-            CairoType::U32 => BigUint::from(u32::MAX).add(BigUint::one()),
-            CairoType::U64 => BigUint::from(u64::MAX).add(BigUint::one()),
-            CairoType::U128 => BigUint::from(u128::MAX).add(BigUint::one()),
             CairoType::U256 => {
-                let max = BigUint::from(1_u32);
-                max.shl(256)
+                CairoValue::Struct(vec![CairoType::U128.min(), CairoType::U128.min()])
+            }
+            CairoType::ByteArray => byte_array(&[]),
+            CairoType::Array(_) => CairoValue::Array(vec![]),
+            CairoType::Struct(members) => {
+                CairoValue::Struct(members.iter().map(Argument::min).collect())
             }
-            CairoType::Felt252 => Felt252::prime(),
+            CairoType::Enum(variants) => self.enum_value(0, variants[0].min()),
+            _ => CairoValue::Felt(Felt252::from(self.range().0)),
         }
     }
 
-    fn gen(&self, rng: &mut StdRng) -> Vec<Felt252> {
+    fn max(&self) -> CairoValue {
         match self {
-            CairoType::U8
-            | CairoType::U16
-            | CairoType::U32
-            | CairoType::U64
-            | CairoType::U128
-            | CairoType::Felt252 => {
-                vec![Felt252::from(
-                    rng.gen_biguint_range(&self.low(), &self.high()),
-                )]
-            }
             CairoType::U256 => {
-                let val = rng.gen_biguint_range(&self.low(), &self.high());
-                u256_to_felt252(val)
+                CairoValue::Struct(vec![CairoType::U128.max(), CairoType::U128.max()])
+            }
+            CairoType::ByteArray => byte_array(&[u8::MAX; MAX_BYTE_ARRAY_LENGTH]),
+            CairoType::Array(element) => CairoValue::Array(vec![element.max(); MAX_ARRAY_LENGTH]),
+            CairoType::Struct(members) => {
+                CairoValue::Struct(members.iter().map(Argument::max).collect())
+            }
+            CairoType::Enum(variants) => {
+                let variant = variants.len() - 1;
+                self.enum_value(variant, variants[variant].max())
+            }
+            _ => CairoValue::Felt(Felt252::from(self.range().1 - BigInt::one())),
+        }
+    }
+}
+
+impl CairoType {
+    /// Range of values of types represented by a single felt, the upper bound is exclusive
+    pub(super) fn range(&self) -> (BigInt, BigInt) {
+        let unsigned = |bits: usize| (BigInt::zero(), BigInt::one().shl(bits));
+        let signed = |bits: usize| {
+            let bound = BigInt::one().shl(bits - 1);
+            (-bound.clone(), bound)
+        };
+
+        match self {
+            CairoType::U8 => unsigned(8),
+            CairoType::U16 => unsigned(16),
+            CairoType::U32 => unsigned(32),
+            CairoType::U64 => unsigned(64),
+            CairoType::U128 => unsigned(128),
+            CairoType::I8 => signed(8),
+            CairoType::I16 => signed(16),
+            CairoType::I32 => signed(32),
+            CairoType::I64 => signed(64),
+            CairoType::I128 => signed(128),
+            CairoType::Felt252 => (BigInt::zero(), BigInt::from(Felt252::prime())),
+            CairoType::ContractAddress => unsigned(251),
+            CairoType::Bytes31 => unsigned(8 * BYTES_IN_WORD),
+            CairoType::U256
+            | CairoType::ByteArray
+            | CairoType::Array(_)
+            | CairoType::Struct(_)
+            | CairoType::Enum(_) => unreachable!("{self:?} is not represented by a single felt"),
+        }
+    }
+
+    /// Number of memory cells taken by a value of the type
+    fn size(&self) -> usize {
+        match self {
+            CairoType::U256 => 2,
+            CairoType::ByteArray => 4,
+            CairoType::Array(_) => 2,
+            CairoType::Struct(members) => members.iter().map(CairoType::size).sum(),
+            CairoType::Enum(variants) => {
+                1 + variants
+                    .iter()
+                    .map(CairoType::size)
+                    .max()
+                    .unwrap_or_default()
+            }
+            _ => 1,
+        }
+    }
+
+    fn contains_array(&self) -> bool {
+        match self {
+            CairoType::ByteArray | CairoType::Array(_) => true,
+            CairoType::Struct(members) | CairoType::Enum(members) => {
+                members.iter().any(CairoType::contains_array)
+            }
+            _ => false,
+        }
+    }
+
+    pub(super) fn enum_value(&self, variant: usize, value: CairoValue) -> CairoValue {
+        let CairoType::Enum(variants) = self else {
+            unreachable!("{self:?} is not an enum")
+        };
+
+        CairoValue::Enum {
+            variant,
+            variants: variants.len(),
+            padding: self.size() - 1 - variants[variant].size(),
+            value: Box::new(value),
+        }
+    }
+
+    /// Reads a value serialized with `Serde`, `None` if the felts do not represent a value of the type
+    pub fn deserialize(&self, felts: &mut impl Iterator<Item = Felt252>) -> Option<CairoValue> {
+        let value = match self {
+            CairoType::U256 => CairoValue::Struct(vec![
+                CairoType::U128.deserialize(felts)?,
+                CairoType::U128.deserialize(felts)?,
+            ]),
+            CairoType::ByteArray => {
+                let value = CairoType::Struct(vec![
+                    CairoType::Array(Box::new(CairoType::Bytes31)),
+                    CairoType::Bytes31,
+                    CairoType::U32,
+                ])
+                .deserialize(felts)?;
+                let CairoValue::Struct(members) = &value else {
+                    unreachable!()
+                };
+                let CairoValue::Felt(pending_word_len) = &members[2] else {
+                    unreachable!()
+                };
+                if pending_word_len.to_usize()? >= BYTES_IN_WORD {
+                    return None;
+                }
+                value
+            }
+            CairoType::Array(element) => {
+                let length = felts.next()?.to_usize()?;
+                CairoValue::Array(
+                    (0..length)
+                        .map(|_| element.deserialize(felts))
+                        .collect::<Option<_>>()?,
+                )
+            }
+            CairoType::Struct(members) => CairoValue::Struct(
+                members
+                    .iter()
+                    .map(|member| member.deserialize(felts))
+                    .collect::<Option<_>>()?,
+            ),
+            CairoType::Enum(variants) => {
+                let variant = felts.next()?.to_usize()?;
+                let value = variants.get(variant)?.deserialize(felts)?;
+                self.enum_value(variant, value)
+            }
+            _ => {
+                let felt = felts.next()?;
+                let (low, high) = self.range();
+                let number = if low.is_negative() {
+                    felt.to_signed_felt()
+                } else {
+                    BigInt::from(felt.to_biguint())
+                };
+                if number < low || number >= high {
+                    return None;
+                }
+                CairoValue::Felt(felt)
+            }
+        };
+
+        Some(value)
+    }
+
+    /// Resolves the type of a test function argument from the Sierra type declarations
+    pub fn from_type_id(id: &ConcreteTypeId, declarations: &TypeDeclarations) -> Result<Self> {
+        let unsupported = || {
+            let name = id
+                .debug_name
+                .as_ref()
+                .map_or_else(|| id.id.to_string(), ToString::to_string);
+            anyhow!("Tried to use incorrect type for fuzzing. Type = {name} is not supported")
+        };
+
+        let long_id = declarations.get(id).ok_or_else(unsupported)?;
+        let user_type = long_id.generic_args.iter().find_map(|arg| match arg {
+            GenericArg::UserType(user_type) => user_type.debug_name.as_deref(),
+            _ => None,
+        });
+        let inner_types = || {
+            long_id
+                .generic_args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArg::Type(ty) => Some(Self::from_type_id(ty, declarations)),
+                    _ => None,
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+
+        let ty = match long_id.generic_id.0.as_str() {
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "u128" => Self::U128,
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "i32" => Self::I32,
+            "i64" => Self::I64,
+            "i128" => Self::I128,
+            "felt252" => Self::Felt252,
+            "ContractAddress" => Self::ContractAddress,
+            "bytes31" => Self::Bytes31,
+            // Snapshots share the representation of the inner type
+            "Snapshot" => inner_types()?.pop().ok_or_else(unsupported)?,
+            "Array" => {
+                let element = inner_types()?.pop().ok_or_else(unsupported)?;
+                if element.contains_array() {
+                    return Err(unsupported());
+                }
+                Self::Array(Box::new(element))
+            }
+            "Struct" => match user_type {
+                Some("core::integer::u256") => Self::U256,
+                Some("core::byte_array::ByteArray") => Self::ByteArray,
+                _ => Self::Struct(inner_types()?),
+            },
+            "Enum" => {
+                let variants = inner_types()?;
+                if variants.is_empty() {
+                    return Err(unsupported());
+                }
+                Self::Enum(variants)
+            }
+            _ => return Err(unsupported()),
+        };
+
+        Ok(ty)
+    }
+
+    /// Resolves the type of a contract function input from the contract ABI, `None` if it is not supported
+    pub fn from_abi_type(name: &str, abi: &[AbiEntry]) -> Option<Self> {
+        // Snapshots share the serialization of the inner type
+        let name = name.trim().trim_start_matches('@');
+
+        if let Some(element) = name
+            .strip_prefix("core::array::Array::<")
+            .or_else(|| name.strip_prefix("core::array::Span::<"))
+            .and_then(|element| element.strip_suffix('>'))
+        {
+            return Some(Self::Array(Box::new(Self::from_abi_type(element, abi)?)));
+        }
+        if let Some(members) = name
+            .strip_prefix('(')
+            .and_then(|members| members.strip_suffix(')'))
+        {
+            return tuple_members(members)
+                .into_iter()
+                .map(|member| Self::from_abi_type(member, abi))
+                .collect::<Option<_>>()
+                .map(Self::Struct);
+        }
+
+        let ty = match name {
+            "core::integer::u8" => Self::U8,
+            "core::integer::u16" => Self::U16,
+            "core::integer::u32" => Self::U32,
+            "core::integer::u64" => Self::U64,
+            "core::integer::u128" => Self::U128,
+            "core::integer::u256" => Self::U256,
+            "core::integer::i8" => Self::I8,
+            "core::integer::i16" => Self::I16,
+            "core::integer::i32" => Self::I32,
+            "core::integer::i64" => Self::I64,
+            "core::integer::i128" => Self::I128,
+            "core::felt252" => Self::Felt252,
+            // Class hashes share the range of contract addresses
+            "core::starknet::contract_address::ContractAddress"
+            | "core::starknet::class_hash::ClassHash" => Self::ContractAddress,
+            "core::bytes_31::bytes31" => Self::Bytes31,
+            "core::byte_array::ByteArray" => Self::ByteArray,
+            _ => {
+                return abi.iter().find_map(|entry| match entry {
+                    AbiEntry::Struct(abi_struct) if abi_struct.name == name => abi_struct
+                        .members
+                        .iter()
+                        .map(|member| Self::from_abi_type(&member.r#type, abi))
+                        .collect::<Option<_>>()
+                        .map(Self::Struct),
+                    AbiEntry::Enum(abi_enum) if abi_enum.name == name => abi_enum
+                        .variants
+                        .iter()
+                        .map(|variant| Self::from_abi_type(&variant.r#type, abi))
+                        .collect::<Option<Vec<_>>>()
+                        .filter(|variants| !variants.is_empty())
+                        .map(Self::Enum),
+                    _ => None,
+                })
             }
+        };
+
+        Some(ty)
+    }
+}
+
+/// Splits members of a tuple type on the top level commas
+fn tuple_members(members: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (index, char) in members.char_indices() {
+        match char {
+            '(' | '<' => depth += 1,
+            ')' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                result.push(&members[start..index]);
+                start = index + 1;
+            }
+            _ => {}
         }
     }
+    result.push(&members[start..]);
+
+    result
+        .into_iter()
+        .filter(|member| !member.trim().is_empty())
+        .collect()
+}
 
-    fn min(&self) -> Vec<Felt252> {
+impl CairoValue {
+    /// Value serialized with `Serde`, as it is presented to the user
+    #[must_use]
+    pub fn serialize(&self) -> Vec<Felt252> {
         match self {
-            CairoType::U8
-            | CairoType::U16
-            | CairoType::U32
-            | CairoType::U64
-            | CairoType::U128
-            | CairoType::Felt252 => vec![Felt252::from(self.low())],
-            CairoType::U256 => vec![Felt252::from(self.low()), Felt252::from(self.low())],
+            CairoValue::Felt(felt) => vec![felt.clone()],
+            CairoValue::Struct(members) => members.iter().flat_map(CairoValue::serialize).collect(),
+            CairoValue::Enum { variant, value, .. } => {
+                let mut result = vec![Felt252::from(*variant)];
+                result.extend(value.serialize());
+                result
+            }
+            CairoValue::Array(elements) => {
+                let mut result = vec![Felt252::from(elements.len())];
+                result.extend(elements.iter().flat_map(CairoValue::serialize));
+                result
+            }
         }
     }
 
-    fn max(&self) -> Vec<Felt252> {
+    /// Memory representation of the value, passed to the test function
+    #[must_use]
+    pub fn to_args(&self) -> Vec<Arg> {
         match self {
-            CairoType::U8
-            | CairoType::U16
-            | CairoType::U32
-            | CairoType::U64
-            | CairoType::U128
-            | CairoType::Felt252 => vec![Felt252::from(self.high().sub(BigUint::one()))],
-            CairoType::U256 => u256_to_felt252(self.high().sub(BigUint::one())),
+            CairoValue::Array(elements) => vec![Arg::Array(
+                elements.iter().flat_map(CairoValue::cells).collect(),
+            )],
+            CairoValue::Struct(members) => members.iter().flat_map(CairoValue::to_args).collect(),
+            CairoValue::Enum { value, .. } => {
+                let mut result: Vec<Arg> = self.enum_header().into_iter().map(Arg::Value).collect();
+                result.extend(value.to_args());
+                result
+            }
+            CairoValue::Felt(felt) => vec![Arg::Value(felt.clone())],
         }
     }
+
+    /// Memory cells of an array element, nested arrays are not supported
+    fn cells(&self) -> Vec<Felt252> {
+        match self {
+            CairoValue::Felt(felt) => vec![felt.clone()],
+            CairoValue::Struct(members) => members.iter().flat_map(CairoValue::cells).collect(),
+            CairoValue::Enum { value, .. } => {
+                let mut result = self.enum_header();
+                result.extend(value.cells());
+                result
+            }
+            CairoValue::Array(_) => unreachable!("Nested arrays are not supported by the fuzzer"),
+        }
+    }
+
+    /// Selector of the variant followed by the padding, as laid out by the Sierra to CASM compiler
+    fn enum_header(&self) -> Vec<Felt252> {
+        let CairoValue::Enum {
+            variant,
+            variants,
+            padding,
+            ..
+        } = self
+        else {
+            unreachable!("{self:?} is not an enum")
+        };
+
+        let selector = if *variants <= 2 {
+            *variant
+        } else {
+            2 * (variants - variant) - 1
+        };
+
+        let mut result = vec![Felt252::from(selector)];
+        result.extend(vec![Felt252::zero(); *padding]);
+        result
+    }
 }
 
-fn u256_to_felt252(val: BigUint) -> Vec<Felt252> {
-    let low = val.mod_floor(&BigUint::from(2_u32).pow(128));
-    let high = val.shr(128);
-    vec![Felt252::from(low), Felt252::from(high)]
+/// `ByteArray` value made of full `bytes31` words and the pending word with the remaining bytes
+pub(super) fn byte_array(bytes: &[u8]) -> CairoValue {
+    let data_length = bytes.len() / BYTES_IN_WORD * BYTES_IN_WORD;
+    let (data, pending_word) = bytes.split_at(data_length);
+
+    CairoValue::Struct(vec![
+        CairoValue::Array(
+            data.chunks(BYTES_IN_WORD)
+                .map(|word| CairoValue::Felt(Felt252::from(BigUint::from_bytes_be(word))))
+                .collect(),
+        ),
+        CairoValue::Felt(Felt252::from(BigUint::from_bytes_be(pending_word))),
+        CairoValue::Felt(Felt252::from(pending_word.len())),
+    ])
 }
 
-impl CairoType {
-    pub fn from_name(name: &str) -> Result<Self> {
-        match name {
-            "u8" => Ok(Self::U8),
-            "u16" => Ok(Self::U16),
-            "u32" => Ok(Self::U32),
-            "u64" => Ok(Self::U64),
-            "u128" => Ok(Self::U128),
-            "u256" | "core::integer::u256" => Ok(Self::U256),
-            "felt252" => Ok(Self::Felt252),
-            _ => Err(anyhow!(
-                "Tried to use incorrect type for fuzzing. Type = {name} is not supported"
-            )),
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cairo_lang_sierra::ids::{GenericTypeId, UserTypeId};
+
+    fn declaration(generic_id: &str, generic_args: Vec<GenericArg>) -> ConcreteTypeLongId {
+        ConcreteTypeLongId {
+            generic_id: GenericTypeId::from_string(generic_id),
+            generic_args,
         }
     }
+
+    fn type_arg(name: &str) -> GenericArg {
+        GenericArg::Type(ConcreteTypeId::from_string(name))
+    }
+
+    fn user_type(name: &str) -> GenericArg {
+        GenericArg::UserType(UserTypeId::from_string(name))
+    }
+
+    fn resolve(name: &str, declarations: &[(&str, ConcreteTypeLongId)]) -> Result<CairoType> {
+        let declarations: TypeDeclarations = declarations
+            .iter()
+            .map(|(name, long_id)| (ConcreteTypeId::from_string(name), long_id))
+            .collect();
+
+        CairoType::from_type_id(&ConcreteTypeId::from_string(name), &declarations)
+    }
+
+    #[test]
+    fn resolves_composite_types() {
+        let declarations = [
+            ("felt252", declaration("felt252", vec![])),
+            ("i8", declaration("i8", vec![])),
+            ("Unit", declaration("Struct", vec![user_type("Tuple")])),
+            (
+                "core::bool",
+                declaration(
+                    "Enum",
+                    vec![user_type("core::bool"), type_arg("Unit"), type_arg("Unit")],
+                ),
+            ),
+            ("Array<i8>", declaration("Array", vec![type_arg("i8")])),
+            (
+                "tests::MyStruct",
+                declaration(
+                    "Struct",
+                    vec![
+                        user_type("tests::MyStruct"),
+                        type_arg("felt252"),
+                        type_arg("core::bool"),
+                        type_arg("Array<i8>"),
+                    ],
+                ),
+            ),
+        ];
+
+        assert_eq!(
+            resolve("tests::MyStruct", &declarations).unwrap(),
+            CairoType::Struct(vec![
+                CairoType::Felt252,
+                CairoType::Enum(vec![CairoType::Struct(vec![]), CairoType::Struct(vec![])]),
+                CairoType::Array(Box::new(CairoType::I8)),
+            ])
+        );
+    }
+
+    #[test]
+    fn resolves_abi_types() {
+        let abi: Vec<AbiEntry> = serde_json::from_str(
+            r#"[
+                {"type": "enum", "name": "core::bool", "variants": [
+                    {"name": "False", "type": "()"},
+                    {"name": "True", "type": "()"}
+                ]},
+                {"type": "struct", "name": "tests::Position", "members": [
+                    {"name": "owner", "type": "core::starknet::contract_address::ContractAddress"},
+                    {"name": "amounts", "type": "core::array::Span::<core::integer::u256>"},
+                    {"name": "active", "type": "core::bool"}
+                ]}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            CairoType::from_abi_type("(tests::Position, core::integer::i8)", &abi),
+            Some(CairoType::Struct(vec![
+                CairoType::Struct(vec![
+                    CairoType::ContractAddress,
+                    CairoType::Array(Box::new(CairoType::U256)),
+                    CairoType::Enum(vec![CairoType::Struct(vec![]), CairoType::Struct(vec![])]),
+                ]),
+                CairoType::I8,
+            ]))
+        );
+        assert_eq!(
+            CairoType::from_abi_type("core::zeroable::NonZero::<core::felt252>", &abi),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_types() {
+        let declarations = [
+            ("felt252", declaration("felt252", vec![])),
+            (
+                "Box<felt252>",
+                declaration("Box", vec![type_arg("felt252")]),
+            ),
+            (
+                "Array<felt252>",
+                declaration("Array", vec![type_arg("felt252")]),
+            ),
+            (
+                "Array<Array<felt252>>",
+                declaration("Array", vec![type_arg("Array<felt252>")]),
+            ),
+        ];
+
+        assert_eq!(
+            resolve("Box<felt252>", &declarations)
+                .unwrap_err()
+                .to_string(),
+            "Tried to use incorrect type for fuzzing. Type = Box<felt252> is not supported"
+        );
+        assert_eq!(
+            resolve("Array<Array<felt252>>", &declarations)
+                .unwrap_err()
+                .to_string(),
+            "Tried to use incorrect type for fuzzing. Type = Array<Array<felt252>> is not supported"
+        );
+    }
+
+    #[test]
+    fn signed_integers_range() {
+        assert_eq!(CairoType::I8.min(), CairoValue::Felt(Felt252::from(-128)));
+        assert_eq!(CairoType::I8.max(), CairoValue::Felt(Felt252::from(127)));
+    }
+
+    #[test]
+    fn enum_memory_layout() {
+        let ty = CairoType::Enum(vec![
+            CairoType::Struct(vec![]),
+            CairoType::U256,
+            CairoType::Felt252,
+        ]);
+        let value = ty.enum_value(2, CairoValue::Felt(Felt252::from(7)));
+
+        assert_eq!(value.serialize(), vec![Felt252::from(2), Felt252::from(7)]);
+        // Selector of the last out of three variants, one cell of padding and the value
+        assert_eq!(
+            value.cells(),
+            vec![Felt252::from(1), Felt252::from(0), Felt252::from(7)]
+        );
+    }
+
+    #[test]
+    fn byte_array_words() {
+        let bytes: Vec<u8> = (0..33).collect();
+
+        let CairoValue::Struct(members) = byte_array(&bytes) else {
+            panic!("ByteArray is a struct")
+        };
+
+        assert_eq!(
+            members,
+            vec![
+                CairoValue::Array(vec![CairoValue::Felt(Felt252::from(
+                    BigUint::from_bytes_be(&bytes[..31])
+                ))]),
+                CairoValue::Felt(Felt252::from(0x1f20)),
+                CairoValue::Felt(Felt252::from(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn deserializes_serialized_values() {
+        let ty = CairoType::Struct(vec![
+            CairoType::I16,
+            CairoType::Enum(vec![CairoType::Struct(vec![]), CairoType::U256]),
+            CairoType::Array(Box::new(CairoType::U8)),
+            CairoType::ByteArray,
+        ]);
+        let value = ty.max();
+
+        assert_eq!(
+            ty.deserialize(&mut value.serialize().into_iter()),
+            Some(value)
+        );
+        // Out of range value
+        assert_eq!(
+            CairoType::U8.deserialize(&mut [Felt252::from(256)].into_iter()),
+            None
+        );
+        // Not enough felts
+        assert_eq!(ty.deserialize(&mut [Felt252::from(1)].into_iter()), None);
+    }
+
+    #[test]
+    fn array_max_value() {
+        let ty = CairoType::Array(Box::new(CairoType::U8));
+
+        assert_eq!(ty.max().serialize().len(), MAX_ARRAY_LENGTH + 1,);
+        assert_eq!(ty.min().serialize(), vec![Felt252::zero()]);
+    }
 }
@@ -0,0 +1,241 @@
+use crate::contracts_data::ContractsData;
+use crate::fuzzer::arguments::{Argument, CairoType, CairoValue};
+use cairo_felt::Felt252;
+use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::invariant::{
+    CallSequence, InvariantCall,
+};
+use conversions::{FromConv, IntoConv};
+use rand::prelude::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use starknet::core::types::contract::{AbiEntry, StateMutability};
+use starknet::core::utils::get_selector_from_name;
+use starknet_api::core::{ClassHash, ContractAddress};
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Maximal number of calls executed in a single run of an invariant test
+pub const MAX_SEQUENCE_LENGTH: usize = 16;
+
+/// External function of a target contract, called with fuzzed calldata
+#[derive(Debug, Clone)]
+struct TargetFunction {
+    name: String,
+    selector: Felt252,
+    inputs: Vec<CairoType>,
+}
+
+#[derive(Debug, Clone)]
+struct Target {
+    contract_address: ContractAddress,
+    contract_name: String,
+    functions: Vec<TargetFunction>,
+}
+
+/// Call executed by an invariant test, together with the names used to report it
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TargetCall {
+    call: InvariantCall,
+    contract_name: String,
+    function_name: String,
+}
+
+impl fmt::Display for TargetCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::{}(calldata: {:?}, caller: {:#x})",
+            self.contract_name,
+            self.function_name,
+            self.call.calldata,
+            Felt252::from_(self.call.caller_address).to_biguint()
+        )
+    }
+}
+
+/// Origin of the calls executed by a run of an invariant test
+#[derive(Debug, Clone)]
+pub(crate) enum CallSource {
+    /// Random calls generated from the seed
+    Random(u64),
+    /// Calls of a previous run, replayed in order
+    Replay(Vec<TargetCall>),
+}
+
+/// Calls of a single run of an invariant test
+pub(crate) struct InvariantCalls<'a> {
+    contracts_data: &'a ContractsData,
+    rng: StdRng,
+    replayed: Option<VecDeque<TargetCall>>,
+    targets: Vec<Target>,
+    senders: Vec<ContractAddress>,
+    executed: Vec<TargetCall>,
+}
+
+impl<'a> InvariantCalls<'a> {
+    pub(crate) fn new(contracts_data: &'a ContractsData, source: CallSource) -> Self {
+        let (seed, replayed) = match source {
+            CallSource::Random(seed) => (seed, None),
+            CallSource::Replay(calls) => (0, Some(calls.into())),
+        };
+
+        Self {
+            contracts_data,
+            rng: StdRng::seed_from_u64(seed),
+            replayed,
+            targets: vec![],
+            senders: vec![],
+            executed: vec![],
+        }
+    }
+
+    /// Calls executed so far, in order
+    pub(crate) fn executed(self) -> Vec<TargetCall> {
+        self.executed
+    }
+
+    fn random_call(&mut self) -> Option<TargetCall> {
+        if self.executed.len() == MAX_SEQUENCE_LENGTH {
+            return None;
+        }
+
+        let callable: Vec<&Target> = self
+            .targets
+            .iter()
+            .filter(|target| !target.functions.is_empty())
+            .collect();
+        let target = callable.choose(&mut self.rng)?;
+        let function = target.functions.choose(&mut self.rng)?;
+
+        let calldata = function
+            .inputs
+            .iter()
+            .flat_map(|ty| random_value(ty, &mut self.rng).serialize())
+            .collect();
+        let caller_address = match self.senders.choose(&mut self.rng) {
+            Some(sender) => *sender,
+            None => match CairoType::ContractAddress.gen(&mut self.rng) {
+                CairoValue::Felt(address) => address.into_(),
+                _ => unreachable!("Contract address is represented by a single felt"),
+            },
+        };
+
+        Some(TargetCall {
+            call: InvariantCall {
+                contract_address: target.contract_address,
+                function_selector: function.selector.clone(),
+                calldata,
+                caller_address,
+            },
+            contract_name: target.contract_name.clone(),
+            function_name: function.name.clone(),
+        })
+    }
+}
+
+impl CallSequence for InvariantCalls<'_> {
+    fn add_target(&mut self, contract_address: ContractAddress, class_hash: ClassHash) {
+        let contract_name = self
+            .contracts_data
+            .class_hashes
+            .get_by_right(&class_hash)
+            .cloned();
+        // Functions of contracts from outside the package are unknown, so they are never called
+        let functions = contract_name
+            .as_ref()
+            .and_then(|name| self.contracts_data.abis.get(name))
+            .map(Vec::as_slice)
+            .map(target_functions)
+            .unwrap_or_default();
+
+        self.targets.push(Target {
+            contract_address,
+            contract_name: contract_name
+                .unwrap_or_else(|| format!("{:#x}", Felt252::from_(contract_address).to_biguint())),
+            functions,
+        });
+    }
+
+    fn add_sender(&mut self, caller_address: ContractAddress) {
+        self.senders.push(caller_address);
+    }
+
+    fn next_call(&mut self) -> Option<InvariantCall> {
+        let call = match &mut self.replayed {
+            Some(replayed) => replayed.pop_front(),
+            None => self.random_call(),
+        }?;
+
+        let invariant_call = call.call.clone();
+        self.executed.push(call);
+        Some(invariant_call)
+    }
+}
+
+/// Random value of the type, extreme values are used more often as they are likely to break contracts
+fn random_value(ty: &CairoType, rng: &mut StdRng) -> CairoValue {
+    match rng.gen_range(0..8) {
+        0 => ty.min(),
+        1 => ty.max(),
+        _ => ty.gen(rng),
+    }
+}
+
+/// External functions which can modify the state of the contract and take inputs supported by the fuzzer
+fn target_functions(abi: &[AbiEntry]) -> Vec<TargetFunction> {
+    abi.iter()
+        .flat_map(|entry| match entry {
+            AbiEntry::Interface(interface) => interface.items.iter().collect(),
+            _ => vec![entry],
+        })
+        .filter_map(|entry| match entry {
+            AbiEntry::Function(function)
+                if matches!(function.state_mutability, StateMutability::External) =>
+            {
+                let inputs = function
+                    .inputs
+                    .iter()
+                    .map(|input| CairoType::from_abi_type(&input.r#type, abi))
+                    .collect::<Option<_>>()?;
+
+                Some(TargetFunction {
+                    name: function.name.clone(),
+                    selector: get_selector_from_name(&function.name).unwrap().into_(),
+                    inputs,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_external_functions_with_supported_inputs() {
+        let abi: Vec<AbiEntry> = serde_json::from_str(
+            r#"[
+                {"type": "interface", "name": "tests::IBank", "items": [
+                    {"type": "function", "name": "deposit", "inputs": [
+                        {"name": "amount", "type": "core::integer::u128"}
+                    ], "outputs": [], "state_mutability": "external"},
+                    {"type": "function", "name": "balance", "inputs": [], "outputs": [
+                        {"type": "core::integer::u128"}
+                    ], "state_mutability": "view"},
+                    {"type": "function", "name": "set_limit", "inputs": [
+                        {"name": "limit", "type": "core::zeroable::NonZero::<core::integer::u128>"}
+                    ], "outputs": [], "state_mutability": "external"}
+                ]}
+            ]"#,
+        )
+        .unwrap();
+
+        let functions = target_functions(&abi);
+
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "deposit");
+        assert_eq!(functions[0].inputs, vec![CairoType::U128]);
+    }
+}
@@ -1,7 +1,5 @@
-use crate::fuzzer::arguments::Argument;
+use crate::fuzzer::arguments::{Argument, CairoType, CairoValue};
 use crate::fuzzer::RunParams;
-use anyhow::Result;
-use cairo_felt::Felt252;
 use rand::prelude::StdRng;
 use rand::SeedableRng;
 
@@ -12,14 +10,15 @@ pub struct RandomFuzzer {
 }
 
 impl RandomFuzzer {
-    pub fn create(seed: u64, total_runs: u32, arguments: &[&str]) -> Result<Self> {
+    #[must_use]
+    pub fn create(seed: u64, total_runs: u32, arguments: &[CairoType]) -> Self {
         let mut rng = StdRng::seed_from_u64(seed);
-        let run_params = RunParams::from(&mut rng, total_runs, arguments)?;
+        let run_params = RunParams::from(&mut rng, total_runs, arguments);
 
-        Ok(Self { rng, run_params })
+        Self { rng, run_params }
     }
 
-    pub fn next_args(&mut self) -> Vec<Felt252> {
+    pub fn next_args(&mut self) -> Vec<CairoValue> {
         assert!(self.run_params.executed_runs < self.run_params.total_runs);
 
         self.next_run();
@@ -28,11 +27,11 @@ impl RandomFuzzer {
 
         for (index, argument) in self.run_params.arguments.iter().enumerate() {
             if self.is_run_with_min_value_for_arg(index) {
-                args.extend(argument.min());
+                args.push(argument.min());
             } else if self.is_run_with_max_value_for_arg(index) {
-                args.extend(argument.max());
+                args.push(argument.max());
             } else {
-                args.extend(argument.gen(&mut self.rng));
+                args.push(argument.gen(&mut self.rng));
             }
         }
 
@@ -57,7 +56,7 @@ impl RandomFuzzer {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::fuzzer::arguments::CairoType;
+    use cairo_felt::Felt252;
     use num_bigint::BigUint;
     use num_traits::Zero;
     use rand::{thread_rng, RngCore};
@@ -74,6 +73,10 @@ mod tests {
         }
     }
 
+    fn serialize(values: &[CairoValue]) -> Vec<Felt252> {
+        values.iter().flat_map(CairoValue::serialize).collect()
+    }
+
     fn all_values_different(vec1: &[Felt252], vec2: &[Felt252]) -> bool {
         vec1.iter().zip(vec2).all(|(i, j)| {
             i.to_biguint() != j.to_biguint()
@@ -101,9 +104,9 @@ mod tests {
             run_params,
         };
 
-        let args1 = fuzzer.next_args();
-        let args2 = fuzzer.next_args();
-        let args3 = fuzzer.next_args();
+        let args1 = serialize(&fuzzer.next_args());
+        let args2 = serialize(&fuzzer.next_args());
+        let args3 = serialize(&fuzzer.next_args());
 
         assert!(all_values_different(&args1, &args2));
         assert!(all_values_different(&args1, &args3));
@@ -149,10 +152,15 @@ mod tests {
     #[test]
     fn using_seed_consistent_result() {
         let seed = thread_rng().next_u64();
-        let mut fuzzer = RandomFuzzer::create(seed, 3, &["felt252", "felt252", "felt252"]).unwrap();
+        let arguments = vec![
+            CairoType::Felt252,
+            CairoType::Array(Box::new(CairoType::I64)),
+            CairoType::ByteArray,
+        ];
+        let mut fuzzer = RandomFuzzer::create(seed, 3, &arguments);
         let values = fuzzer.next_args();
 
-        let mut fuzzer = RandomFuzzer::create(seed, 3, &["felt252", "felt252", "felt252"]).unwrap();
+        let mut fuzzer = RandomFuzzer::create(seed, 3, &arguments);
         let values_from_seed = fuzzer.next_args();
 
         assert_eq!(values, values_from_seed);
@@ -162,23 +170,23 @@ mod tests {
     fn min_and_max_used_at_least_once_for_each_arg() {
         let seed = thread_rng().next_u64();
         let runs_number = 10;
-        let arguments = vec!["felt252", "felt252", "felt252"];
+        let arguments = vec![CairoType::Felt252; 3];
         let args_number = arguments.len();
 
-        let mut fuzzer = RandomFuzzer::create(seed, runs_number, &arguments).unwrap();
+        let mut fuzzer = RandomFuzzer::create(seed, runs_number, &arguments);
 
         let mut min_used = vec![false; args_number];
         let mut max_used = vec![false; args_number];
 
         for _ in 1..=runs_number {
-            let values = fuzzer.next_args();
+            let values = serialize(&fuzzer.next_args());
+            let min = &CairoType::Felt252.min().serialize()[0];
+            let max = &CairoType::Felt252.max().serialize()[0];
             for (i, value) in values.iter().enumerate() {
-                assert!(
-                    *value >= CairoType::Felt252.min()[0] && *value <= CairoType::Felt252.max()[0]
-                );
-                if *value == CairoType::Felt252.min()[0] {
+                assert!(value >= min && value <= max);
+                if value == min {
                     min_used[i] = true;
-                } else if *value == CairoType::Felt252.max()[0] {
+                } else if value == max {
                     max_used[i] = true;
                 }
             }
@@ -187,15 +195,4 @@ mod tests {
         assert_eq!(min_used, vec![true; args_number]);
         assert_eq!(max_used, vec![true; args_number]);
     }
-
-    #[test]
-    fn create_fuzzer_from_invalid_arguments() {
-        let result = RandomFuzzer::create(1234, 512, &["felt252", "invalid", "args"]);
-        let err = result.unwrap_err();
-
-        assert_eq!(
-            err.to_string(),
-            "Tried to use incorrect type for fuzzing. Type = invalid is not supported"
-        );
-    }
 }
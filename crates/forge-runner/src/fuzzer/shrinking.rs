@@ -0,0 +1,231 @@
+use crate::fuzzer::arguments::{byte_array, CairoType, CairoValue};
+use cairo_felt::Felt252;
+use num_bigint::{BigInt, Sign};
+use num_traits::{Signed, ToPrimitive, Zero};
+
+/// Maximal number of test runs with shrunk arguments after a fuzz test fails
+pub const MAX_SHRINK_RUNS: usize = 256;
+
+/// Simpler values of the given type, ordered from the simplest one.
+/// Numbers are moved towards zero, arrays and byte arrays are shortened,
+/// enums are changed to earlier variants and members of structs are shrunk one at a time.
+pub fn shrink_candidates(ty: &CairoType, value: &CairoValue) -> Vec<CairoValue> {
+    let candidates = match (ty, value) {
+        (CairoType::U256, CairoValue::Struct(members)) => {
+            shrink_members(&[CairoType::U128, CairoType::U128], members)
+        }
+        (CairoType::Struct(types), CairoValue::Struct(members)) => shrink_members(types, members),
+        (CairoType::ByteArray, value) => shrink_byte_array(&byte_array_bytes(value)),
+        (CairoType::Array(element), CairoValue::Array(elements)) => shrink_array(element, elements),
+        (
+            CairoType::Enum(variants),
+            CairoValue::Enum {
+                variant,
+                value: variant_value,
+                ..
+            },
+        ) => {
+            let mut candidates: Vec<CairoValue> = (0..*variant)
+                .map(|earlier| ty.enum_value(earlier, simplest(&variants[earlier])))
+                .collect();
+            candidates.extend(
+                shrink_candidates(&variants[*variant], variant_value)
+                    .into_iter()
+                    .map(|shrunk| ty.enum_value(*variant, shrunk)),
+            );
+            candidates
+        }
+        (_, CairoValue::Felt(felt)) => shrink_number(ty, felt),
+        _ => unreachable!("Value {value:?} does not match type {ty:?}"),
+    };
+
+    let mut unique = vec![];
+    for candidate in candidates {
+        if candidate != *value && !unique.contains(&candidate) {
+            unique.push(candidate);
+        }
+    }
+    unique
+}
+
+/// The simplest value of the type: zero, empty array or the first variant
+fn simplest(ty: &CairoType) -> CairoValue {
+    match ty {
+        CairoType::U256 => CairoValue::Struct(vec![CairoValue::Felt(Felt252::zero()); 2]),
+        CairoType::ByteArray => byte_array(&[]),
+        CairoType::Array(_) => CairoValue::Array(vec![]),
+        CairoType::Struct(members) => CairoValue::Struct(members.iter().map(simplest).collect()),
+        CairoType::Enum(variants) => ty.enum_value(0, simplest(&variants[0])),
+        _ => CairoValue::Felt(Felt252::zero()),
+    }
+}
+
+fn shrink_number(ty: &CairoType, felt: &Felt252) -> Vec<CairoValue> {
+    let number = if ty.range().0.is_negative() {
+        felt.to_signed_felt()
+    } else {
+        BigInt::from_biguint(Sign::Plus, felt.to_biguint())
+    };
+    if number.is_zero() {
+        return vec![];
+    }
+
+    [BigInt::zero(), &number / 2, &number - number.signum()]
+        .into_iter()
+        .map(|shrunk| CairoValue::Felt(Felt252::from(shrunk)))
+        .collect()
+}
+
+fn shrink_members(types: &[CairoType], members: &[CairoValue]) -> Vec<CairoValue> {
+    types
+        .iter()
+        .zip(members)
+        .enumerate()
+        .flat_map(|(index, (ty, member))| {
+            shrink_candidates(ty, member)
+                .into_iter()
+                .map(move |shrunk| {
+                    let mut members = members.to_vec();
+                    members[index] = shrunk;
+                    CairoValue::Struct(members)
+                })
+        })
+        .collect()
+}
+
+fn shrink_array(element: &CairoType, elements: &[CairoValue]) -> Vec<CairoValue> {
+    if elements.is_empty() {
+        return vec![];
+    }
+
+    let mut candidates = vec![
+        CairoValue::Array(vec![]),
+        CairoValue::Array(elements[..elements.len() / 2].to_vec()),
+        CairoValue::Array(elements[..elements.len() - 1].to_vec()),
+        CairoValue::Array(elements[1..].to_vec()),
+    ];
+    for (index, value) in elements.iter().enumerate() {
+        candidates.extend(shrink_candidates(element, value).into_iter().map(|shrunk| {
+            let mut elements = elements.to_vec();
+            elements[index] = shrunk;
+            CairoValue::Array(elements)
+        }));
+    }
+    candidates
+}
+
+fn shrink_byte_array(bytes: &[u8]) -> Vec<CairoValue> {
+    if bytes.is_empty() {
+        return vec![];
+    }
+
+    vec![
+        byte_array(&[]),
+        byte_array(&bytes[..bytes.len() / 2]),
+        byte_array(&bytes[..bytes.len() - 1]),
+        byte_array(&vec![0; bytes.len()]),
+    ]
+}
+
+/// Bytes stored in the `ByteArray` value
+fn byte_array_bytes(value: &CairoValue) -> Vec<u8> {
+    let CairoValue::Struct(members) = value else {
+        unreachable!("{value:?} is not a byte array")
+    };
+    let [CairoValue::Array(words), CairoValue::Felt(pending_word), CairoValue::Felt(pending_word_len)] =
+        members.as_slice()
+    else {
+        unreachable!("{value:?} is not a byte array")
+    };
+
+    let mut bytes = vec![];
+    for word in words {
+        let CairoValue::Felt(word) = word else {
+            unreachable!("{value:?} is not a byte array")
+        };
+        bytes.extend(felt_bytes(word, 31));
+    }
+    let pending_word_len = pending_word_len
+        .to_usize()
+        .expect("Pending word length should fit in usize");
+    bytes.extend(felt_bytes(pending_word, pending_word_len));
+    bytes
+}
+
+/// Big-endian bytes of the felt, left-padded to the given length
+fn felt_bytes(felt: &Felt252, length: usize) -> Vec<u8> {
+    let bytes = felt.to_biguint().to_bytes_be();
+    let mut result = vec![0; length.saturating_sub(bytes.len())];
+    result.extend(&bytes[bytes.len().saturating_sub(length)..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn felt(value: i64) -> CairoValue {
+        CairoValue::Felt(Felt252::from(value))
+    }
+
+    #[test]
+    fn shrinks_numbers_towards_zero() {
+        assert_eq!(
+            shrink_candidates(&CairoType::U8, &felt(100)),
+            vec![felt(0), felt(50), felt(99)]
+        );
+        assert_eq!(
+            shrink_candidates(&CairoType::I32, &felt(-7)),
+            vec![felt(0), felt(-3), felt(-6)]
+        );
+        assert_eq!(
+            shrink_candidates(&CairoType::Felt252, &felt(1)),
+            vec![felt(0)]
+        );
+        assert!(shrink_candidates(&CairoType::U8, &felt(0)).is_empty());
+    }
+
+    #[test]
+    fn shrinks_arrays() {
+        let ty = CairoType::Array(Box::new(CairoType::U8));
+        let value = CairoValue::Array(vec![felt(1), felt(2)]);
+
+        assert_eq!(
+            shrink_candidates(&ty, &value),
+            vec![
+                CairoValue::Array(vec![]),
+                CairoValue::Array(vec![felt(1)]),
+                CairoValue::Array(vec![felt(2)]),
+                CairoValue::Array(vec![felt(0), felt(2)]),
+                CairoValue::Array(vec![felt(1), felt(0)]),
+                CairoValue::Array(vec![felt(1), felt(1)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn shrinks_enums_to_earlier_variants() {
+        let ty = CairoType::Enum(vec![CairoType::Struct(vec![]), CairoType::U8]);
+        let value = ty.enum_value(1, felt(2));
+
+        assert_eq!(
+            shrink_candidates(&ty, &value),
+            vec![
+                ty.enum_value(0, CairoValue::Struct(vec![])),
+                ty.enum_value(1, felt(0)),
+                ty.enum_value(1, felt(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn shrinks_byte_arrays() {
+        let bytes: Vec<u8> = (1..=40).collect();
+
+        assert_eq!(byte_array_bytes(&byte_array(&bytes)), bytes);
+        assert_eq!(
+            shrink_candidates(&CairoType::ByteArray, &byte_array(&[5, 6])),
+            vec![byte_array(&[]), byte_array(&[5]), byte_array(&[0, 0]),]
+        );
+    }
+}
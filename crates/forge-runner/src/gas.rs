@@ -42,6 +42,24 @@ pub fn calculate_used_gas(
         .expect("Calculating gas failed, some resources were not included."))
 }
 
+/// Estimates gas used by a single contract call.
+/// Storage updates are tracked per transaction, so they are not included.
+#[must_use]
+pub fn calculate_call_gas(
+    block_context: &BlockContext,
+    execution_resources: &ExecutionResources,
+    l2_to_l1_payloads_length: &[usize],
+) -> u128 {
+    let total_vm_usage = get_total_vm_usage(execution_resources);
+    let l1_gas_usage =
+        calculate_tx_gas_usage(l2_to_l1_payloads_length, StateChangesCount::default(), None);
+
+    let resource_mapping = used_resources_to_resource_mapping(&total_vm_usage, l1_gas_usage);
+
+    calculate_tx_l1_gas_usage(&resource_mapping, block_context)
+        .expect("Calculating gas failed, some resources were not included.")
+}
+
 #[must_use]
 fn used_resources_to_resource_mapping(
     execution_resources: &VmExecutionResources,
@@ -85,6 +103,7 @@ pub fn check_available_gas(
             name,
             arguments,
             gas_info,
+            time,
             ..
         } if available_gas.map_or(false, |available_gas| gas_info > available_gas as u128) => {
             TestCaseSummary::Failed {
@@ -94,6 +113,8 @@ pub fn check_available_gas(
                 )),
                 arguments,
                 test_statistics: (),
+                call_trace: None,
+                time,
             }
         }
         _ => summary,
@@ -0,0 +1,217 @@
+use crate::contracts_data::ContractsData;
+use crate::gas::calculate_call_gas;
+use blockifier::block_context::BlockContext;
+use cairo_felt::Felt252;
+use cheatnet::state::CallTrace;
+use conversions::FromConv;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::sync::Mutex;
+
+/// Resources used by a single call of a contract function
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CallSample {
+    steps: usize,
+    gas: u128,
+}
+
+/// Minimum, mean, median and maximum of the sampled values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Statistics {
+    pub min: u128,
+    pub mean: f64,
+    pub median: f64,
+    pub max: u128,
+}
+
+impl Statistics {
+    #[allow(clippy::cast_precision_loss)]
+    fn new(mut values: Vec<u128>) -> Self {
+        values.sort_unstable();
+        let len = values.len();
+        let median = if len % 2 == 0 {
+            (values[len / 2 - 1] + values[len / 2]) as f64 / 2.0
+        } else {
+            values[len / 2] as f64
+        };
+
+        Self {
+            min: values[0],
+            mean: values.iter().sum::<u128>() as f64 / len as f64,
+            median,
+            max: values[len - 1],
+        }
+    }
+}
+
+/// Resources used by all calls of a contract function during the test run
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionGasReport {
+    pub calls: usize,
+    pub steps: Statistics,
+    pub gas: Statistics,
+}
+
+/// Gathers resources used by contract calls of tests run in parallel
+#[derive(Debug, Default)]
+pub struct GasReportCollector {
+    /// Samples keyed by the contract name and the function name
+    samples: Mutex<BTreeMap<String, BTreeMap<String, Vec<CallSample>>>>,
+}
+
+impl GasReportCollector {
+    /// Records all calls made during a test, the test call itself is omitted
+    pub fn record_call_trace(
+        &self,
+        call_trace: &Rc<RefCell<CallTrace>>,
+        contracts_data: &ContractsData,
+        block_context: &BlockContext,
+    ) {
+        let mut samples = self.samples.lock().unwrap();
+        for nested_call in &call_trace.borrow().nested_calls {
+            record_call(&mut samples, nested_call, contracts_data, block_context);
+        }
+    }
+
+    /// Report for every called function, keyed by the contract name and the function name
+    #[must_use]
+    pub fn report(&self) -> BTreeMap<String, BTreeMap<String, FunctionGasReport>> {
+        self.samples
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(contract, functions)| {
+                let functions = functions
+                    .iter()
+                    .map(|(function, samples)| (function.clone(), function_report(samples)))
+                    .collect();
+                (contract.clone(), functions)
+            })
+            .collect()
+    }
+}
+
+fn function_report(samples: &[CallSample]) -> FunctionGasReport {
+    FunctionGasReport {
+        calls: samples.len(),
+        steps: Statistics::new(samples.iter().map(|sample| sample.steps as u128).collect()),
+        gas: Statistics::new(samples.iter().map(|sample| sample.gas).collect()),
+    }
+}
+
+fn record_call(
+    samples: &mut BTreeMap<String, BTreeMap<String, Vec<CallSample>>>,
+    call_trace: &Rc<RefCell<CallTrace>>,
+    contracts_data: &ContractsData,
+    block_context: &BlockContext,
+) {
+    let call_trace = call_trace.borrow();
+    let entry_point = &call_trace.entry_point;
+
+    let contract_name = entry_point.class_hash.map_or_else(
+        || "<unknown>".to_string(),
+        |class_hash| {
+            contracts_data
+                .class_hashes
+                .get_by_right(&class_hash)
+                .cloned()
+                .unwrap_or_else(|| format!("{:#x}", Felt252::from_(class_hash).to_biguint()))
+        },
+    );
+    let function_name = contracts_data
+        .selectors
+        .get(&entry_point.entry_point_selector)
+        .cloned()
+        .unwrap_or_else(|| {
+            format!(
+                "{:#x}",
+                Felt252::from_(entry_point.entry_point_selector).to_biguint()
+            )
+        });
+
+    samples
+        .entry(contract_name)
+        .or_default()
+        .entry(function_name)
+        .or_default()
+        .push(CallSample {
+            steps: call_trace.used_execution_resources.vm_resources.n_steps,
+            gas: calculate_call_gas(
+                block_context,
+                &call_trace.used_execution_resources,
+                &call_trace.used_l1_resources.l2_l1_message_sizes,
+            ),
+        });
+
+    for nested_call in &call_trace.nested_calls {
+        record_call(samples, nested_call, contracts_data, block_context);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics_odd_number_of_values() {
+        assert_eq!(
+            Statistics::new(vec![7, 1, 4]),
+            Statistics {
+                min: 1,
+                mean: 4.0,
+                median: 4.0,
+                max: 7,
+            }
+        );
+    }
+
+    #[test]
+    fn statistics_even_number_of_values() {
+        assert_eq!(
+            Statistics::new(vec![10, 1, 2, 3]),
+            Statistics {
+                min: 1,
+                mean: 4.0,
+                median: 2.5,
+                max: 10,
+            }
+        );
+    }
+
+    #[test]
+    fn report_from_samples() {
+        let collector = GasReportCollector::default();
+        collector.samples.lock().unwrap().insert(
+            "HelloStarknet".to_string(),
+            BTreeMap::from([(
+                "get_balance".to_string(),
+                vec![
+                    CallSample { steps: 100, gas: 1 },
+                    CallSample { steps: 300, gas: 3 },
+                ],
+            )]),
+        );
+
+        let report = collector.report();
+
+        assert_eq!(
+            report["HelloStarknet"]["get_balance"],
+            FunctionGasReport {
+                calls: 2,
+                steps: Statistics {
+                    min: 100,
+                    mean: 200.0,
+                    median: 200.0,
+                    max: 300,
+                },
+                gas: Statistics {
+                    min: 1,
+                    mean: 2.0,
+                    median: 2.0,
+                    max: 3,
+                },
+            }
+        );
+    }
+}
@@ -0,0 +1,452 @@
+use crate::coverage::CoverageData;
+use crate::test_case_summary::{
+    AnyTestCaseSummary, FuzzingStatistics, GasStatistics, TestCaseSummary, TestTime,
+};
+use cairo_felt::Felt252;
+use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::UsedResources;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Event emitted when `snforge test` is run with `--format json`.
+/// Every event is printed as a single line of JSON, with the `event` field
+/// describing its kind.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TestEvent<'a> {
+    /// Tests were collected from the package
+    Collected {
+        package: &'a str,
+        tests: usize,
+        filtered: usize,
+    },
+    /// Runner started executing tests from a test crate
+    SuiteStarted {
+        package: &'a str,
+        location: &'a str,
+        tests: usize,
+    },
+    /// Runner started executing a test case
+    TestStarted {
+        name: &'a str,
+    },
+    TestPassed {
+        name: &'a str,
+        #[serde(flatten)]
+        details: TestDetails<'a>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        msg: Option<&'a str>,
+    },
+    TestFailed {
+        name: &'a str,
+        #[serde(flatten)]
+        details: TestDetails<'a>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        msg: Option<&'a str>,
+    },
+    /// Test did not finish within its timeout, counted as failed
+    TestTimedOut {
+        name: &'a str,
+        #[serde(flatten)]
+        details: TestDetails<'a>,
+        /// Timeout in seconds
+        timeout: f64,
+    },
+    TestIgnored {
+        name: &'a str,
+    },
+    /// Test was not run because of `--exit-first`
+    TestSkipped {
+        name: &'a str,
+    },
+    /// Summary of all tests run in the package
+    Summary {
+        package: &'a str,
+        passed: usize,
+        failed: usize,
+        skipped: usize,
+        ignored: usize,
+        filtered: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        fuzzer_seed: Option<u64>,
+        /// Shard run with `--shard`, as `<index>/<total>`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        shard: Option<String>,
+    },
+    /// Test was collected by `snforge test --list`, it is not run
+    TestListed {
+        name: &'a str,
+        package: &'a str,
+        location: &'a str,
+        ignored: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        available_gas: Option<usize>,
+        /// `success`, `panic` or the expected panic, e.g. `panic with "Insufficient balance"`
+        expected_result: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        fuzzer: Option<Fuzzer>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        fork: Option<Fork<'a>>,
+    },
+    /// Coverage collected from all tests of the package, printed with `--coverage`
+    Coverage {
+        package: &'a str,
+        #[serde(flatten)]
+        coverage: &'a CoverageData,
+    },
+    /// All packages were tested
+    Finished {
+        failures: Vec<&'a str>,
+    },
+    /// Error which stopped `snforge test`, or a test run in `--watch` mode
+    Error {
+        message: &'a str,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct TestDetails<'a> {
+    /// Arguments used in the test case run as hex strings
+    arguments: Vec<String>,
+    /// Simplest failing arguments found by shrinking, as hex strings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shrunk_arguments: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gas: Option<Gas<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fuzzing: Option<&'a FuzzingStatistics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    used_resources: Option<Resources>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time: Option<Time>,
+}
+
+/// Fuzzer configuration set with the `#[fuzzer]` attribute
+#[derive(Debug, Serialize)]
+pub struct Fuzzer {
+    pub runs: u32,
+    pub seed: u64,
+}
+
+/// Fork used by a test, the block is not fetched so `latest` is not resolved to a number
+#[derive(Debug, Serialize)]
+pub struct Fork<'a> {
+    /// Name of the fork configuration from `Scarb.toml`, if the test refers to it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<&'a str>,
+    pub url: &'a str,
+    pub block_id_type: &'a str,
+    pub block_id_value: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum Gas<'a> {
+    Single(u128),
+    Fuzzing(&'a GasStatistics),
+}
+
+#[derive(Debug, Serialize)]
+struct Resources {
+    steps: usize,
+    memory_holes: usize,
+    builtins: BTreeMap<String, usize>,
+    syscalls: BTreeMap<String, usize>,
+}
+
+/// Wall-clock time of the test in seconds, measured over all runs for fuzz tests
+#[derive(Debug, Serialize)]
+struct Time {
+    total: f64,
+    rpc: f64,
+}
+
+impl From<&TestTime> for Time {
+    fn from(time: &TestTime) -> Self {
+        Self {
+            total: time.total.as_secs_f64(),
+            rpc: time.rpc.as_secs_f64(),
+        }
+    }
+}
+
+impl From<&UsedResources> for Resources {
+    fn from(used_resources: &UsedResources) -> Self {
+        let vm_resources = &used_resources.execution_resources.vm_resources;
+
+        Self {
+            steps: vm_resources.n_steps,
+            memory_holes: vm_resources.n_memory_holes,
+            builtins: vm_resources
+                .builtin_instance_counter
+                .iter()
+                .map(|(name, count)| (name.clone(), *count))
+                .collect(),
+            syscalls: used_resources
+                .execution_resources
+                .syscall_counter
+                .iter()
+                .map(|(selector, count)| (format!("{selector:?}"), *count))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> TestEvent<'a> {
+    /// Builds an event from the result of a test case.
+    /// Returns `None` for skipped test cases as their names are not known.
+    #[must_use]
+    pub fn from_summary(summary: &'a AnyTestCaseSummary) -> Option<Self> {
+        let event = match summary {
+            AnyTestCaseSummary::Single(TestCaseSummary::Passed {
+                name,
+                msg,
+                arguments,
+                gas_info,
+                used_resources,
+                time,
+                ..
+            }) => TestEvent::TestPassed {
+                name,
+                details: TestDetails {
+                    arguments: felts_to_hex(arguments),
+                    shrunk_arguments: None,
+                    gas: Some(Gas::Single(*gas_info)),
+                    fuzzing: None,
+                    used_resources: Some(used_resources.into()),
+                    time: Some(time.into()),
+                },
+                msg: msg.as_deref(),
+            },
+            AnyTestCaseSummary::Fuzzing(TestCaseSummary::Passed {
+                name,
+                msg,
+                arguments,
+                gas_info,
+                test_statistics,
+                time,
+                ..
+            }) => TestEvent::TestPassed {
+                name,
+                details: TestDetails {
+                    arguments: felts_to_hex(arguments),
+                    shrunk_arguments: None,
+                    gas: Some(Gas::Fuzzing(gas_info)),
+                    fuzzing: Some(test_statistics),
+                    used_resources: None,
+                    time: Some(time.into()),
+                },
+                msg: msg.as_deref(),
+            },
+            AnyTestCaseSummary::Single(TestCaseSummary::Failed {
+                name,
+                msg,
+                arguments,
+                time,
+                ..
+            }) => TestEvent::TestFailed {
+                name,
+                details: TestDetails {
+                    arguments: felts_to_hex(arguments),
+                    shrunk_arguments: None,
+                    gas: None,
+                    fuzzing: None,
+                    used_resources: None,
+                    time: Some(time.into()),
+                },
+                msg: msg.as_deref(),
+            },
+            AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed {
+                name,
+                msg,
+                arguments,
+                test_statistics,
+                time,
+                ..
+            }) => TestEvent::TestFailed {
+                name,
+                details: TestDetails {
+                    arguments: felts_to_hex(arguments),
+                    shrunk_arguments: test_statistics
+                        .shrunk_arguments
+                        .as_deref()
+                        .map(felts_to_hex),
+                    gas: None,
+                    fuzzing: Some(test_statistics),
+                    used_resources: None,
+                    time: Some(time.into()),
+                },
+                msg: msg.as_deref(),
+            },
+            AnyTestCaseSummary::Single(TestCaseSummary::TimedOut {
+                name,
+                arguments,
+                timeout,
+                ..
+            }) => TestEvent::TestTimedOut {
+                name,
+                details: TestDetails {
+                    arguments: felts_to_hex(arguments),
+                    shrunk_arguments: None,
+                    gas: None,
+                    fuzzing: None,
+                    used_resources: None,
+                    time: None,
+                },
+                timeout: timeout.as_secs_f64(),
+            },
+            AnyTestCaseSummary::Fuzzing(TestCaseSummary::TimedOut {
+                name,
+                arguments,
+                timeout,
+                test_statistics,
+            }) => TestEvent::TestTimedOut {
+                name,
+                details: TestDetails {
+                    arguments: felts_to_hex(arguments),
+                    shrunk_arguments: None,
+                    gas: None,
+                    fuzzing: Some(test_statistics),
+                    used_resources: None,
+                    time: None,
+                },
+                timeout: timeout.as_secs_f64(),
+            },
+            AnyTestCaseSummary::Single(TestCaseSummary::Ignored { name })
+            | AnyTestCaseSummary::Fuzzing(TestCaseSummary::Ignored { name }) => {
+                TestEvent::TestIgnored { name }
+            }
+            AnyTestCaseSummary::Single(TestCaseSummary::Skipped {})
+            | AnyTestCaseSummary::Fuzzing(TestCaseSummary::Skipped {}) => return None,
+        };
+
+        Some(event)
+    }
+
+    pub fn print(&self) {
+        println!(
+            "{}",
+            serde_json::to_string(self).expect("Failed to serialize test event")
+        );
+    }
+}
+
+fn felts_to_hex(felts: &[Felt252]) -> Vec<String> {
+    felts
+        .iter()
+        .map(|felt| format!("{:#x}", felt.to_biguint()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expected_result::ExpectedTestResult;
+    use std::time::Duration;
+
+    #[test]
+    fn serializes_failed_fuzz_test() {
+        let summary = AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed {
+            name: "tests::fuzzed".to_string(),
+            msg: Some("\n    0x616263 ('abc')\n".to_string()),
+            arguments: vec![Felt252::from(10), Felt252::from(255)],
+            test_statistics: FuzzingStatistics {
+                runs: 12,
+                shrunk_arguments: Some(vec![Felt252::from(0), Felt252::from(255)]),
+                call_sequence: None,
+                cumulative_time: TestTime::default(),
+            },
+            call_trace: None,
+            time: TestTime {
+                total: Duration::from_millis(2500),
+                rpc: Duration::from_millis(500),
+            },
+        });
+
+        let event = TestEvent::from_summary(&summary).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"test_failed","name":"tests::fuzzed","arguments":["0xa","0xff"],"shrunk_arguments":["0x0","0xff"],"fuzzing":{"runs":12},"time":{"total":2.5,"rpc":0.5},"msg":"\n    0x616263 ('abc')\n"}"#
+        );
+    }
+
+    #[test]
+    fn serializes_passed_fuzz_test() {
+        let summary = AnyTestCaseSummary::Fuzzing(TestCaseSummary::Passed {
+            name: "tests::fuzzed".to_string(),
+            msg: None,
+            arguments: vec![],
+            gas_info: GasStatistics {
+                min: 1,
+                max: 3,
+                mean: 2.0,
+                std_deviation: 1.0,
+            },
+            used_resources: UsedResources::default(),
+            test_statistics: FuzzingStatistics {
+                runs: 256,
+                shrunk_arguments: None,
+                call_sequence: None,
+                cumulative_time: TestTime::default(),
+            },
+            trace_data: (),
+            call_trace: None,
+            time: TestTime::default(),
+        });
+
+        let event = TestEvent::from_summary(&summary).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"test_passed","name":"tests::fuzzed","arguments":[],"gas":{"min":1,"max":3,"mean":2.0,"std_deviation":1.0},"fuzzing":{"runs":256},"time":{"total":0.0,"rpc":0.0}}"#
+        );
+    }
+
+    #[test]
+    fn serializes_timed_out_test() {
+        let summary = AnyTestCaseSummary::Single(TestCaseSummary::TimedOut {
+            name: "tests::looping".to_string(),
+            arguments: vec![],
+            timeout: Duration::from_millis(1500),
+            test_statistics: (),
+        });
+
+        let event = TestEvent::from_summary(&summary).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"test_timed_out","name":"tests::looping","arguments":[],"timeout":1.5}"#
+        );
+    }
+
+    #[test]
+    fn serializes_listed_test() {
+        let event = TestEvent::TestListed {
+            name: "tests::forked",
+            package: "package_name",
+            location: "tests",
+            ignored: false,
+            available_gas: None,
+            expected_result: ExpectedTestResult::Success.to_string(),
+            fuzzer: Some(Fuzzer { runs: 22, seed: 38 }),
+            fork: Some(Fork {
+                name: Some("MAINNET"),
+                url: "http://127.0.0.1:5050/rpc",
+                block_id_type: "number",
+                block_id_value: "54060",
+            }),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"test_listed","name":"tests::forked","package":"package_name","location":"tests","ignored":false,"expected_result":"success","fuzzer":{"runs":22,"seed":38},"fork":{"name":"MAINNET","url":"http://127.0.0.1:5050/rpc","block_id_type":"number","block_id_value":"54060"}}"#
+        );
+    }
+
+    #[test]
+    fn skipped_test_has_no_event() {
+        let summary = AnyTestCaseSummary::Single(TestCaseSummary::Skipped {});
+
+        assert!(TestEvent::from_summary(&summary).is_none());
+    }
+}
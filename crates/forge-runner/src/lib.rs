@@ -1,39 +1,57 @@
 use crate::compiled_runnable::{CompiledTestCrateRunnable, FuzzerConfig, TestCaseRunnable};
-use crate::fuzzer::RandomFuzzer;
-use crate::printing::print_test_result;
-use crate::running::{run_fuzz_test, run_test};
-use crate::test_case_summary::TestCaseSummary;
+use crate::fuzzer::{
+    deserialize_arguments, shrink_candidates, CairoType, CairoValue, CallSource, RandomFuzzer,
+    TargetCall, TypeDeclarations, MAX_SHRINK_RUNS,
+};
+use crate::printing::{print_test_result, print_test_skipped, print_test_started};
+use crate::running::{run_fuzz_test, run_invariant_test, run_test};
+use crate::test_case_summary::{TestCaseSummary, TestTime};
 use crate::test_crate_summary::TestCrateSummary;
-use anyhow::{anyhow, Result};
+use anyhow::{bail, Result};
 
+use cairo_felt::Felt252;
 use cairo_lang_runner::RunnerError;
 use cairo_lang_sierra::ids::ConcreteTypeId;
-use cairo_lang_sierra::program::Function;
+use cairo_lang_sierra::program::{Function, Program};
 use camino::Utf8PathBuf;
+use itertools::Itertools;
+use num_traits::ToPrimitive;
 
 use contracts_data::ContractsData;
+use coverage::CoverageCollector;
+use fixtures::{setup_depth, FixtureSnapshots};
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
+use fuzz_corpus::FuzzCorpus;
+use gas_report::GasReportCollector;
 
 use build_trace_data::save_trace_data;
 use profiler_api::run_profiler;
 use smol_str::SmolStr;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
-use test_case_summary::{AnyTestCaseSummary, Fuzzing};
+use std::time::{Duration, Instant};
+use test_case_summary::{AnyTestCaseSummary, Fuzzing, Single};
 use tokio::sync::mpsc::{channel, Sender};
 use tokio::task::JoinHandle;
 use universal_sierra_compiler_api::{compile_sierra_to_casm, AssembledProgramWithDebugInfo};
 
+mod backtrace;
 pub mod build_trace_data;
 pub mod compiled_runnable;
 pub mod contracts_data;
+pub mod coverage;
 pub mod expected_result;
+pub mod fixtures;
+pub mod fuzz_corpus;
+pub mod gas_report;
+pub mod json_events;
 pub mod profiler_api;
 pub mod test_case_summary;
 pub mod test_crate_summary;
 
+mod call_trace;
 mod fuzzer;
 mod gas;
 mod printing;
@@ -72,6 +90,35 @@ impl ExecutionDataToSave {
     }
 }
 
+/// Format of the output printed while running tests
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Human,
+    /// Newline-delimited JSON events, see [`json_events::TestEvent`]
+    Json,
+}
+
+/// Tests whose call traces are printed, selected with `-vvv` and `-vvvv`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TraceVerbosity {
+    /// Call traces of failed tests
+    Failed,
+    /// Call traces of all tests
+    All,
+}
+
+impl TraceVerbosity {
+    fn from_verbosity(verbosity: u8) -> Option<Self> {
+        match verbosity {
+            0..=2 => None,
+            3 => Some(Self::Failed),
+            _ => Some(Self::All),
+        }
+    }
+}
+
 /// Configuration of the test runner
 #[derive(Debug, PartialEq)]
 #[non_exhaustive]
@@ -81,11 +128,21 @@ pub struct RunnerConfig {
     pub fuzzer_runs: u32,
     pub fuzzer_seed: u64,
     pub detailed_resources: bool,
+    /// Print wall-clock time of every test next to its result
+    pub show_time: bool,
     pub execution_data_to_save: ExecutionDataToSave,
     pub max_n_steps: Option<u32>,
+    /// Wall-clock time limit of a test run, applied to each run of fuzz tests separately
+    pub timeout: Option<Duration>,
+    pub output_format: OutputFormat,
+    pub coverage: bool,
+    pub gas_report: bool,
+    /// Tests whose call traces are printed, `None` if no traces are printed
+    pub trace_verbosity: Option<TraceVerbosity>,
 }
 
 impl RunnerConfig {
+    /// Creates the configuration, options added later are set with the `with_*` methods
     #[must_use]
     #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
     pub fn new(
@@ -104,17 +161,64 @@ impl RunnerConfig {
             fuzzer_runs,
             fuzzer_seed,
             detailed_resources,
+            show_time: false,
             execution_data_to_save: ExecutionDataToSave::from_flags(save_trace_data, build_profile),
             max_n_steps,
+            timeout: None,
+            output_format: OutputFormat::default(),
+            coverage: false,
+            gas_report: false,
+            trace_verbosity: None,
         }
     }
+
+    #[must_use]
+    pub fn with_show_time(mut self, show_time: bool) -> Self {
+        self.show_time = show_time;
+        self
+    }
+
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
+    #[must_use]
+    pub fn with_coverage(mut self, coverage: bool) -> Self {
+        self.coverage = coverage;
+        self
+    }
+
+    #[must_use]
+    pub fn with_gas_report(mut self, gas_report: bool) -> Self {
+        self.gas_report = gas_report;
+        self
+    }
+
+    /// Sets the tests whose call traces are printed from the number of `-v` flags
+    #[must_use]
+    pub fn with_verbosity(mut self, verbosity: u8) -> Self {
+        self.trace_verbosity = TraceVerbosity::from_verbosity(verbosity);
+        self
+    }
 }
 
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct RunnerParams {
     contracts_data: ContractsData,
     environment_variables: HashMap<String, String>,
+    coverage: CoverageCollector,
+    gas_report: GasReportCollector,
+    fuzz_corpus: FuzzCorpus,
+    fixtures: FixtureSnapshots,
 }
 
 impl RunnerParams {
@@ -122,12 +226,35 @@ impl RunnerParams {
     pub fn new(
         contracts_data: ContractsData,
         environment_variables: HashMap<String, String>,
+        fuzz_corpus: FuzzCorpus,
     ) -> Self {
         Self {
             contracts_data,
             environment_variables,
+            coverage: CoverageCollector::default(),
+            gas_report: GasReportCollector::default(),
+            fuzz_corpus,
+            fixtures: FixtureSnapshots::default(),
         }
     }
+
+    /// Coverage collected from tests run with these params, if `RunnerConfig::coverage` is set
+    #[must_use]
+    pub fn coverage(&self) -> &CoverageCollector {
+        &self.coverage
+    }
+
+    /// Resources used by contract calls in tests run with these params, if `RunnerConfig::gas_report` is set
+    #[must_use]
+    pub fn gas_report(&self) -> &GasReportCollector {
+        &self.gas_report
+    }
+
+    /// Failing fuzz inputs, updated with failures found and fixed by tests run with these params
+    #[must_use]
+    pub fn fuzz_corpus(&self) -> &FuzzCorpus {
+        &self.fuzz_corpus
+    }
 }
 
 /// Exit status of the runner
@@ -152,6 +279,7 @@ pub enum TestCrateRunResult {
     Interrupted(TestCrateSummary),
 }
 
+#[allow(clippy::too_many_lines)]
 pub async fn run_tests_from_crate(
     tests: Arc<CompiledTestCrateRunnable>,
     runner_config: Arc<RunnerConfig>,
@@ -160,8 +288,29 @@ pub async fn run_tests_from_crate(
 ) -> Result<TestCrateRunResult> {
     let sierra_program = &tests.sierra_program;
     let casm_program = Arc::new(compile_sierra_to_casm(sierra_program)?);
+    let type_declarations: TypeDeclarations = sierra_program
+        .type_declarations
+        .iter()
+        .map(|declaration| (declaration.id.clone(), &declaration.long_id))
+        .collect();
+
+    if runner_config.coverage {
+        let tracked_crates = [tests.package_name.as_str(), "tests"];
+        runner_params
+            .coverage
+            .set_contracts(&runner_params.contracts_data, &tracked_crates);
+        runner_params.coverage.set_test_crate(
+            sierra_program,
+            &casm_program,
+            &tracked_crates,
+            &tests
+                .test_cases
+                .iter()
+                .map(TestCaseRunnable::function_name)
+                .collect(),
+        );
+    }
 
-    let mut tasks = FuturesUnordered::new();
     let test_cases = &tests.test_cases;
     // Initiate two channels to manage the `--exit-first` flag.
     // Owing to `cheatnet` fork's utilization of its own Tokio runtime for RPC requests,
@@ -169,61 +318,124 @@ pub async fn run_tests_from_crate(
     // As `spawn_blocking` can't be prematurely cancelled (refer: https://dtantsur.github.io/rust-openstack/tokio/task/fn.spawn_blocking.html),
     // a channel is used to signal the task that test processing is no longer necessary.
     let (send, mut rec) = channel(1);
+    // Skipped test cases do not carry their names, so track the ones which did not report a result
+    let mut not_reported: HashSet<&str> = HashSet::new();
 
+    // Fixed arguments are checked before any test is run, a mismatch is an error in Scarb.toml
     for case in test_cases {
-        let case_name = case.name.clone();
-
-        if !tests_filter.should_be_run(case) {
-            tasks.push(tokio::task::spawn(async {
-                // TODO TestCaseType should also be encoded in the test case definition
-                Ok(AnyTestCaseSummary::Single(TestCaseSummary::Ignored {
-                    name: case_name,
-                }))
-            }));
-            continue;
+        if let Some(fixed_arguments) = &case.fixed_arguments {
+            let function = test_function(sierra_program, case)?;
+            let args = argument_types(function, &type_declarations)?;
+            if deserialize_arguments(&args, fixed_arguments.calldata.clone()).is_none() {
+                bail!(
+                    "Arguments = [{}] of test case = {} from `test_case` in Scarb.toml do not match the parameters of the test function, expected serialized values of types: [{}]",
+                    fixed_arguments.calldata.iter().join(", "),
+                    case.name,
+                    function_args(function, &BUILTINS).iter().join(", "),
+                );
+            }
+        }
+    }
+
+    // Setup fixtures have to finish before the tests starting from their state, outermost first
+    let mut batches: BTreeMap<usize, Vec<&TestCaseRunnable>> = BTreeMap::new();
+    for case in test_cases {
+        let order = if case.is_setup_fixture {
+            setup_depth(&case.name)
+        } else {
+            usize::MAX
         };
+        batches.entry(order).or_default().push(case);
+    }
 
-        let function = sierra_program
-            .funcs
-            .iter()
-            .find(|f| f.id.debug_name.as_ref().unwrap().ends_with(&case_name))
-            .ok_or(RunnerError::MissingFunction { suffix: case_name })?;
+    let setup_fixtures: HashSet<&str> = test_cases
+        .iter()
+        .filter(|case| case.is_setup_fixture)
+        .map(|case| case.name.as_str())
+        .collect();
+    let mut results = vec![];
+    let mut interrupted = false;
 
-        let args = function_args(function, &BUILTINS);
+    for batch in batches.into_values() {
+        let mut tasks = FuturesUnordered::new();
 
-        let case = Arc::new(case.clone());
-        let args: Vec<ConcreteTypeId> = args.into_iter().cloned().collect();
+        for case in batch {
+            let case_name = case.name.clone();
+
+            if !tests_filter.should_be_run(case) {
+                if case.is_setup_fixture {
+                    runner_params.fixtures.insert_failure(
+                        &case.name,
+                        format!("\n    Setup fixture {case_name} is ignored\n"),
+                    );
+                    continue;
+                }
+                tasks.push(tokio::task::spawn(async {
+                    // TODO TestCaseType should also be encoded in the test case definition
+                    Ok(AnyTestCaseSummary::Single(TestCaseSummary::Ignored {
+                        name: case_name,
+                    }))
+                }));
+                continue;
+            };
 
-        tasks.push(choose_test_strategy_and_run(
-            args,
-            case,
-            casm_program.clone(),
-            runner_config.clone(),
-            runner_params.clone(),
-            send.clone(),
-        ));
-    }
+            let args = argument_types(test_function(sierra_program, case)?, &type_declarations)?;
 
-    let mut results = vec![];
-    let mut interrupted = false;
+            if !case.is_setup_fixture {
+                not_reported.insert(&case.name);
+            }
+            let case = Arc::new(case.clone());
+
+            tasks.push(choose_test_strategy_and_run(
+                args,
+                case,
+                casm_program.clone(),
+                runner_config.clone(),
+                runner_params.clone(),
+                send.clone(),
+            ));
+        }
 
-    while let Some(task) = tasks.next().await {
-        let result = task??;
+        while let Some(task) = tasks.next().await {
+            let result = task??;
+
+            if let Some(name) = result.name() {
+                not_reported.remove(name);
+
+                // Setup fixtures are not tests, their failures are reported by the tests using them
+                if setup_fixtures.contains(name) {
+                    if !result.is_passed() {
+                        runner_params
+                            .fixtures
+                            .insert_failure(name, setup_failure_msg(name, &result));
+                    }
+                    continue;
+                }
+            }
+            print_test_result(&result, &runner_config);
+            maybe_save_execution_data(&result, &runner_config.execution_data_to_save)?;
 
-        print_test_result(&result, &runner_config);
-        maybe_save_execution_data(&result, &runner_config.execution_data_to_save)?;
+            if result.is_failed() && runner_config.exit_first {
+                interrupted = true;
+                rec.close();
+            }
 
-        if result.is_failed() && runner_config.exit_first {
-            interrupted = true;
-            rec.close();
+            results.push(result);
         }
+    }
 
-        results.push(result);
+    for name in test_cases
+        .iter()
+        .map(|case| case.name.as_str())
+        .filter(|name| not_reported.contains(name))
+    {
+        print_test_skipped(name, &runner_config);
     }
 
     let summary = TestCrateSummary {
         test_case_summaries: results,
         runner_exit_status: RunnerStatus::Default,
+        tests_location: tests.tests_location,
     };
 
     if interrupted {
@@ -233,6 +445,17 @@ pub async fn run_tests_from_crate(
     }
 }
 
+/// Message the tests using the setup fixture fail with, when the fixture did not pass
+fn setup_failure_msg(setup_name: &str, result: &AnyTestCaseSummary) -> String {
+    if result.is_timed_out() {
+        return format!("\n    Setup fixture {setup_name} timed out\n");
+    }
+    match result.msg() {
+        Some(msg) => format!("\n    Setup fixture {setup_name} failed:{msg}"),
+        None => format!("\n    Setup fixture {setup_name} failed\n"),
+    }
+}
+
 fn maybe_save_execution_data(
     result: &AnyTestCaseSummary,
     execution_data_to_save: &ExecutionDataToSave,
@@ -257,14 +480,35 @@ fn maybe_save_execution_data(
 
 #[allow(clippy::too_many_arguments)]
 fn choose_test_strategy_and_run(
-    args: Vec<ConcreteTypeId>,
+    args: Vec<CairoType>,
     case: Arc<TestCaseRunnable>,
     casm_program: Arc<AssembledProgramWithDebugInfo>,
     runner_config: Arc<RunnerConfig>,
     runner_params: Arc<RunnerParams>,
     send: Sender<()>,
 ) -> JoinHandle<Result<AnyTestCaseSummary>> {
-    if args.is_empty() {
+    if let Some(fixed_arguments) = &case.fixed_arguments {
+        let calldata = fixed_arguments.calldata.clone();
+        tokio::task::spawn(async move {
+            let res = run_with_fixed_arguments(
+                &args,
+                calldata,
+                case,
+                casm_program,
+                runner_config,
+                runner_params,
+                send,
+            )
+            .await?;
+            Ok(AnyTestCaseSummary::Single(res))
+        })
+    } else if args.is_empty() && case.invariant {
+        tokio::task::spawn(async move {
+            let res = run_with_invariants(case, casm_program, runner_config, runner_params, send)
+                .await??;
+            Ok(AnyTestCaseSummary::Fuzzing(res))
+        })
+    } else if args.is_empty() {
         tokio::task::spawn(async move {
             let res = run_test(case, casm_program, runner_config, runner_params, send).await??;
             Ok(AnyTestCaseSummary::Single(res))
@@ -280,7 +524,7 @@ fn choose_test_strategy_and_run(
 }
 
 fn run_with_fuzzing(
-    args: Vec<ConcreteTypeId>,
+    args: Vec<CairoType>,
     case: Arc<TestCaseRunnable>,
     casm_program: Arc<AssembledProgramWithDebugInfo>,
     runner_config: Arc<RunnerConfig>,
@@ -291,52 +535,219 @@ fn run_with_fuzzing(
         if send.is_closed() {
             return Ok(TestCaseSummary::Skipped {});
         }
+        print_test_started(&case, &runner_config);
+        let start = Instant::now();
 
         let (fuzzing_send, mut fuzzing_rec) = channel(1);
-        let args = args
-            .iter()
-            .map(|arg| {
-                arg.debug_name
-                    .as_ref()
-                    .ok_or_else(|| anyhow!("Type {arg:?} does not have a debug name"))
-                    .map(SmolStr::as_str)
-            })
-            .collect::<Result<Vec<_>>>()?;
-
-        let (fuzzer_runs, fuzzer_seed) = match case.fuzzer_config {
-            Some(FuzzerConfig {
-                fuzzer_runs,
-                fuzzer_seed,
-            }) => (fuzzer_runs, fuzzer_seed),
-            _ => (runner_config.fuzzer_runs, runner_config.fuzzer_seed),
+
+        let (fuzzer_runs, fuzzer_seed) = fuzzer_config(&case, &runner_config);
+        let (results, failing_args) = if let Some((failing_args, result)) = replay_fuzz_corpus(
+            &args,
+            &case,
+            &casm_program,
+            &runner_config,
+            &runner_params,
+            &send,
+        )
+        .await?
+        {
+            (vec![result], Some(failing_args))
+        } else {
+            let mut fuzzer = RandomFuzzer::create(fuzzer_seed, fuzzer_runs, &args);
+
+            let mut tasks = FuturesUnordered::new();
+
+            for _ in 1..=fuzzer_runs {
+                let args = fuzzer.next_args();
+
+                let task = run_fuzz_test(
+                    args.clone(),
+                    case.clone(),
+                    casm_program.clone(),
+                    runner_config.clone(),
+                    runner_params.clone(),
+                    send.clone(),
+                    fuzzing_send.clone(),
+                );
+                tasks.push(async move { task.await.map(|result| (args, result)) });
+            }
+
+            let mut results = vec![];
+            let mut failing_args = None;
+            while let Some(task) = tasks.next().await {
+                let (args, result) = task?;
+                let result = result?;
+
+                results.push(result.clone());
+
+                match result {
+                    TestCaseSummary::Failed { arguments, .. } => {
+                        fuzzing_rec.close();
+                        runner_params
+                            .fuzz_corpus()
+                            .add(&case.name, &arguments, fuzzer_seed);
+                        failing_args = Some(args);
+                        break;
+                    }
+                    // Timed out arguments are neither stored nor shrunk, as rerunning them would take too long
+                    TestCaseSummary::TimedOut { .. } => {
+                        fuzzing_rec.close();
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            (results, failing_args)
+        };
+
+        let runs = u32::try_from(
+            results
+                .iter()
+                .filter(|item| {
+                    matches!(
+                        item,
+                        TestCaseSummary::Passed { .. } | TestCaseSummary::Failed { .. }
+                    )
+                })
+                .count(),
+        )?;
+
+        let mut fuzzing_run_summary: TestCaseSummary<Fuzzing> = TestCaseSummary::from(results);
+
+        if let (
+            Some(failing_args),
+            TestCaseSummary::Failed {
+                test_statistics, ..
+            },
+        ) = (failing_args, &mut fuzzing_run_summary)
+        {
+            test_statistics.shrunk_arguments = shrink_failing_arguments(
+                &args,
+                failing_args,
+                &case,
+                &casm_program,
+                &runner_config,
+                &runner_params,
+                &send,
+            )
+            .await?
+            .map(|shrunk| shrunk.iter().flat_map(CairoValue::serialize).collect());
+        }
+
+        if let TestCaseSummary::Passed { .. } = fuzzing_run_summary {
+            // Because we execute tests parallel, it's possible to
+            // get Passed after Skipped. To treat fuzzing a test as Passed
+            // we have to ensure that all fuzzing subtests Passed
+            if runs != fuzzer_runs {
+                return Ok(TestCaseSummary::Skipped {});
+            };
         };
-        let mut fuzzer = RandomFuzzer::create(fuzzer_seed, fuzzer_runs, &args)?;
+
+        Ok(fuzzing_run_summary.with_wall_clock_time(start.elapsed()))
+    })
+}
+
+/// Runs a case of a parametrized test with its fixed arguments
+async fn run_with_fixed_arguments(
+    types: &[CairoType],
+    calldata: Vec<Felt252>,
+    case: Arc<TestCaseRunnable>,
+    casm_program: Arc<AssembledProgramWithDebugInfo>,
+    runner_config: Arc<RunnerConfig>,
+    runner_params: Arc<RunnerParams>,
+    send: Sender<()>,
+) -> Result<TestCaseSummary<Single>> {
+    if send.is_closed() {
+        return Ok(TestCaseSummary::Skipped {});
+    }
+    print_test_started(&case, &runner_config);
+
+    // Arguments of all cases are checked before running the tests
+    let args = deserialize_arguments(types, calldata)
+        .expect("Arguments of the test case should match the parameters of the test function");
+
+    // A case is run once, so its fuzzing channel is never closed
+    let (case_send, _case_rec) = channel(1);
+
+    run_fuzz_test(
+        args,
+        case,
+        casm_program,
+        runner_config,
+        runner_params,
+        send,
+        case_send,
+    )
+    .await?
+}
+
+fn fuzzer_config(case: &TestCaseRunnable, runner_config: &RunnerConfig) -> (u32, u64) {
+    match case.fuzzer_config {
+        Some(FuzzerConfig {
+            fuzzer_runs,
+            fuzzer_seed,
+        }) => (fuzzer_runs, fuzzer_seed),
+        _ => (runner_config.fuzzer_runs, runner_config.fuzzer_seed),
+    }
+}
+
+fn run_with_invariants(
+    case: Arc<TestCaseRunnable>,
+    casm_program: Arc<AssembledProgramWithDebugInfo>,
+    runner_config: Arc<RunnerConfig>,
+    runner_params: Arc<RunnerParams>,
+    send: Sender<()>,
+) -> JoinHandle<Result<TestCaseSummary<Fuzzing>>> {
+    tokio::task::spawn(async move {
+        if send.is_closed() {
+            return Ok(TestCaseSummary::Skipped {});
+        }
+        print_test_started(&case, &runner_config);
+        let start = Instant::now();
+
+        let (invariant_send, mut invariant_rec) = channel(1);
+
+        let (fuzzer_runs, fuzzer_seed) = fuzzer_config(&case, &runner_config);
+        // Each run generates its calls from a seed drawn by the fuzzer
+        let mut fuzzer = RandomFuzzer::create(fuzzer_seed, fuzzer_runs, &[CairoType::U64]);
 
         let mut tasks = FuturesUnordered::new();
 
         for _ in 1..=fuzzer_runs {
-            let args = fuzzer.next_args();
+            let seed = match fuzzer.next_args().as_slice() {
+                [CairoValue::Felt(seed)] => seed.to_u64().unwrap(),
+                _ => unreachable!("Seed is represented by a single felt"),
+            };
 
-            tasks.push(run_fuzz_test(
-                args,
+            tasks.push(run_invariant_test(
+                CallSource::Random(seed),
                 case.clone(),
                 casm_program.clone(),
                 runner_config.clone(),
                 runner_params.clone(),
                 send.clone(),
-                fuzzing_send.clone(),
+                invariant_send.clone(),
             ));
         }
 
         let mut results = vec![];
+        let mut failing_calls = None;
         while let Some(task) = tasks.next().await {
-            let result = task??;
+            let (result, calls) = task??;
 
             results.push(result.clone());
 
-            if let TestCaseSummary::Failed { .. } = result {
-                fuzzing_rec.close();
-                break;
+            match result {
+                TestCaseSummary::Failed { .. } => {
+                    invariant_rec.close();
+                    failing_calls = Some(calls);
+                    break;
+                }
+                TestCaseSummary::TimedOut { .. } => {
+                    invariant_rec.close();
+                    break;
+                }
+                _ => {}
             }
         }
 
@@ -352,21 +763,213 @@ fn run_with_fuzzing(
                 .count(),
         )?;
 
-        let fuzzing_run_summary: TestCaseSummary<Fuzzing> = TestCaseSummary::from(results);
+        let mut invariant_run_summary: TestCaseSummary<Fuzzing> = TestCaseSummary::from(results);
+
+        if let (
+            Some(failing_calls),
+            TestCaseSummary::Failed {
+                test_statistics, ..
+            },
+        ) = (failing_calls, &mut invariant_run_summary)
+        {
+            let shrunk_calls = shrink_call_sequence(
+                failing_calls,
+                &case,
+                &casm_program,
+                &runner_config,
+                &runner_params,
+                &send,
+            )
+            .await?;
+            test_statistics.call_sequence =
+                Some(shrunk_calls.iter().map(ToString::to_string).collect());
+        }
 
-        if let TestCaseSummary::Passed { .. } = fuzzing_run_summary {
-            // Because we execute tests parallel, it's possible to
-            // get Passed after Skipped. To treat fuzzing a test as Passed
-            // we have to ensure that all fuzzing subtests Passed
+        if let TestCaseSummary::Passed { .. } = invariant_run_summary {
+            // All runs have to pass for the invariant test to pass, see `run_with_fuzzing`
             if runs != fuzzer_runs {
                 return Ok(TestCaseSummary::Skipped {});
             };
         };
 
-        Ok(fuzzing_run_summary)
+        Ok(invariant_run_summary.with_wall_clock_time(start.elapsed()))
     })
 }
 
+/// Looks for the shortest sequence of calls still failing the test,
+/// by removing calls from `failing_calls` one at a time.
+async fn shrink_call_sequence(
+    failing_calls: Vec<TargetCall>,
+    case: &Arc<TestCaseRunnable>,
+    casm_program: &Arc<AssembledProgramWithDebugInfo>,
+    runner_config: &Arc<RunnerConfig>,
+    runner_params: &Arc<RunnerParams>,
+    send: &Sender<()>,
+) -> Result<Vec<TargetCall>> {
+    // Invariant channel is closed after the failure, so shrinking runs need their own one
+    let (shrinking_send, _shrinking_rec) = channel(1);
+    let mut current = failing_calls;
+    let mut runs = 0;
+    let mut index = 0;
+
+    while index < current.len() && runs < MAX_SHRINK_RUNS {
+        runs += 1;
+
+        let mut calls = current.clone();
+        calls.remove(index);
+
+        let (result, executed) = run_invariant_test(
+            CallSource::Replay(calls),
+            case.clone(),
+            casm_program.clone(),
+            runner_config.clone(),
+            runner_params.clone(),
+            send.clone(),
+            shrinking_send.clone(),
+        )
+        .await??;
+
+        match result {
+            // Calls after the failure are not executed, so they are dropped as well
+            TestCaseSummary::Failed { .. } => current = executed,
+            // Execution was interrupted e.g. by `--exit-first`
+            TestCaseSummary::Skipped {} => break,
+            TestCaseSummary::Passed { .. }
+            | TestCaseSummary::TimedOut { .. }
+            | TestCaseSummary::Ignored { .. } => index += 1,
+        }
+    }
+
+    Ok(current)
+}
+
+/// Replays failing inputs stored in the fuzz corpus, removing the ones which no longer fail the test.
+/// Returns the first input which still fails the test together with the result.
+async fn replay_fuzz_corpus(
+    types: &[CairoType],
+    case: &Arc<TestCaseRunnable>,
+    casm_program: &Arc<AssembledProgramWithDebugInfo>,
+    runner_config: &Arc<RunnerConfig>,
+    runner_params: &Arc<RunnerParams>,
+    send: &Sender<()>,
+) -> Result<Option<(Vec<CairoValue>, TestCaseSummary<Single>)>> {
+    let (replay_send, _replay_rec) = channel(1);
+    let corpus = runner_params.fuzz_corpus();
+
+    for entry in corpus.inputs(&case.name) {
+        // Inputs not matching the current test arguments are obsolete
+        let Some(args) = entry
+            .felts()
+            .and_then(|felts| deserialize_arguments(types, felts))
+        else {
+            corpus.remove(&entry);
+            continue;
+        };
+
+        let result = run_fuzz_test(
+            args.clone(),
+            case.clone(),
+            casm_program.clone(),
+            runner_config.clone(),
+            runner_params.clone(),
+            send.clone(),
+            replay_send.clone(),
+        )
+        .await??;
+
+        match result {
+            TestCaseSummary::Failed { .. } => return Ok(Some((args, result))),
+            TestCaseSummary::Passed { .. } => corpus.remove(&entry),
+            TestCaseSummary::TimedOut { .. }
+            | TestCaseSummary::Ignored { .. }
+            | TestCaseSummary::Skipped {} => {}
+        }
+    }
+
+    Ok(None)
+}
+
+/// Looks for the simplest arguments which still fail the test, starting from `failing_args`.
+/// Returns `None` if no simpler failing arguments were found.
+async fn shrink_failing_arguments(
+    types: &[CairoType],
+    failing_args: Vec<CairoValue>,
+    case: &Arc<TestCaseRunnable>,
+    casm_program: &Arc<AssembledProgramWithDebugInfo>,
+    runner_config: &Arc<RunnerConfig>,
+    runner_params: &Arc<RunnerParams>,
+    send: &Sender<()>,
+) -> Result<Option<Vec<CairoValue>>> {
+    // Fuzzing channel is closed after the failure, so shrinking runs need their own one
+    let (shrinking_send, _shrinking_rec) = channel(1);
+    let mut current = failing_args;
+    let mut shrunk = false;
+    let mut runs = 0;
+
+    'shrinking: loop {
+        for (index, ty) in types.iter().enumerate() {
+            for candidate in shrink_candidates(ty, &current[index]) {
+                if runs == MAX_SHRINK_RUNS {
+                    break 'shrinking;
+                }
+                runs += 1;
+
+                let mut args = current.clone();
+                args[index] = candidate;
+
+                let result = run_fuzz_test(
+                    args.clone(),
+                    case.clone(),
+                    casm_program.clone(),
+                    runner_config.clone(),
+                    runner_params.clone(),
+                    send.clone(),
+                    shrinking_send.clone(),
+                )
+                .await??;
+
+                match result {
+                    TestCaseSummary::Failed { .. } => {
+                        current = args;
+                        shrunk = true;
+                        continue 'shrinking;
+                    }
+                    // Execution was interrupted e.g. by `--exit-first`
+                    TestCaseSummary::Skipped {} => break 'shrinking,
+                    TestCaseSummary::Passed { .. }
+                    | TestCaseSummary::TimedOut { .. }
+                    | TestCaseSummary::Ignored { .. } => {}
+                }
+            }
+        }
+        break;
+    }
+
+    Ok(shrunk.then_some(current))
+}
+
+fn test_function<'a>(sierra_program: &'a Program, case: &TestCaseRunnable) -> Result<&'a Function> {
+    let function_name = case.function_name();
+    let function = sierra_program
+        .funcs
+        .iter()
+        .find(|f| f.id.debug_name.as_ref().unwrap().ends_with(function_name))
+        .ok_or_else(|| RunnerError::MissingFunction {
+            suffix: function_name.to_string(),
+        })?;
+    Ok(function)
+}
+
+fn argument_types(
+    function: &Function,
+    type_declarations: &TypeDeclarations,
+) -> Result<Vec<CairoType>> {
+    function_args(function, &BUILTINS)
+        .into_iter()
+        .map(|arg| CairoType::from_type_id(arg, type_declarations))
+        .collect()
+}
+
 fn function_args<'a>(function: &'a Function, builtins: &[&str]) -> Vec<&'a ConcreteTypeId> {
     let builtins: Vec<_> = builtins
         .iter()
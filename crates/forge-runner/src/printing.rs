@@ -1,14 +1,36 @@
 use crate::{
+    compiled_runnable::TestCaseRunnable,
+    json_events::TestEvent,
     test_case_summary::{AnyTestCaseSummary, FuzzingStatistics, TestCaseSummary},
-    RunnerConfig,
+    OutputFormat, RunnerConfig,
 };
 use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::UsedResources;
 use console::style;
+use std::time::Duration;
+
+/// Setup fixtures are not tests, so their runs are not reported
+pub(crate) fn print_test_started(case: &TestCaseRunnable, runner_config: &RunnerConfig) {
+    if runner_config.output_format == OutputFormat::Json && !case.is_setup_fixture {
+        TestEvent::TestStarted { name: &case.name }.print();
+    }
+}
+
+pub(crate) fn print_test_skipped(name: &str, runner_config: &RunnerConfig) {
+    if runner_config.output_format == OutputFormat::Json {
+        TestEvent::TestSkipped { name }.print();
+    }
+}
 
 pub(crate) fn print_test_result(
     any_test_result: &AnyTestCaseSummary,
     runner_config: &RunnerConfig,
 ) {
+    if runner_config.output_format == OutputFormat::Json {
+        if let Some(event) = TestEvent::from_summary(any_test_result) {
+            event.print();
+        }
+        return;
+    }
     if any_test_result.is_skipped() {
         return;
     }
@@ -21,7 +43,7 @@ pub(crate) fn print_test_result(
     if let AnyTestCaseSummary::Fuzzing(test_result) = any_test_result {
         fuzzer_report = match test_result {
             TestCaseSummary::Passed {
-                test_statistics: FuzzingStatistics { runs },
+                test_statistics: FuzzingStatistics { runs, .. },
                 gas_info,
                 ..
             } => Some(format!(
@@ -30,12 +52,43 @@ pub(crate) fn print_test_result(
             )),
             TestCaseSummary::Failed {
                 arguments,
-                test_statistics: FuzzingStatistics { runs },
+                test_statistics:
+                    FuzzingStatistics {
+                        runs,
+                        shrunk_arguments,
+                        call_sequence,
+                    },
+                ..
+            } => Some(match (shrunk_arguments, call_sequence) {
+                (_, Some(call_sequence)) => format!(
+                    " (runs: {runs}, calls: {}){}",
+                    call_sequence.len(),
+                    format_call_sequence(call_sequence)
+                ),
+                (Some(shrunk_arguments), None) => format!(
+                    " (runs: {runs}, arguments: {arguments:?}, shrunk arguments: {shrunk_arguments:?})"
+                ),
+                (None, None) => format!(" (runs: {runs}, arguments: {arguments:?})"),
+            }),
+            TestCaseSummary::TimedOut {
+                arguments,
+                test_statistics: FuzzingStatistics { runs, .. },
                 ..
             } => Some(format!(" (runs: {runs}, arguments: {arguments:?})")),
             _ => None,
         };
     }
+    // Cases of parametrized tests are the only single tests run with arguments
+    if let AnyTestCaseSummary::Single(
+        TestCaseSummary::Passed { arguments, .. }
+        | TestCaseSummary::Failed { arguments, .. }
+        | TestCaseSummary::TimedOut { arguments, .. },
+    ) = any_test_result
+    {
+        if !arguments.is_empty() {
+            fuzzer_report = Some(format!(" (arguments: {arguments:?})"));
+        }
+    }
     let fuzzer_report = fuzzer_report.unwrap_or_else(String::new);
 
     let gas_usage = match any_test_result {
@@ -52,7 +105,78 @@ pub(crate) fn print_test_result(
         _ => String::new(),
     };
 
-    println!("{result_header} {result_name}{fuzzer_report}{gas_usage}{used_resources}{result_msg}");
+    let time = if runner_config.show_time {
+        format_time(any_test_result)
+    } else {
+        String::new()
+    };
+
+    let call_trace = any_test_result.call_trace().unwrap_or_default();
+
+    println!(
+        "{result_header} {result_name}{fuzzer_report}{gas_usage}{time}{used_resources}{result_msg}{call_trace}"
+    );
+}
+
+/// Time of fuzz tests is the wall-clock time of all runs, which are executed in parallel,
+/// so their cumulative time and the mean time of a run are shown too
+fn format_time(any_test_result: &AnyTestCaseSummary) -> String {
+    let Some(time) = any_test_result.time() else {
+        return String::new();
+    };
+    let cumulative = match any_test_result {
+        AnyTestCaseSummary::Fuzzing(
+            TestCaseSummary::Passed {
+                test_statistics:
+                    FuzzingStatistics {
+                        runs,
+                        cumulative_time,
+                        ..
+                    },
+                ..
+            }
+            | TestCaseSummary::Failed {
+                test_statistics:
+                    FuzzingStatistics {
+                        runs,
+                        cumulative_time,
+                        ..
+                    },
+                ..
+            },
+        ) => {
+            let runs = u32::try_from(*runs).unwrap_or(u32::MAX).max(1);
+            format!(
+                ", cumulative: {}, mean: {}",
+                format_duration(cumulative_time.total),
+                format_duration(cumulative_time.total / runs)
+            )
+        }
+        _ => String::new(),
+    };
+    let rpc = if time.rpc.is_zero() {
+        String::new()
+    } else {
+        format!(", rpc: {}", format_duration(time.rpc))
+    };
+
+    format!(" (time: {}{cumulative}{rpc})", format_duration(time.total))
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}s", duration.as_secs_f64())
+}
+
+fn format_call_sequence(call_sequence: &[String]) -> String {
+    if call_sequence.is_empty() {
+        return String::new();
+    }
+    let calls: String = call_sequence
+        .iter()
+        .map(|call| format!("\n    {call}"))
+        .collect();
+
+    format!("\n\nCall sequence:{calls}")
 }
 
 fn format_detailed_resources(used_resources: &UsedResources) -> String {
@@ -98,6 +222,13 @@ where
 }
 
 fn result_message(any_test_result: &AnyTestCaseSummary) -> String {
+    if let AnyTestCaseSummary::Single(TestCaseSummary::TimedOut { timeout, .. })
+    | AnyTestCaseSummary::Fuzzing(TestCaseSummary::TimedOut { timeout, .. }) = any_test_result
+    {
+        return format!(
+            "\n\nFailure data:\n    Test did not finish within the timeout of {timeout:?}\n"
+        );
+    }
     if let Some(msg) = any_test_result.msg() {
         if any_test_result.is_passed() {
             return format!("\n\nSuccess data:{msg}");
@@ -113,6 +244,9 @@ fn result_header(any_test_result: &AnyTestCaseSummary) -> String {
     if any_test_result.is_passed() {
         return format!("[{}]", style("PASS").green());
     }
+    if any_test_result.is_timed_out() {
+        return format!("[{}]", style("TIMEOUT").red());
+    }
     if any_test_result.is_failed() {
         return format!("[{}]", style("FAIL").red());
     }
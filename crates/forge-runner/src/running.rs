@@ -4,12 +4,18 @@ use std::default::Default;
 use std::marker::PhantomData;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use crate::backtrace::with_backtrace;
+use crate::call_trace::render_call_trace;
 use crate::compiled_runnable::ValidatedForkConfig;
 use crate::contracts_data::ContractsData;
+use crate::fixtures::StateSnapshot;
+use crate::fuzzer::{CairoValue, CallSource, InvariantCalls, TargetCall};
 use crate::gas::calculate_used_gas;
-use crate::test_case_summary::{Single, TestCaseSummary};
-use crate::{RunnerConfig, RunnerParams, TestCaseRunnable, CACHE_DIR};
+use crate::printing::print_test_started;
+use crate::test_case_summary::{Single, TestCaseSummary, TestTime};
+use crate::{RunnerConfig, RunnerParams, TestCaseRunnable, TraceVerbosity, CACHE_DIR};
 use anyhow::{bail, ensure, Result};
 use blockifier::execution::entry_point::{EntryPointExecutionContext, ExecutionResources};
 use blockifier::execution::execution_utils::ReadOnlySegments;
@@ -22,7 +28,9 @@ use cairo_lang_casm::instructions::Instruction;
 use cairo_lang_runner::casm_run::{
     build_cairo_runner, hint_to_hint_params, run_function_with_runner,
 };
-use cairo_lang_runner::{initialize_vm, Arg, RunResult, RunnerError, SierraCasmRunner};
+use cairo_lang_runner::{
+    initialize_vm, Arg, RunResult, RunResultValue, RunnerError, SierraCasmRunner,
+};
 use cairo_lang_sierra::extensions::segment_arena::SegmentArenaType;
 use cairo_lang_sierra::extensions::NoGenericArgsGenericType;
 use cairo_lang_sierra::ids::GenericTypeId;
@@ -32,15 +40,20 @@ use cairo_vm::vm::vm_core::VirtualMachine;
 use camino::Utf8Path;
 use cheatnet::constants as cheatnet_constants;
 use cheatnet::constants::build_test_entry_point;
-use cheatnet::forking::state::ForkStateReader;
-use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::UsedResources;
+use cheatnet::forking::state::{ForkStateReader, RpcTime};
+use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::{
+    CallFailure, UsedResources,
+};
 use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::CallToBlockifierExtension;
 use cheatnet::runtime_extensions::cheatable_starknet_runtime_extension::CheatableStarknetRuntimeExtension;
+use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::invariant::CallSequence;
 use cheatnet::runtime_extensions::forge_runtime_extension::{
     get_all_used_resources, update_top_call_execution_resources, update_top_call_l1_resources,
     ForgeExtension, ForgeRuntime,
 };
-use cheatnet::state::{BlockInfoReader, CallTrace, CheatnetState, ExtendedStateReader};
+use cheatnet::state::{
+    BlockInfoReader, CallTrace, Cancellation, CheatnetState, ExtendedStateReader,
+};
 use runtime::starknet::context::{build_context, set_max_steps};
 use runtime::{ExtendedRuntime, StarknetRuntime};
 use tokio::sync::mpsc::Sender;
@@ -56,15 +69,28 @@ pub fn run_test(
     runner_params: Arc<RunnerParams>,
     send: Sender<()>,
 ) -> JoinHandle<Result<TestCaseSummary<Single>>> {
-    tokio::task::spawn_blocking(move || {
+    let timed_out = timed_out_summary(&case, vec![]);
+    let timeout = test_timeout(&case, &runner_config);
+    let cancellation = Cancellation::default();
+    let run_cancellation = cancellation.clone();
+
+    let run = tokio::task::spawn_blocking(move || {
         // Due to the inability of spawn_blocking to be abruptly cancelled,
         // a channel is used to receive information indicating
         // that the execution of the task is no longer necessary.
         if send.is_closed() {
             return Ok(TestCaseSummary::Skipped {});
         }
-        let run_result =
-            run_test_case(vec![], &case, &casm_program, &runner_config, &runner_params);
+        print_test_started(&case, &runner_config);
+        let run_result = run_test_case(
+            vec![],
+            &case,
+            &casm_program,
+            &runner_config,
+            &runner_params,
+            None,
+            &run_cancellation,
+        );
 
         // TODO: code below is added to fix snforge tests
         // remove it after improve exit-first tests
@@ -73,13 +99,21 @@ pub fn run_test(
             return Ok(TestCaseSummary::Skipped {});
         }
 
-        extract_test_case_summary(run_result, &case, vec![], &runner_params.contracts_data)
-    })
+        extract_test_case_summary(
+            run_result,
+            &case,
+            vec![],
+            &runner_params.contracts_data,
+            runner_config.trace_verbosity,
+        )
+    });
+
+    with_timeout(run, timeout, cancellation, timed_out)
 }
 
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn run_fuzz_test(
-    args: Vec<Felt252>,
+    args: Vec<CairoValue>,
     case: Arc<TestCaseRunnable>,
     casm_program: Arc<AssembledProgramWithDebugInfo>,
     runner_config: Arc<RunnerConfig>,
@@ -87,7 +121,12 @@ pub(crate) fn run_fuzz_test(
     send: Sender<()>,
     fuzzing_send: Sender<()>,
 ) -> JoinHandle<Result<TestCaseSummary<Single>>> {
-    tokio::task::spawn_blocking(move || {
+    let timed_out = timed_out_summary(&case, args.iter().flat_map(CairoValue::serialize).collect());
+    let timeout = test_timeout(&case, &runner_config);
+    let cancellation = Cancellation::default();
+    let run_cancellation = cancellation.clone();
+
+    let run = tokio::task::spawn_blocking(move || {
         // Due to the inability of spawn_blocking to be abruptly cancelled,
         // a channel is used to receive information indicating
         // that the execution of the task is no longer necessary.
@@ -96,11 +135,13 @@ pub(crate) fn run_fuzz_test(
         }
 
         let run_result = run_test_case(
-            args.clone(),
+            args.iter().flat_map(CairoValue::to_args).collect(),
             &case,
             &casm_program,
             &runner_config,
             &runner_params,
+            None,
+            &run_cancellation,
         );
 
         // TODO: code below is added to fix snforge tests
@@ -110,7 +151,113 @@ pub(crate) fn run_fuzz_test(
             return Ok(TestCaseSummary::Skipped {});
         }
 
-        extract_test_case_summary(run_result, &case, args, &runner_params.contracts_data)
+        let arguments = args.iter().flat_map(CairoValue::serialize).collect();
+        extract_test_case_summary(
+            run_result,
+            &case,
+            arguments,
+            &runner_params.contracts_data,
+            runner_config.trace_verbosity,
+        )
+    });
+
+    with_timeout(run, timeout, cancellation, timed_out)
+}
+
+/// Runs an invariant test with calls from the source, returns the calls it executed
+pub(crate) fn run_invariant_test(
+    call_source: CallSource,
+    case: Arc<TestCaseRunnable>,
+    casm_program: Arc<AssembledProgramWithDebugInfo>,
+    runner_config: Arc<RunnerConfig>,
+    runner_params: Arc<RunnerParams>,
+    send: Sender<()>,
+    invariant_send: Sender<()>,
+) -> JoinHandle<Result<(TestCaseSummary<Single>, Vec<TargetCall>)>> {
+    let timed_out = timed_out_summary(&case, vec![]);
+    let timeout = test_timeout(&case, &runner_config);
+    let cancellation = Cancellation::default();
+    let run_cancellation = cancellation.clone();
+
+    let run = tokio::task::spawn_blocking(move || {
+        // Due to the inability of spawn_blocking to be abruptly cancelled,
+        // a channel is used to receive information indicating
+        // that the execution of the task is no longer necessary.
+        if send.is_closed() | invariant_send.is_closed() {
+            return Ok((TestCaseSummary::Skipped {}, vec![]));
+        }
+
+        let mut calls = InvariantCalls::new(&runner_params.contracts_data, call_source);
+        let run_result = run_test_case(
+            vec![],
+            &case,
+            &casm_program,
+            &runner_config,
+            &runner_params,
+            Some(&mut calls),
+            &run_cancellation,
+        );
+
+        if send.is_closed() {
+            return Ok((TestCaseSummary::Skipped {}, vec![]));
+        }
+
+        let summary = extract_test_case_summary(
+            run_result,
+            &case,
+            vec![],
+            &runner_params.contracts_data,
+            runner_config.trace_verbosity,
+        )?;
+        Ok((summary, calls.executed()))
+    });
+
+    // Calls executed by a timed out run are not known
+    with_timeout(run, timeout, cancellation, |timeout| {
+        (timed_out(timeout), vec![])
+    })
+}
+
+fn test_timeout(case: &TestCaseRunnable, runner_config: &RunnerConfig) -> Option<Duration> {
+    case.timeout.or(runner_config.timeout)
+}
+
+/// Builds the summary reported when the run of the test case exceeds its timeout
+fn timed_out_summary(
+    case: &TestCaseRunnable,
+    arguments: Vec<Felt252>,
+) -> impl FnOnce(Duration) -> TestCaseSummary<Single> + Send + 'static {
+    let name = case.name.clone();
+
+    move |timeout| TestCaseSummary::TimedOut {
+        name,
+        arguments,
+        timeout,
+        test_statistics: (),
+    }
+}
+
+/// Waits for the run to finish within the timeout, returns the result of `timed_out` otherwise.
+/// Blocking tasks cannot be aborted, so a timed out run is cancelled and stops by itself
+/// before its next step or request to the fork, its result is discarded.
+fn with_timeout<T: Send + 'static>(
+    run: JoinHandle<Result<T>>,
+    timeout: Option<Duration>,
+    cancellation: Cancellation,
+    timed_out: impl FnOnce(Duration) -> T + Send + 'static,
+) -> JoinHandle<Result<T>> {
+    tokio::spawn(async move {
+        let Some(timeout) = timeout else {
+            return run.await?;
+        };
+
+        match tokio::time::timeout(timeout, run).await {
+            Ok(result) => result?,
+            Err(_) => {
+                cancellation.cancel();
+                Ok(timed_out(timeout))
+            }
+        }
     })
 }
 
@@ -154,27 +301,30 @@ pub struct RunResultWithInfo {
     pub(crate) call_trace: Rc<RefCell<CallTrace>>,
     pub(crate) gas_used: u128,
     pub(crate) used_resources: UsedResources,
+    pub(crate) time: TestTime,
 }
 
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::too_many_lines)]
 pub fn run_test_case(
-    args: Vec<Felt252>,
+    args: Vec<Arg>,
     case: &TestCaseRunnable,
     casm_program: &AssembledProgramWithDebugInfo,
     runner_config: &Arc<RunnerConfig>,
     runner_params: &Arc<RunnerParams>,
+    call_sequence: Option<&mut dyn CallSequence>,
+    cancellation: &Cancellation,
 ) -> Result<RunResultWithInfo> {
+    let start = Instant::now();
     ensure!(
         case.available_gas != Some(0),
         "\n\t`available_gas` attribute was incorrectly configured. Make sure you use scarb >= 2.4.4\n"
     );
 
     let initial_gas = usize::MAX;
-    let runner_args: Vec<Arg> = args.into_iter().map(Arg::Value).collect();
     let (entry_code, builtins) = SierraCasmRunner::create_entry_code_from_params(
         &case.test_details.parameter_types,
-        &runner_args,
+        &args,
         initial_gas,
         casm_program.debug_info[case.test_details.entry_point_offset].0,
     )
@@ -183,14 +333,29 @@ pub fn run_test_case(
 
     let assembled_program: &mut AssembledCairoProgramWithSerde =
         &mut casm_program.assembled_cairo_program.clone();
-    add_header(entry_code, assembled_program);
+    let header_len = add_header(entry_code, assembled_program);
     add_footer(footer, assembled_program);
 
     let (string_to_hint, hints_dict) = create_hints_dict(assembled_program);
 
+    let fixture = runner_params
+        .fixtures
+        .snapshot_for(case.setup_fixture.as_deref())?;
+    let dict_state_reader = fixture
+        .as_ref()
+        .map_or_else(cheatnet_constants::build_testing_state, |snapshot| {
+            snapshot.dict_state_reader.clone()
+        });
+    // Setup fixtures need the state they started from to snapshot the state they leave
+    let initial_state = case.is_setup_fixture.then(|| dict_state_reader.clone());
+
+    let fork_state_reader = get_fork_state_reader(&runner_config.workspace_root, &case.fork_config)
+        .map(|reader| reader.with_cancellation(cancellation.clone()));
+    let rpc_time = fork_state_reader.as_ref().map(ForkStateReader::rpc_time);
+
     let mut state_reader = ExtendedStateReader {
-        dict_state_reader: cheatnet_constants::build_testing_state(),
-        fork_state_reader: get_fork_state_reader(&runner_config.workspace_root, &case.fork_config),
+        dict_state_reader,
+        fork_state_reader,
     };
     let block_info = state_reader.get_block_info()?;
 
@@ -212,8 +377,13 @@ pub fn run_test_case(
 
     let mut cheatnet_state = CheatnetState {
         block_info,
+        cancellation: cancellation.clone(),
+        record_executed_pcs: runner_config.coverage,
         ..Default::default()
     };
+    if let Some(snapshot) = &fixture {
+        cheatnet_state.restore_cheats(&snapshot.cheats);
+    }
 
     let cheatable_runtime = ExtendedRuntime {
         extension: CheatableStarknetRuntimeExtension {
@@ -233,6 +403,7 @@ pub fn run_test_case(
     let forge_extension = ForgeExtension {
         environment_variables: &runner_params.environment_variables,
         contracts: &runner_params.contracts_data.contracts,
+        call_sequence,
     };
 
     let mut forge_runtime = ExtendedRuntime {
@@ -272,7 +443,19 @@ pub fn run_test_case(
                 .vm_resources += &vm_resources_without_inner_calls;
 
             let cells = runner.relocated_memory;
-            let ap = vm.get_relocated_trace().unwrap().last().unwrap().ap;
+            let trace = vm.get_relocated_trace().unwrap();
+            let ap = trace.last().unwrap().ap;
+
+            if runner_config.coverage {
+                let program_len = casm_program.assembled_cairo_program.bytecode.len();
+                // Program segment is relocated to start at 1
+                runner_params.coverage.record_executed_offsets(
+                    trace
+                        .iter()
+                        .filter_map(|entry| entry.pc.checked_sub(1 + header_len))
+                        .filter(|&offset| offset < program_len),
+                );
+            }
 
             let (results_data, gas_counter) =
                 SierraCasmRunner::get_results_data(&case.test_details.return_types, &cells, ap);
@@ -301,16 +484,51 @@ pub fn run_test_case(
     let block_context = get_context(&forge_runtime).block_context.clone();
     let call_trace_ref = get_call_trace_ref(&mut forge_runtime);
 
+    if runner_config.coverage {
+        runner_params
+            .coverage
+            .record_call_trace(&call_trace_ref, &runner_params.contracts_data);
+    }
+
     update_top_call_execution_resources(&mut forge_runtime);
     update_top_call_l1_resources(&mut forge_runtime);
     let used_resources = get_all_used_resources(forge_runtime);
     let gas = calculate_used_gas(&block_context, &mut cached_state, &used_resources)?;
 
+    // Failed setup fixtures are marked as such once their results are reported
+    if let (
+        Some(initial_state),
+        Ok(RunResult {
+            value: RunResultValue::Success(_),
+            ..
+        }),
+    ) = (initial_state, &run_result)
+    {
+        let snapshot = StateSnapshot::from_setup_run(
+            initial_state,
+            &mut cached_state,
+            cheatnet_state.cheats_snapshot(),
+        )?;
+        runner_params.fixtures.insert(&case.name, snapshot);
+    }
+
+    if runner_config.gas_report && run_result.is_ok() {
+        runner_params.gas_report.record_call_trace(
+            &call_trace_ref,
+            &runner_params.contracts_data,
+            &block_context,
+        );
+    }
+
     Ok(RunResultWithInfo {
         run_result,
         gas_used: gas,
         used_resources,
         call_trace: call_trace_ref,
+        time: TestTime {
+            total: start.elapsed(),
+            rpc: rpc_time.as_ref().map(RpcTime::get).unwrap_or_default(),
+        },
     })
 }
 
@@ -319,11 +537,12 @@ fn extract_test_case_summary(
     case: &TestCaseRunnable,
     args: Vec<Felt252>,
     contracts_data: &ContractsData,
+    trace_verbosity: Option<TraceVerbosity>,
 ) -> Result<TestCaseSummary<Single>> {
     match run_result {
         Ok(result_with_info) => {
-            match result_with_info.run_result {
-                Ok(run_result) => Ok(TestCaseSummary::from_run_result_and_info(
+            let mut summary = match result_with_info.run_result {
+                Ok(run_result) => TestCaseSummary::from_run_result_and_info(
                     run_result,
                     case,
                     args,
@@ -331,19 +550,39 @@ fn extract_test_case_summary(
                     result_with_info.used_resources,
                     &result_with_info.call_trace,
                     contracts_data,
-                )),
+                    result_with_info.time,
+                ),
                 // CairoRunError comes from VirtualMachineError which may come from HintException that originates in TestExecutionSyscallHandler
-                Err(RunnerError::CairoRunError(error)) => Ok(TestCaseSummary::Failed {
-                    name: case.name.clone(),
-                    msg: Some(format!(
-                        "\n    {}\n",
-                        error.to_string().replace(" Custom Hint Error: ", "\n    ")
-                    )),
-                    arguments: args,
-                    test_statistics: (),
-                }),
+                Err(RunnerError::CairoRunError(error)) => {
+                    let error = error.to_string();
+                    TestCaseSummary::Failed {
+                        name: case.name.clone(),
+                        msg: with_backtrace(
+                            Some(format!(
+                                "\n    {}\n",
+                                error.replace(" Custom Hint Error: ", "\n    ")
+                            )),
+                            // Errors of contract calls are re-raised, failing the test with a hint error
+                            &CallFailure::Error { msg: error },
+                            &result_with_info.call_trace,
+                            contracts_data,
+                        ),
+                        arguments: args,
+                        test_statistics: (),
+                        call_trace: None,
+                        time: result_with_info.time,
+                    }
+                }
                 Err(err) => bail!(err),
-            }
+            };
+            add_call_trace(
+                &mut summary,
+                &case.name,
+                &result_with_info.call_trace,
+                contracts_data,
+                trace_verbosity,
+            );
+            Ok(summary)
         }
         // `ForkStateReader.get_block_info`, `get_fork_state_reader, `calculate_used_gas` may return an error
         // `available_gas` may be specified with Scarb ~2.4
@@ -352,10 +591,40 @@ fn extract_test_case_summary(
             msg: Some(error.to_string()),
             arguments: args,
             test_statistics: (),
+            call_trace: None,
+            time: TestTime::default(),
         }),
     }
 }
 
+/// Renders the call trace of the test case if traces of tests with its result are printed
+fn add_call_trace(
+    summary: &mut TestCaseSummary<Single>,
+    test_name: &str,
+    call_trace: &Rc<RefCell<CallTrace>>,
+    contracts_data: &ContractsData,
+    trace_verbosity: Option<TraceVerbosity>,
+) {
+    let rendered = match (trace_verbosity, summary) {
+        (
+            Some(TraceVerbosity::All),
+            TestCaseSummary::Passed {
+                call_trace: rendered,
+                ..
+            },
+        )
+        | (
+            Some(_),
+            TestCaseSummary::Failed {
+                call_trace: rendered,
+                ..
+            },
+        ) => rendered,
+        _ => return,
+    };
+    *rendered = Some(render_call_trace(test_name, call_trace, contracts_data));
+}
+
 fn get_fork_state_reader(
     workspace_root: &Utf8Path,
     fork_config: &Option<ValidatedForkConfig>,
@@ -391,10 +660,11 @@ fn get_call_trace_ref(runtime: &mut ForgeRuntime) -> Rc<RefCell<CallTrace>> {
         .top()
 }
 
+/// Prepends the entry code to the program, returns its length
 fn add_header(
     entry_code: Vec<Instruction>,
     assembled_program: &mut AssembledCairoProgramWithSerde,
-) {
+) -> usize {
     let mut new_bytecode = vec![];
     let mut new_hints = vec![];
     for instruction in entry_code {
@@ -411,6 +681,8 @@ fn add_header(
 
     assembled_program.hints = [new_hints, assembled_program.hints.clone()].concat();
     assembled_program.bytecode = [new_bytecode, assembled_program.bytecode.clone()].concat();
+
+    new_bytecode_len
 }
 
 fn add_footer(footer: Vec<Instruction>, assembled_program: &mut AssembledCairoProgramWithSerde) {
@@ -446,3 +718,51 @@ fn create_hints_dict(
 
     (string_to_hint, hints_dict)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_run_exceeding_timeout() {
+        let run = tokio::task::spawn_blocking(|| {
+            std::thread::sleep(Duration::from_secs(1));
+            Ok("finished")
+        });
+
+        let cancellation = Cancellation::default();
+
+        let result = with_timeout(
+            run,
+            Some(Duration::from_millis(10)),
+            cancellation.clone(),
+            |_| "timed out",
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(result, "timed out");
+        assert!(cancellation.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn reports_result_of_run_within_timeout() {
+        let run = tokio::task::spawn_blocking(|| Ok("finished"));
+
+        let cancellation = Cancellation::default();
+
+        let result = with_timeout(
+            run,
+            Some(Duration::from_secs(60)),
+            cancellation.clone(),
+            |_| "timed out",
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(result, "finished");
+        assert!(!cancellation.is_cancelled());
+    }
+}
@@ -1,21 +1,25 @@
+use crate::backtrace::with_backtrace;
 use crate::build_trace_data::build_profiler_call_trace;
 use crate::compiled_runnable::TestCaseRunnable;
 use crate::contracts_data::ContractsData;
-use crate::expected_result::{ExpectedPanicValue, ExpectedTestResult};
+use crate::expected_result::{decode_panic_data, ExpectedPanicValue, ExpectedTestResult};
 use crate::gas::check_available_gas;
 use cairo_felt::Felt252;
-use cairo_lang_runner::short_string::as_cairo_short_string;
 use cairo_lang_runner::{RunResult, RunResultValue};
-use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::UsedResources;
+use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::{
+    CallFailure, UsedResources,
+};
 use cheatnet::state::CallTrace as InternalCallTrace;
 use num_traits::Pow;
+use serde::Serialize;
 use shared::utils::build_readable_text;
 use std::cell::RefCell;
 use std::option::Option;
 use std::rc::Rc;
+use std::time::Duration;
 use trace_data::CallTrace;
 
-#[derive(Debug, PartialEq, Clone, Default)]
+#[derive(Debug, PartialEq, Clone, Default, Serialize)]
 pub struct GasStatistics {
     pub min: u128,
     pub max: u128,
@@ -52,9 +56,59 @@ impl GasStatistics {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize)]
 pub struct FuzzingStatistics {
     pub runs: usize,
+    /// Simplest arguments still failing the test, found by shrinking the failing arguments
+    #[serde(skip)]
+    pub shrunk_arguments: Option<Vec<Felt252>>,
+    /// Shortest sequence of calls breaking the invariants, reported for invariant tests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub call_sequence: Option<Vec<String>>,
+    /// Time of all runs summed up, runs are executed in parallel so it can exceed the wall-clock time
+    #[serde(skip)]
+    pub cumulative_time: TestTime,
+}
+
+/// Wall-clock time spent running a test case
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct TestTime {
+    /// Time of the whole run, from the start of the first run to the end of the last one for fuzz tests
+    pub total: Duration,
+    /// Part of `total` spent waiting for RPC responses in forked tests
+    pub rpc: Duration,
+}
+
+impl TestTime {
+    /// Part of `total` spent executing the test, outside of RPC requests
+    #[must_use]
+    pub fn execution(&self) -> Duration {
+        self.total.saturating_sub(self.rpc)
+    }
+
+    /// Scales the time to the wall-clock `total`, keeping the share of RPC requests in it.
+    /// Used for fuzz tests, which run in parallel so their RPC time cannot be measured directly
+    #[must_use]
+    pub fn scaled_to(&self, total: Duration) -> Self {
+        let rpc = if self.total.is_zero() {
+            Duration::ZERO
+        } else {
+            total.mul_f64(self.rpc.as_secs_f64() / self.total.as_secs_f64())
+        };
+        Self {
+            total,
+            rpc: rpc.min(total),
+        }
+    }
+}
+
+impl std::iter::Sum for TestTime {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |sum, time| Self {
+            total: sum.total + time.total,
+            rpc: sum.rpc + time.rpc,
+        })
+    }
 }
 
 pub trait TestType {
@@ -98,6 +152,10 @@ pub enum TestCaseSummary<T: TestType> {
         test_statistics: <T as TestType>::TestStatistics,
         /// Test trace data
         trace_data: <T as TestType>::TraceData,
+        /// Call trace printed after the result, rendered only if requested with `-vvvv`
+        call_trace: Option<String>,
+        /// Time spent running the test case
+        time: TestTime,
     },
     /// Test case failed
     Failed {
@@ -109,6 +167,21 @@ pub enum TestCaseSummary<T: TestType> {
         arguments: Vec<Felt252>,
         /// Statistics of the test run
         test_statistics: <T as TestType>::TestStatistics,
+        /// Call trace printed after the result, rendered only if requested with `-vvv`
+        call_trace: Option<String>,
+        /// Time spent running the test case
+        time: TestTime,
+    },
+    /// Test case did not finish within its timeout, counted as failed
+    TimedOut {
+        /// Name of the test case
+        name: String,
+        /// Arguments used in the test case run
+        arguments: Vec<Felt252>,
+        /// Timeout exceeded by the test case run
+        timeout: Duration,
+        /// Statistics of the test run
+        test_statistics: <T as TestType>::TestStatistics,
     },
     /// Test case ignored due to `#[ignored]` attribute or `--ignored` flag
     Ignored {
@@ -132,6 +205,7 @@ impl<T: TestType> TestCaseSummary<T> {
         match self {
             TestCaseSummary::Failed { name, .. }
             | TestCaseSummary::Passed { name, .. }
+            | TestCaseSummary::TimedOut { name, .. }
             | TestCaseSummary::Ignored { name, .. } => Some(name),
             TestCaseSummary::Skipped { .. } => None,
         }
@@ -145,9 +219,37 @@ impl<T: TestType> TestCaseSummary<T> {
             _ => None,
         }
     }
+
+    /// Call trace rendered for the verbose output, `None` if it was not requested
+    #[must_use]
+    pub fn call_trace(&self) -> Option<&str> {
+        match self {
+            TestCaseSummary::Failed {
+                call_trace: Some(call_trace),
+                ..
+            }
+            | TestCaseSummary::Passed {
+                call_trace: Some(call_trace),
+                ..
+            } => Some(call_trace),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn time(&self) -> Option<TestTime> {
+        match self {
+            TestCaseSummary::Failed { time, .. } | TestCaseSummary::Passed { time, .. } => {
+                Some(*time)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl TestCaseSummary<Fuzzing> {
+    /// Combines the results of all runs, the time of the test is the cumulative time of the runs
+    /// until it is set to the wall-clock time with [`TestCaseSummary::with_wall_clock_time`]
     #[must_use]
     pub fn from(results: Vec<TestCaseSummary<Single>>) -> Self {
         let last: TestCaseSummary<Single> = results
@@ -155,6 +257,7 @@ impl TestCaseSummary<Fuzzing> {
             .last()
             .cloned()
             .expect("Fuzz test should always run at least once");
+        let time = results.iter().filter_map(TestCaseSummary::time).sum();
         // Only the last result matters as fuzzing is cancelled after first fail
         match last {
             TestCaseSummary::Passed {
@@ -165,6 +268,8 @@ impl TestCaseSummary<Fuzzing> {
                 used_resources: _,
                 test_statistics: (),
                 trace_data: _,
+                call_trace,
+                time: _,
             } => {
                 let runs = results.len();
                 let gas_usages: Vec<u128> = results
@@ -181,8 +286,15 @@ impl TestCaseSummary<Fuzzing> {
                     arguments,
                     gas_info: GasStatistics::new(&gas_usages),
                     used_resources: UsedResources::default(),
-                    test_statistics: FuzzingStatistics { runs },
+                    test_statistics: FuzzingStatistics {
+                        runs,
+                        shrunk_arguments: None,
+                        call_sequence: None,
+                        cumulative_time: time,
+                    },
                     trace_data: (),
+                    call_trace,
+                    time,
                 }
             }
             TestCaseSummary::Failed {
@@ -190,22 +302,65 @@ impl TestCaseSummary<Fuzzing> {
                 msg,
                 arguments,
                 test_statistics: (),
+                call_trace,
+                time: _,
             } => TestCaseSummary::Failed {
                 name,
                 msg,
                 arguments,
                 test_statistics: FuzzingStatistics {
                     runs: results.len(),
+                    shrunk_arguments: None,
+                    call_sequence: None,
+                    cumulative_time: time,
+                },
+                call_trace,
+                time,
+            },
+            TestCaseSummary::TimedOut {
+                name,
+                arguments,
+                timeout,
+                test_statistics: (),
+            } => TestCaseSummary::TimedOut {
+                name,
+                arguments,
+                timeout,
+                test_statistics: FuzzingStatistics {
+                    runs: results.len(),
+                    shrunk_arguments: None,
+                    call_sequence: None,
+                    cumulative_time: time,
                 },
             },
             TestCaseSummary::Ignored { name } => TestCaseSummary::Ignored { name: name.clone() },
             TestCaseSummary::Skipped {} => TestCaseSummary::Skipped {},
         }
     }
+
+    /// Sets the time of the test to the wall-clock time of the whole fuzzing campaign
+    #[must_use]
+    pub fn with_wall_clock_time(mut self, wall_clock_time: Duration) -> Self {
+        if let TestCaseSummary::Passed {
+            time,
+            test_statistics,
+            ..
+        }
+        | TestCaseSummary::Failed {
+            time,
+            test_statistics,
+            ..
+        } = &mut self
+        {
+            *time = test_statistics.cumulative_time.scaled_to(wall_clock_time);
+        }
+        self
+    }
 }
 
 impl TestCaseSummary<Single> {
     #[must_use]
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn from_run_result_and_info(
         run_result: RunResult,
         test_case: &TestCaseRunnable,
@@ -214,6 +369,7 @@ impl TestCaseSummary<Single> {
         used_resources: UsedResources,
         call_trace: &Rc<RefCell<InternalCallTrace>>,
         contracts_data: &ContractsData,
+        time: TestTime,
     ) -> Self {
         let name = test_case.name.clone();
         let msg = extract_result_data(&run_result, &test_case.expected_result);
@@ -228,6 +384,8 @@ impl TestCaseSummary<Single> {
                         gas_info: gas,
                         used_resources,
                         trace_data: build_profiler_call_trace(call_trace, contracts_data),
+                        call_trace: None,
+                        time,
                     };
                     check_available_gas(&test_case.available_gas, summary)
                 }
@@ -236,46 +394,58 @@ impl TestCaseSummary<Single> {
                     msg,
                     arguments,
                     test_statistics: (),
+                    call_trace: None,
+                    time,
                 },
             },
             RunResultValue::Panic(value) => match &test_case.expected_result {
                 ExpectedTestResult::Success => TestCaseSummary::Failed {
                     name,
-                    msg,
+                    msg: with_backtrace(
+                        msg,
+                        &CallFailure::Panic { panic_data: value },
+                        call_trace,
+                        contracts_data,
+                    ),
                     arguments,
                     test_statistics: (),
+                    call_trace: None,
+                    time,
                 },
-                ExpectedTestResult::Panics(panic_expectation) => match panic_expectation {
-                    ExpectedPanicValue::Exact(expected) if &value != expected => {
-                        TestCaseSummary::Failed {
+                ExpectedTestResult::Panics(panic_expectation) => {
+                    if panic_expectation.matches(&value) {
+                        TestCaseSummary::Passed {
                             name,
                             msg,
                             arguments,
                             test_statistics: (),
+                            gas_info: gas,
+                            used_resources,
+                            trace_data: build_profiler_call_trace(call_trace, contracts_data),
+                            call_trace: None,
+                            time,
+                        }
+                    } else {
+                        TestCaseSummary::Failed {
+                            name,
+                            msg: with_backtrace(
+                                msg,
+                                &CallFailure::Panic { panic_data: value },
+                                call_trace,
+                                contracts_data,
+                            ),
+                            arguments,
+                            test_statistics: (),
+                            call_trace: None,
+                            time,
                         }
                     }
-                    _ => TestCaseSummary::Passed {
-                        name,
-                        msg,
-                        arguments,
-                        test_statistics: (),
-                        gas_info: gas,
-                        used_resources,
-                        trace_data: build_profiler_call_trace(call_trace, contracts_data),
-                    },
-                },
+                }
             },
         }
     }
 }
 
-fn join_short_strings(data: &[Felt252]) -> String {
-    data.iter()
-        .map(|felt| as_cairo_short_string(felt).unwrap_or_default())
-        .collect::<Vec<String>>()
-        .join(", ")
-}
-
 /// Returns a string with the data that was produced by the test case.
 /// If the test was expected to fail with specific data e.g. `#[should_panic(expected: ('data',))]`
 /// and failed to do so, it returns a string comparing the panic data and the expected data.
@@ -283,42 +453,28 @@ fn join_short_strings(data: &[Felt252]) -> String {
 fn extract_result_data(run_result: &RunResult, expectation: &ExpectedTestResult) -> Option<String> {
     match &run_result.value {
         RunResultValue::Success(data) => match expectation {
-            ExpectedTestResult::Panics(panic_expectation) => match panic_expectation {
-                ExpectedPanicValue::Exact(panic_data) => {
-                    let panic_string = join_short_strings(panic_data);
-
-                    Some(format!(
-                        "\n    Expected to panic but didn't\n    Expected panic data:  {panic_data:?} ({panic_string})\n"
-                    ))
-                }
-                ExpectedPanicValue::Any => Some("\n    Expected to panic but didn't\n".into()),
-            },
+            ExpectedTestResult::Panics(ExpectedPanicValue::Any) => {
+                Some("\n    Expected to panic but didn't\n".into())
+            }
+            ExpectedTestResult::Panics(expected) => Some(format!(
+                "\n    Expected to panic but didn't\n    Expected panic data:  {expected}\n"
+            )),
             ExpectedTestResult::Success => build_readable_text(data),
         },
-        RunResultValue::Panic(panic_data) => {
-            let expected_data = match expectation {
-                ExpectedTestResult::Panics(panic_expectation) => match panic_expectation {
-                    ExpectedPanicValue::Exact(data) => Some(data),
-                    ExpectedPanicValue::Any => None,
-                },
-                ExpectedTestResult::Success => None,
-            };
-
-            match expected_data {
-                Some(expected) if expected == panic_data => None,
-                Some(expected) => {
-                    let panic_string = join_short_strings(panic_data);
-                    let expected_string = join_short_strings(expected);
-
-                    Some(format!(
-                        "\n    Incorrect panic data\n    {}\n    {}\n",
-                        format_args!("Actual:    {panic_data:?} ({panic_string})"),
-                        format_args!("Expected:  {expected:?} ({expected_string})")
-                    ))
-                }
-                None => build_readable_text(panic_data),
+        RunResultValue::Panic(panic_data) => match expectation {
+            ExpectedTestResult::Panics(ExpectedPanicValue::Any) | ExpectedTestResult::Success => {
+                build_readable_text(panic_data)
             }
-        }
+            ExpectedTestResult::Panics(expected) if expected.matches(panic_data) => None,
+            ExpectedTestResult::Panics(expected) => Some(format!(
+                "\n    Incorrect panic data\n    {}\n    {}\n",
+                format_args!(
+                    "Actual:    {panic_data:?} ({})",
+                    decode_panic_data(panic_data)
+                ),
+                format_args!("Expected:  {expected}")
+            )),
+        },
     }
 }
 
@@ -339,6 +495,22 @@ impl AnyTestCaseSummary {
         }
     }
 
+    #[must_use]
+    pub fn call_trace(&self) -> Option<&str> {
+        match self {
+            AnyTestCaseSummary::Fuzzing(case) => case.call_trace(),
+            AnyTestCaseSummary::Single(case) => case.call_trace(),
+        }
+    }
+
+    #[must_use]
+    pub fn time(&self) -> Option<TestTime> {
+        match self {
+            AnyTestCaseSummary::Fuzzing(case) => case.time(),
+            AnyTestCaseSummary::Single(case) => case.time(),
+        }
+    }
+
     #[must_use]
     pub fn is_passed(&self) -> bool {
         matches!(
@@ -348,12 +520,23 @@ impl AnyTestCaseSummary {
         )
     }
 
+    /// Timed out test cases are failed as well
     #[must_use]
     pub fn is_failed(&self) -> bool {
+        self.is_timed_out()
+            || matches!(
+                self,
+                AnyTestCaseSummary::Single(TestCaseSummary::Failed { .. })
+                    | AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed { .. })
+            )
+    }
+
+    #[must_use]
+    pub fn is_timed_out(&self) -> bool {
         matches!(
             self,
-            AnyTestCaseSummary::Single(TestCaseSummary::Failed { .. })
-                | AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed { .. })
+            AnyTestCaseSummary::Single(TestCaseSummary::TimedOut { .. })
+                | AnyTestCaseSummary::Fuzzing(TestCaseSummary::TimedOut { .. })
         )
     }
 
@@ -375,3 +558,53 @@ impl AnyTestCaseSummary {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed_run(total: Duration, rpc: Duration) -> TestCaseSummary<Single> {
+        TestCaseSummary::Failed {
+            name: "tests::fuzzed".to_string(),
+            msg: None,
+            arguments: vec![],
+            test_statistics: (),
+            call_trace: None,
+            time: TestTime { total, rpc },
+        }
+    }
+
+    #[test]
+    fn fuzzing_time_is_wall_clock_time_of_runs() {
+        let results = vec![
+            failed_run(Duration::from_secs(3), Duration::from_secs(1)),
+            failed_run(Duration::from_secs(5), Duration::from_secs(3)),
+        ];
+
+        let summary =
+            TestCaseSummary::<Fuzzing>::from(results).with_wall_clock_time(Duration::from_secs(2));
+
+        let TestCaseSummary::Failed {
+            time,
+            test_statistics,
+            ..
+        } = summary
+        else {
+            panic!("Fuzz test should fail")
+        };
+        assert_eq!(
+            test_statistics.cumulative_time,
+            TestTime {
+                total: Duration::from_secs(8),
+                rpc: Duration::from_secs(4),
+            }
+        );
+        assert_eq!(
+            time,
+            TestTime {
+                total: Duration::from_secs(2),
+                rpc: Duration::from_secs(1),
+            }
+        );
+    }
+}
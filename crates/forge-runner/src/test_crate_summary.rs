@@ -1,3 +1,4 @@
+use crate::compiled_runnable::CrateLocation;
 use crate::test_case_summary::AnyTestCaseSummary;
 use crate::RunnerStatus;
 
@@ -8,6 +9,8 @@ pub struct TestCrateSummary {
     pub test_case_summaries: Vec<AnyTestCaseSummary>,
     /// Status of the runner after executing tests in the file
     pub runner_exit_status: RunnerStatus,
+    /// Location of the test crate the summaries come from
+    pub tests_location: CrateLocation,
 }
 
 impl TestCrateSummary {
@@ -1,5 +1,5 @@
 use cairo_lang_sierra::program::Program;
-use forge_runner::compiled_runnable::{FuzzerConfig, TestDetails};
+use forge_runner::compiled_runnable::{CrateLocation, FuzzerConfig, TestDetails};
 use forge_runner::expected_result::ExpectedTestResult;
 use serde::Deserialize;
 
@@ -21,14 +21,6 @@ pub(crate) struct TestCaseRaw {
     pub test_details: TestDetails,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy, Deserialize)]
-pub(crate) enum CrateLocation {
-    /// Main crate in a package
-    Lib,
-    /// Crate in the `tests/` directory
-    Tests,
-}
-
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub(crate) enum RawForkConfig {
     Id(String),
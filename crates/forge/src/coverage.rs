@@ -0,0 +1,422 @@
+use anyhow::{Context, Result};
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_parser::utils::{get_syntax_root_and_diagnostics_from_file, SimpleParserDatabase};
+use cairo_lang_syntax::node::ast::{
+    FunctionWithBody, ImplItem, MaybeImplBody, MaybeModuleBody, ModuleItem, SyntaxFile,
+};
+use cairo_lang_syntax::node::TypedSyntaxNode;
+use camino::{Utf8Path, Utf8PathBuf};
+use forge_runner::coverage::{CoverageData, FunctionCoverage};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::fs;
+
+/// Path of the LCOV report, relative to the workspace root
+pub const COVERAGE_REPORT_PATH: &str = "coverage/lcov.info";
+
+/// LCOV report built from coverage of all tested packages.
+///
+/// Sierra programs compiled by Scarb do not carry source locations of statements,
+/// so functions are located by parsing the crate sources and each function is reported
+/// with a single line: the line of its declaration.
+#[derive(Debug, Default, PartialEq)]
+pub struct CoverageReport {
+    files: BTreeMap<Utf8PathBuf, SourceFileCoverage>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct SourceFileCoverage {
+    /// Name and hits of every function, keyed by its declaration line
+    functions: BTreeMap<usize, (String, usize)>,
+}
+
+impl SourceFileCoverage {
+    fn add_function(&mut self, name: &str, line: usize, hits: usize) {
+        // Monomorphized functions and functions used both by tests and contracts share the declaration
+        let (_, function_hits) = self
+            .functions
+            .entry(line)
+            .or_insert_with(|| (name.to_string(), 0));
+        *function_hits += hits;
+    }
+}
+
+impl CoverageReport {
+    /// Adds coverage of the package located in `package_root`
+    pub fn add_package(
+        &mut self,
+        package_root: &Utf8Path,
+        package_name: &str,
+        data: &CoverageData,
+    ) {
+        let locations = FunctionLocations::of_package(package_root, package_name);
+        let functions = data.functions.iter().chain(
+            data.contracts
+                .values()
+                .flat_map(|contract| contract.functions.iter()),
+        );
+
+        for (name, function) in functions {
+            let path = path_segments(name).join("::");
+            if let Some((file, line)) = locations.0.get(&path) {
+                self.files.entry(file.clone()).or_default().add_function(
+                    &path,
+                    *line,
+                    hits(function),
+                );
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn to_lcov(&self) -> String {
+        let mut lcov = String::new();
+
+        for (file, coverage) in &self.files {
+            writeln!(lcov, "TN:\nSF:{file}").unwrap();
+
+            for (line, (name, _)) in &coverage.functions {
+                writeln!(lcov, "FN:{line},{name}").unwrap();
+            }
+            for (name, hits) in coverage.functions.values() {
+                writeln!(lcov, "FNDA:{hits},{name}").unwrap();
+            }
+            let functions_hit = coverage
+                .functions
+                .values()
+                .filter(|(_, hits)| *hits > 0)
+                .count();
+            writeln!(
+                lcov,
+                "FNF:{}\nFNH:{functions_hit}",
+                coverage.functions.len()
+            )
+            .unwrap();
+
+            for (line, (_, hits)) in &coverage.functions {
+                writeln!(lcov, "DA:{line},{hits}").unwrap();
+            }
+            writeln!(lcov, "LF:{}\nLH:{functions_hit}", coverage.functions.len()).unwrap();
+
+            lcov.push_str("end_of_record\n");
+        }
+
+        lcov
+    }
+
+    pub fn save(&self, path: &Utf8Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_lcov())
+            .with_context(|| format!("Failed to write coverage report to {path}"))
+    }
+}
+
+fn hits(function: &FunctionCoverage) -> usize {
+    if function.is_executed() {
+        function.calls.max(1)
+    } else {
+        0
+    }
+}
+
+/// Splits a Sierra debug name into path segments, dropping generic arguments
+fn path_segments(name: &str) -> Vec<String> {
+    let mut without_generics = String::with_capacity(name.len());
+    let mut depth = 0_usize;
+    for char in name.chars() {
+        match char {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            char if depth == 0 => without_generics.push(char),
+            _ => {}
+        }
+    }
+
+    without_generics
+        .split("::")
+        .filter(|segment| !segment.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// File and 1-based declaration line of every function with a body, keyed by its full path
+#[derive(Debug, Default)]
+struct FunctionLocations(HashMap<String, (Utf8PathBuf, usize)>);
+
+impl FunctionLocations {
+    /// Locates functions of the package crate and of the `tests` crate
+    fn of_package(package_root: &Utf8Path, package_name: &str) -> Self {
+        let db = SimpleParserDatabase::default();
+        let mut locations = Self::default();
+
+        let src = package_root.join("src");
+        locations.add_file(&db, &src.join("lib.cairo"), &src, package_name);
+
+        let tests = package_root.join("tests");
+        if tests.join("lib.cairo").is_file() {
+            locations.add_file(&db, &tests.join("lib.cairo"), &tests, "tests");
+        } else if let Ok(entries) = tests.read_dir_utf8() {
+            // Without `lib.cairo` every file is a module of the `tests` crate
+            for entry in entries.filter_map(Result::ok) {
+                let file = entry.path();
+                if let Some(module) = file
+                    .extension()
+                    .filter(|ext| *ext == "cairo")
+                    .and(file.file_stem())
+                {
+                    locations.add_file(&db, file, &tests.join(module), &format!("tests::{module}"));
+                }
+            }
+        }
+
+        locations
+    }
+
+    /// Adds functions from a module file, `module_dir` is where files of its submodules are placed
+    fn add_file(
+        &mut self,
+        db: &SimpleParserDatabase,
+        file: &Utf8Path,
+        module_dir: &Utf8Path,
+        module_path: &str,
+    ) {
+        if !file.is_file() {
+            return;
+        }
+        // Files with syntax errors are not compiled, so their diagnostics can be ignored
+        let (root, _) =
+            get_syntax_root_and_diagnostics_from_file(db, file.as_std_path().to_path_buf());
+        let file_id = FileId::new(db, file.as_std_path().to_path_buf());
+        let items = SyntaxFile::from_syntax_node(db, root)
+            .items(db)
+            .elements(db);
+
+        self.add_items(db, file, file_id, items, module_dir, module_path);
+    }
+
+    fn add_items(
+        &mut self,
+        db: &SimpleParserDatabase,
+        file: &Utf8Path,
+        file_id: FileId,
+        items: Vec<ModuleItem>,
+        module_dir: &Utf8Path,
+        module_path: &str,
+    ) {
+        for item in items {
+            match item {
+                ModuleItem::FreeFunction(function) => {
+                    self.add_function(db, file, file_id, &function, module_path);
+                }
+                ModuleItem::Impl(item_impl) => {
+                    let MaybeImplBody::Some(body) = item_impl.body(db) else {
+                        continue;
+                    };
+                    let impl_path = format!("{module_path}::{}", item_impl.name(db).text(db));
+                    for impl_item in body.items(db).elements(db) {
+                        if let ImplItem::Function(function) = impl_item {
+                            self.add_function(db, file, file_id, &function, &impl_path);
+                        }
+                    }
+                }
+                ModuleItem::Module(module) => {
+                    let name = module.name(db).text(db);
+                    let submodule_path = format!("{module_path}::{name}");
+                    let submodule_dir = module_dir.join(name.as_str());
+                    match module.body(db) {
+                        MaybeModuleBody::Some(body) => self.add_items(
+                            db,
+                            file,
+                            file_id,
+                            body.items(db).elements(db),
+                            &submodule_dir,
+                            &submodule_path,
+                        ),
+                        MaybeModuleBody::None(_) => self.add_file(
+                            db,
+                            &module_dir.join(format!("{name}.cairo")),
+                            &submodule_dir,
+                            &submodule_path,
+                        ),
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_function(
+        &mut self,
+        db: &SimpleParserDatabase,
+        file: &Utf8Path,
+        file_id: FileId,
+        function: &FunctionWithBody,
+        scope_path: &str,
+    ) {
+        let declaration = function.declaration(db);
+        let Some(position) = declaration
+            .as_syntax_node()
+            .span_without_trivia(db)
+            .start
+            .position_in_file(db, file_id)
+        else {
+            return;
+        };
+        let name = declaration.name(db).text(db);
+
+        self.0.insert(
+            format!("{scope_path}::{name}"),
+            (file.to_path_buf(), position.line + 1),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::fixture::{FileWriteStr, PathChild};
+    use assert_fs::TempDir;
+    use forge_runner::coverage::ContractCoverage;
+    use indoc::indoc;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn strips_generic_arguments() {
+        assert_eq!(
+            path_segments("pkg::Impl::<core::felt252, core::integer::u8>::append"),
+            vec!["pkg", "Impl", "append"]
+        );
+    }
+
+    #[test]
+    fn locates_functions_in_modules_and_impls() {
+        let temp = TempDir::new().unwrap();
+        temp.child("src/lib.cairo")
+            .write_str(indoc! {r"
+                mod contract;
+
+                // fn fib in a comment
+                fn fib(a: felt252) -> felt252 {
+                    a
+                }
+
+                mod inner {
+                    fn helper() {}
+                }
+            "})
+            .unwrap();
+        temp.child("src/contract.cairo")
+            .write_str(indoc! {r"
+                #[starknet::contract]
+                mod HelloStarknet {
+                    #[storage]
+                    struct Storage {}
+
+                    #[abi(embed_v0)]
+                    impl HelloStarknetImpl of super::IHelloStarknet<ContractState> {
+                        fn get_balance(self: @ContractState) -> felt252 {
+                            0
+                        }
+                    }
+                }
+            "})
+            .unwrap();
+        temp.child("tests/test_fib.cairo")
+            .write_str("fn test_fib() {}\n")
+            .unwrap();
+        let root = Utf8PathBuf::from_path_buf(temp.to_path_buf()).unwrap();
+
+        let locations = FunctionLocations::of_package(&root, "pkg").0;
+
+        assert_eq!(
+            locations.get("pkg::fib"),
+            Some(&(root.join("src/lib.cairo"), 4))
+        );
+        assert_eq!(
+            locations.get("pkg::inner::helper"),
+            Some(&(root.join("src/lib.cairo"), 9))
+        );
+        assert_eq!(
+            locations.get("pkg::contract::HelloStarknet::HelloStarknetImpl::get_balance"),
+            Some(&(root.join("src/contract.cairo"), 8))
+        );
+        assert_eq!(
+            locations.get("tests::test_fib::test_fib"),
+            Some(&(root.join("tests/test_fib.cairo"), 1))
+        );
+        assert_eq!(locations.len(), 4);
+    }
+
+    #[test]
+    fn writes_lcov() {
+        let temp = TempDir::new().unwrap();
+        temp.child("src/lib.cairo")
+            .write_str(indoc! {r"
+                fn called() {}
+
+                fn not_called() {}
+
+                #[starknet::contract]
+                mod Contract {
+                    #[abi(embed_v0)]
+                    impl ContractImpl of super::IContract<ContractState> {
+                        fn get(self: @ContractState) {}
+                    }
+                }
+            "})
+            .unwrap();
+        let root = Utf8PathBuf::from_path_buf(temp.to_path_buf()).unwrap();
+        let executed = |calls| FunctionCoverage {
+            statements: 3,
+            executed_statements: BTreeSet::from([0, 1]),
+            calls,
+        };
+        let data = CoverageData {
+            functions: BTreeMap::from([
+                ("pkg::called".to_string(), executed(2)),
+                ("pkg::not_called".to_string(), FunctionCoverage::default()),
+                ("pkg::generated".to_string(), executed(1)),
+            ]),
+            contracts: BTreeMap::from([(
+                "Contract".to_string(),
+                ContractCoverage {
+                    entry_points: BTreeMap::from([("get".to_string(), 3)]),
+                    functions: BTreeMap::from([(
+                        "pkg::Contract::ContractImpl::get".to_string(),
+                        executed(3),
+                    )]),
+                },
+            )]),
+        };
+
+        let mut report = CoverageReport::default();
+        report.add_package(&root, "pkg", &data);
+
+        assert_eq!(
+            report.to_lcov(),
+            format!(
+                indoc! {r"
+                    TN:
+                    SF:{}
+                    FN:1,pkg::called
+                    FN:3,pkg::not_called
+                    FN:9,pkg::Contract::ContractImpl::get
+                    FNDA:2,pkg::called
+                    FNDA:0,pkg::not_called
+                    FNDA:3,pkg::Contract::ContractImpl::get
+                    FNF:3
+                    FNH:2
+                    DA:1,2
+                    DA:3,0
+                    DA:9,3
+                    LF:3
+                    LH:2
+                    end_of_record
+                "},
+                root.join("src/lib.cairo")
+            )
+        );
+    }
+}
@@ -0,0 +1,427 @@
+use anyhow::{anyhow, bail, Context, Result};
+use camino::Utf8Path;
+use forge_runner::test_case_summary::{AnyTestCaseSummary, TestCaseSummary};
+use forge_runner::test_crate_summary::TestCrateSummary;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::fs;
+
+/// Name of the gas snapshot file, placed in the workspace root
+pub const GAS_SNAPSHOT_FILE: &str = ".gas-snapshot";
+
+/// Gas used by passed tests of all tested packages.
+/// Entries are keyed by the package name and the test name.
+#[derive(Debug, Default, PartialEq)]
+pub struct GasSnapshot {
+    entries: BTreeMap<(String, String), SnapshotGas>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotGas {
+    Single(u128),
+    Fuzzing {
+        runs: usize,
+        mean: f64,
+        min: u128,
+        max: u128,
+    },
+}
+
+impl SnapshotGas {
+    /// Value compared between snapshots, mean is used for fuzz tests
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn value(&self) -> f64 {
+        match self {
+            SnapshotGas::Single(gas) => *gas as f64,
+            SnapshotGas::Fuzzing { mean, .. } => *mean,
+        }
+    }
+}
+
+impl Display for SnapshotGas {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotGas::Single(gas) => write!(f, "gas: {gas}"),
+            SnapshotGas::Fuzzing {
+                runs,
+                mean,
+                min,
+                max,
+            } => write!(f, "runs: {runs}, mean: {mean}, min: {min}, max: {max}"),
+        }
+    }
+}
+
+/// Difference between the gas of a test in the snapshot file and in the current run
+#[derive(Debug, PartialEq)]
+pub struct GasDiff {
+    pub package: String,
+    pub test: String,
+    pub previous: f64,
+    pub current: f64,
+}
+
+impl GasDiff {
+    #[must_use]
+    pub fn delta(&self) -> f64 {
+        self.current - self.previous
+    }
+
+    /// Change relative to the snapshot, `None` if the snapshot value is zero
+    #[must_use]
+    pub fn percentage(&self) -> Option<f64> {
+        (self.previous != 0.0).then(|| self.delta() / self.previous * 100.0)
+    }
+
+    /// Whether the change is bigger than `tolerance` percent
+    #[must_use]
+    pub fn exceeds(&self, tolerance: f64) -> bool {
+        match self.percentage() {
+            Some(percentage) => percentage.abs() > tolerance,
+            None => self.delta() != 0.0,
+        }
+    }
+}
+
+/// Result of comparing the current run against the snapshot file
+#[derive(Debug, Default, PartialEq)]
+pub struct SnapshotCheck {
+    /// Tests with changed gas usage
+    pub diffs: Vec<GasDiff>,
+    /// Tests with changed gas usage exceeding the tolerance
+    pub exceeded: usize,
+    /// Tests missing from the snapshot file, as (package, test) pairs
+    pub missing: Vec<(String, String)>,
+    /// Entries of the snapshot file whose tests no longer exist, as (package, test) pairs
+    pub stale: Vec<(String, String)>,
+}
+
+impl SnapshotCheck {
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.exceeded == 0
+    }
+}
+
+/// Validates the allowed change of gas usage (in percents) when checking the gas snapshot
+pub fn validate_tolerance(tolerance: f64) -> Result<f64> {
+    if !tolerance.is_finite() || tolerance < 0.0 {
+        bail!("Gas snapshot tolerance must be a non-negative number, got {tolerance}");
+    }
+    Ok(tolerance)
+}
+
+impl GasSnapshot {
+    /// Moves all entries of `other` into the snapshot, replacing the ones of the same tests
+    pub fn extend(&mut self, other: GasSnapshot) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Tests of the package which have entries in the snapshot, but are not among `collected_tests`
+    #[must_use]
+    pub fn stale_tests(
+        &self,
+        package_name: &str,
+        collected_tests: &HashSet<String>,
+    ) -> Vec<(String, String)> {
+        self.entries
+            .keys()
+            .filter(|(package, test)| package == package_name && !collected_tests.contains(test))
+            .cloned()
+            .collect()
+    }
+
+    /// Removes entries of the package's tests which are not among `collected_tests`
+    pub fn remove_stale_tests(&mut self, package_name: &str, collected_tests: &HashSet<String>) {
+        self.entries
+            .retain(|(package, test), _| package != package_name || collected_tests.contains(test));
+    }
+
+    /// Adds gas of all passed tests of the package
+    pub fn add_package(&mut self, package_name: &str, summaries: &[TestCrateSummary]) {
+        let test_cases = summaries
+            .iter()
+            .flat_map(|summary| &summary.test_case_summaries);
+
+        for test_case in test_cases {
+            let (name, gas) = match test_case {
+                AnyTestCaseSummary::Single(TestCaseSummary::Passed { name, gas_info, .. }) => {
+                    (name, SnapshotGas::Single(*gas_info))
+                }
+                AnyTestCaseSummary::Fuzzing(TestCaseSummary::Passed {
+                    name,
+                    gas_info,
+                    test_statistics,
+                    ..
+                }) => (
+                    name,
+                    SnapshotGas::Fuzzing {
+                        runs: test_statistics.runs,
+                        mean: gas_info.mean,
+                        min: gas_info.min,
+                        max: gas_info.max,
+                    },
+                ),
+                _ => continue,
+            };
+            self.entries
+                .insert((package_name.to_string(), name.clone()), gas);
+        }
+    }
+
+    pub fn load(path: &Utf8Path) -> Result<Self> {
+        let content = fs::read_to_string(path).with_context(|| {
+            format!("Failed to read gas snapshot from {path}, run `snforge test --snapshot` to create it")
+        })?;
+        content.parse()
+    }
+
+    /// Loads the snapshot file, an empty snapshot is returned if it does not exist yet
+    pub fn load_or_default(path: &Utf8Path) -> Result<Self> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self, path: &Utf8Path) -> Result<()> {
+        fs::write(path, self.to_string())
+            .with_context(|| format!("Failed to write gas snapshot to {path}"))
+    }
+
+    /// Compares gas of the current run (`self`) of the package against the `previous` snapshot.
+    /// `collected_tests` are all tests of the package, regardless of the filters used.
+    /// `tolerance` is the allowed change of gas usage in percents.
+    #[must_use]
+    pub fn check(
+        &self,
+        previous: &GasSnapshot,
+        package_name: &str,
+        collected_tests: &HashSet<String>,
+        tolerance: f64,
+    ) -> SnapshotCheck {
+        let mut result = SnapshotCheck {
+            stale: previous.stale_tests(package_name, collected_tests),
+            ..Default::default()
+        };
+
+        for ((package, test), gas) in &self.entries {
+            let Some(previous_gas) = previous.entries.get(&(package.clone(), test.clone())) else {
+                result.missing.push((package.clone(), test.clone()));
+                continue;
+            };
+
+            let diff = GasDiff {
+                package: package.clone(),
+                test: test.clone(),
+                previous: previous_gas.value(),
+                current: gas.value(),
+            };
+            if diff.delta() == 0.0 {
+                continue;
+            }
+            if diff.exceeds(tolerance) {
+                result.exceeded += 1;
+            }
+            result.diffs.push(diff);
+        }
+
+        result
+    }
+}
+
+impl Display for GasSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for ((package, test), gas) in &self.entries {
+            writeln!(f, "{package} {test} ({gas})")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for GasSnapshot {
+    type Err = anyhow::Error;
+
+    fn from_str(content: &str) -> Result<Self> {
+        let mut entries = BTreeMap::new();
+
+        for (idx, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (key, gas) = parse_line(line)
+                .ok_or_else(|| anyhow!("Invalid gas snapshot entry in line {}: {line}", idx + 1))?;
+            entries.insert(key, gas);
+        }
+
+        Ok(Self { entries })
+    }
+}
+
+fn parse_line(line: &str) -> Option<((String, String), SnapshotGas)> {
+    let (package, rest) = line.split_once(' ')?;
+    let (test, details) = rest.rsplit_once(" (")?;
+    let details: BTreeMap<&str, &str> = details
+        .strip_suffix(')')?
+        .split(", ")
+        .map(|detail| detail.split_once(": "))
+        .collect::<Option<_>>()?;
+
+    let gas = if let Some(gas) = details.get("gas") {
+        SnapshotGas::Single(gas.parse().ok()?)
+    } else {
+        SnapshotGas::Fuzzing {
+            runs: details.get("runs")?.parse().ok()?,
+            mean: details.get("mean")?.parse().ok()?,
+            min: details.get("min")?.parse().ok()?,
+            max: details.get("max")?.parse().ok()?,
+        }
+    };
+
+    Some(((package.to_string(), test.to_string()), gas))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn snapshot(entries: &[(&str, SnapshotGas)]) -> GasSnapshot {
+        GasSnapshot {
+            entries: entries
+                .iter()
+                .map(|(test, gas)| (("pkg".to_string(), (*test).to_string()), gas.clone()))
+                .collect(),
+        }
+    }
+
+    fn collected(tests: &[&str]) -> HashSet<String> {
+        tests.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn serializes_and_parses() {
+        let snapshot = snapshot(&[
+            ("tests::single", SnapshotGas::Single(12)),
+            (
+                "tests::fuzzed",
+                SnapshotGas::Fuzzing {
+                    runs: 256,
+                    mean: 2.5,
+                    min: 1,
+                    max: 4,
+                },
+            ),
+        ]);
+
+        let content = snapshot.to_string();
+
+        assert_eq!(
+            content,
+            indoc! {"
+                pkg tests::fuzzed (runs: 256, mean: 2.5, min: 1, max: 4)
+                pkg tests::single (gas: 12)
+            "}
+        );
+        assert_eq!(content.parse::<GasSnapshot>().unwrap(), snapshot);
+    }
+
+    #[test]
+    fn invalid_line() {
+        let result = "pkg tests::single gas: 12\n".parse::<GasSnapshot>();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn checks_against_tolerance() {
+        let previous = snapshot(&[
+            ("tests::same", SnapshotGas::Single(10)),
+            ("tests::cheaper", SnapshotGas::Single(100)),
+            ("tests::pricier", SnapshotGas::Single(100)),
+        ]);
+        let current = snapshot(&[
+            ("tests::same", SnapshotGas::Single(10)),
+            ("tests::cheaper", SnapshotGas::Single(95)),
+            ("tests::pricier", SnapshotGas::Single(120)),
+            ("tests::new", SnapshotGas::Single(1)),
+        ]);
+
+        let collected = collected(&[
+            "tests::same",
+            "tests::cheaper",
+            "tests::pricier",
+            "tests::new",
+        ]);
+
+        let check = current.check(&previous, "pkg", &collected, 5.0);
+
+        assert_eq!(check.diffs.len(), 2);
+        assert_eq!(check.exceeded, 1);
+        assert_eq!(
+            check.missing,
+            vec![("pkg".to_string(), "tests::new".to_string())]
+        );
+        assert!(check.stale.is_empty());
+        assert!(!check.is_ok());
+        assert!(current.check(&previous, "pkg", &collected, 20.0).is_ok());
+    }
+
+    #[test]
+    fn reports_and_removes_stale_tests() {
+        let mut previous = snapshot(&[
+            ("tests::kept", SnapshotGas::Single(10)),
+            ("tests::removed", SnapshotGas::Single(10)),
+        ]);
+        previous.entries.insert(
+            ("other_pkg".to_string(), "tests::other".to_string()),
+            SnapshotGas::Single(1),
+        );
+        let current = snapshot(&[("tests::kept", SnapshotGas::Single(10))]);
+        let collected = collected(&["tests::kept"]);
+
+        let check = current.check(&previous, "pkg", &collected, 0.0);
+
+        assert_eq!(
+            check.stale,
+            vec![("pkg".to_string(), "tests::removed".to_string())]
+        );
+        assert!(check.is_ok());
+
+        previous.remove_stale_tests("pkg", &collected);
+        assert_eq!(
+            previous.to_string(),
+            indoc! {"
+                other_pkg tests::other (gas: 1)
+                pkg tests::kept (gas: 10)
+            "}
+        );
+    }
+
+    #[test]
+    fn merges_entries() {
+        let mut merged = snapshot(&[
+            ("tests::not_run", SnapshotGas::Single(10)),
+            ("tests::changed", SnapshotGas::Single(10)),
+        ]);
+
+        merged.extend(snapshot(&[("tests::changed", SnapshotGas::Single(20))]));
+
+        assert_eq!(
+            merged.to_string(),
+            indoc! {"
+                pkg tests::changed (gas: 20)
+                pkg tests::not_run (gas: 10)
+            "}
+        );
+    }
+
+    #[test]
+    fn rejects_negative_tolerance() {
+        assert!(validate_tolerance(-1.0).is_err());
+        assert!(validate_tolerance(f64::NAN).is_err());
+        assert_eq!(validate_tolerance(2.5).unwrap(), 2.5);
+    }
+}
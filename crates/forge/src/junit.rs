@@ -0,0 +1,377 @@
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use forge_runner::compiled_runnable::CrateLocation;
+use forge_runner::test_case_summary::{AnyTestCaseSummary, FuzzingStatistics, TestCaseSummary};
+use forge_runner::test_crate_summary::TestCrateSummary;
+use std::fmt::Write;
+use std::fs;
+
+/// JUnit XML report built from the summaries of all test crates
+#[derive(Debug, Default, PartialEq)]
+pub struct JUnitReport {
+    test_suites: Vec<TestSuite>,
+}
+
+#[derive(Debug, PartialEq)]
+struct TestSuite {
+    name: String,
+    test_cases: Vec<TestCase>,
+}
+
+#[derive(Debug, PartialEq)]
+struct TestCase {
+    name: String,
+    class_name: String,
+    result: TestCaseResult,
+    /// Wall-clock time of the test in seconds, not known for ignored and timed out tests
+    time: Option<f64>,
+    properties: Vec<(String, String)>,
+}
+
+#[derive(Debug, PartialEq)]
+enum TestCaseResult {
+    Passed,
+    Failed { message: String },
+    Ignored,
+}
+
+impl JUnitReport {
+    /// Adds one test suite for every test crate of the package
+    pub fn add_package(&mut self, package_name: &str, summaries: &[TestCrateSummary]) {
+        for summary in summaries {
+            let location = match summary.tests_location {
+                CrateLocation::Lib => "src",
+                CrateLocation::Tests => "tests",
+            };
+
+            self.test_suites.push(TestSuite {
+                name: format!("{package_name}::{location}"),
+                test_cases: summary
+                    .test_case_summaries
+                    .iter()
+                    .filter_map(test_case_from_summary)
+                    .collect(),
+            });
+        }
+    }
+
+    #[must_use]
+    pub fn to_xml(&self) -> String {
+        let tests: usize = self.test_suites.iter().map(TestSuite::tests).sum();
+        let failures: usize = self.test_suites.iter().map(TestSuite::failures).sum();
+        let skipped: usize = self.test_suites.iter().map(TestSuite::skipped).sum();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        writeln!(
+            xml,
+            r#"<testsuites name="snforge" tests="{tests}" failures="{failures}" skipped="{skipped}">"#
+        )
+        .unwrap();
+        for test_suite in &self.test_suites {
+            test_suite.write_xml(&mut xml);
+        }
+        xml.push_str("</testsuites>\n");
+
+        xml
+    }
+
+    pub fn save(&self, path: &Utf8Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        fs::write(path, self.to_xml())
+            .with_context(|| format!("Failed to write JUnit report to {path}"))
+    }
+}
+
+impl TestSuite {
+    fn tests(&self) -> usize {
+        self.test_cases.len()
+    }
+
+    fn failures(&self) -> usize {
+        self.test_cases
+            .iter()
+            .filter(|case| matches!(case.result, TestCaseResult::Failed { .. }))
+            .count()
+    }
+
+    fn skipped(&self) -> usize {
+        self.test_cases
+            .iter()
+            .filter(|case| case.result == TestCaseResult::Ignored)
+            .count()
+    }
+
+    fn write_xml(&self, xml: &mut String) {
+        writeln!(
+            xml,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" errors="0" skipped="{}">"#,
+            escape(&self.name),
+            self.tests(),
+            self.failures(),
+            self.skipped()
+        )
+        .unwrap();
+
+        for test_case in &self.test_cases {
+            write!(
+                xml,
+                r#"    <testcase name="{}" classname="{}""#,
+                escape(&test_case.name),
+                escape(&test_case.class_name)
+            )
+            .unwrap();
+            if let Some(time) = test_case.time {
+                write!(xml, r#" time="{time:.3}""#).unwrap();
+            }
+            xml.push_str(">\n");
+
+            if !test_case.properties.is_empty() {
+                xml.push_str("      <properties>\n");
+                for (name, value) in &test_case.properties {
+                    writeln!(
+                        xml,
+                        r#"        <property name="{}" value="{}"/>"#,
+                        escape(name),
+                        escape(value)
+                    )
+                    .unwrap();
+                }
+                xml.push_str("      </properties>\n");
+            }
+
+            match &test_case.result {
+                TestCaseResult::Passed => {}
+                TestCaseResult::Failed { message } => {
+                    writeln!(
+                        xml,
+                        r#"      <failure message="{}">{}</failure>"#,
+                        escape(first_line(message)),
+                        escape(message)
+                    )
+                    .unwrap();
+                }
+                TestCaseResult::Ignored => xml.push_str("      <skipped/>\n"),
+            }
+
+            xml.push_str("    </testcase>\n");
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+}
+
+fn test_case_from_summary(summary: &AnyTestCaseSummary) -> Option<TestCase> {
+    let name = summary.name()?.to_string();
+    let class_name = name
+        .rsplit_once("::")
+        .map_or(name.as_str(), |(module, _)| module)
+        .to_string();
+
+    let result = if summary.is_passed() {
+        TestCaseResult::Passed
+    } else if let AnyTestCaseSummary::Single(TestCaseSummary::TimedOut { timeout, .. })
+    | AnyTestCaseSummary::Fuzzing(TestCaseSummary::TimedOut { timeout, .. }) = summary
+    {
+        TestCaseResult::Failed {
+            message: format!("Test did not finish within the timeout of {timeout:?}"),
+        }
+    } else if summary.is_failed() {
+        TestCaseResult::Failed {
+            message: summary.msg().unwrap_or("Test failed").trim().to_string(),
+        }
+    } else {
+        TestCaseResult::Ignored
+    };
+
+    Some(TestCase {
+        name,
+        class_name,
+        result,
+        time: summary.time().map(|time| time.total.as_secs_f64()),
+        properties: properties_from_summary(summary),
+    })
+}
+
+fn properties_from_summary(summary: &AnyTestCaseSummary) -> Vec<(String, String)> {
+    match summary {
+        AnyTestCaseSummary::Single(TestCaseSummary::Passed { gas_info, .. }) => {
+            vec![("gas".to_string(), gas_info.to_string())]
+        }
+        AnyTestCaseSummary::Fuzzing(TestCaseSummary::Passed {
+            gas_info,
+            test_statistics: FuzzingStatistics { runs, .. },
+            ..
+        }) => vec![
+            ("fuzzer_runs".to_string(), runs.to_string()),
+            ("gas_min".to_string(), gas_info.min.to_string()),
+            ("gas_max".to_string(), gas_info.max.to_string()),
+            ("gas_mean".to_string(), format!("{:.2}", gas_info.mean)),
+            (
+                "gas_std_deviation".to_string(),
+                format!("{:.2}", gas_info.std_deviation),
+            ),
+        ],
+        AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed {
+            arguments,
+            test_statistics:
+                FuzzingStatistics {
+                    runs,
+                    shrunk_arguments,
+                    call_sequence,
+                },
+            ..
+        }) => {
+            let mut properties = vec![
+                ("fuzzer_runs".to_string(), runs.to_string()),
+                ("arguments".to_string(), format!("{arguments:?}")),
+            ];
+            if let Some(shrunk_arguments) = shrunk_arguments {
+                properties.push((
+                    "shrunk_arguments".to_string(),
+                    format!("{shrunk_arguments:?}"),
+                ));
+            }
+            if let Some(call_sequence) = call_sequence {
+                properties.push(("call_sequence".to_string(), call_sequence.join("; ")));
+            }
+            properties
+        }
+        _ => vec![],
+    }
+}
+
+fn first_line(message: &str) -> &str {
+    message.lines().next().unwrap_or_default().trim()
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for char in value.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            char if char.is_control() && char != '\n' && char != '\t' => {}
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::UsedResources;
+    use forge_runner::test_case_summary::{GasStatistics, TestTime};
+    use forge_runner::RunnerStatus;
+    use std::time::Duration;
+
+    #[test]
+    fn escapes_special_characters() {
+        assert_eq!(
+            escape(r#"a < b && "c" > 'd'"#),
+            "a &lt; b &amp;&amp; &quot;c&quot; &gt; &apos;d&apos;"
+        );
+    }
+
+    #[test]
+    fn builds_suite_per_test_crate() {
+        let mut report = JUnitReport::default();
+        report.add_package(
+            "my_package",
+            &[
+                TestCrateSummary {
+                    test_case_summaries: vec![
+                        AnyTestCaseSummary::Single(TestCaseSummary::Ignored {
+                            name: "my_package::tests::ignored".to_string(),
+                        }),
+                        AnyTestCaseSummary::Single(TestCaseSummary::Failed {
+                            name: "my_package::tests::failing".to_string(),
+                            msg: Some("\n    original value: [8111420071579136082810415440747], converted to a string: [failing check]\n".to_string()),
+                            arguments: vec![],
+                            test_statistics: (),
+                            call_trace: None,
+                            time: TestTime {
+                                total: Duration::from_millis(1500),
+                                rpc: Duration::ZERO,
+                            },
+                        }),
+                        AnyTestCaseSummary::Single(TestCaseSummary::Skipped {}),
+                    ],
+                    runner_exit_status: RunnerStatus::Default,
+                    tests_location: CrateLocation::Lib,
+                },
+                TestCrateSummary {
+                    test_case_summaries: vec![
+                        AnyTestCaseSummary::Fuzzing(TestCaseSummary::Passed {
+                            name: "tests::fuzzing::fuzzed".to_string(),
+                            msg: None,
+                            arguments: vec![],
+                            gas_info: GasStatistics {
+                                min: 1,
+                                max: 3,
+                                mean: 2.0,
+                                std_deviation: 1.0,
+                            },
+                            used_resources: UsedResources::default(),
+                            test_statistics: FuzzingStatistics {
+                                runs: 256,
+                                shrunk_arguments: None,
+                                call_sequence: None,
+                                cumulative_time: TestTime::default(),
+                            },
+                            trace_data: (),
+                            call_trace: None,
+                            time: TestTime {
+                                total: Duration::from_millis(12),
+                                rpc: Duration::ZERO,
+                            },
+                        }),
+                        AnyTestCaseSummary::Single(TestCaseSummary::Ignored {
+                            name: "tests::fuzzing::ignored".to_string(),
+                        }),
+                    ],
+                    runner_exit_status: RunnerStatus::Default,
+                    tests_location: CrateLocation::Tests,
+                },
+            ],
+        );
+
+        assert_eq!(
+            report.to_xml(),
+            indoc::indoc! {r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <testsuites name="snforge" tests="4" failures="1" skipped="2">
+                  <testsuite name="my_package::src" tests="2" failures="1" errors="0" skipped="1">
+                    <testcase name="my_package::tests::ignored" classname="my_package::tests">
+                      <skipped/>
+                    </testcase>
+                    <testcase name="my_package::tests::failing" classname="my_package::tests" time="1.500">
+                      <failure message="original value: [8111420071579136082810415440747], converted to a string: [failing check]">original value: [8111420071579136082810415440747], converted to a string: [failing check]</failure>
+                    </testcase>
+                  </testsuite>
+                  <testsuite name="my_package::tests" tests="2" failures="0" errors="0" skipped="1">
+                    <testcase name="tests::fuzzing::fuzzed" classname="tests::fuzzing" time="0.012">
+                      <properties>
+                        <property name="fuzzer_runs" value="256"/>
+                        <property name="gas_min" value="1"/>
+                        <property name="gas_max" value="3"/>
+                        <property name="gas_mean" value="2.00"/>
+                        <property name="gas_std_deviation" value="1.00"/>
+                      </properties>
+                    </testcase>
+                    <testcase name="tests::fuzzing::ignored" classname="tests::fuzzing">
+                      <skipped/>
+                    </testcase>
+                  </testsuite>
+                </testsuites>
+            "#}
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use camino::Utf8Path;
 use warn::{
     warn_if_available_gas_used_with_incompatible_scarb_version, warn_if_incompatible_rpc_version,
@@ -13,19 +13,27 @@ use forge_runner::test_crate_summary::TestCrateSummary;
 use forge_runner::{RunnerConfig, RunnerParams, TestCrateRunResult};
 
 use crate::block_number_map::BlockNumberMap;
-use forge_runner::compiled_runnable::{CompiledTestCrateRunnable, TestCaseRunnable};
+use forge_runner::compiled_runnable::{
+    CompiledTestCrateRunnable, FixedArguments, TestCaseRunnable,
+};
+use forge_runner::expected_result::ExpectedTestResult;
+use forge_runner::fixtures::setup_fixture_of;
 
-use crate::scarb::config::ForkTarget;
+use crate::scarb::config::{ExpectedPanic, ForkTarget, ParametrizedCase, TestTag, TestTimeout};
 use crate::test_filter::TestsFilter;
 
 pub mod block_number_map;
 pub mod compiled_raw;
-
+pub mod coverage;
+pub mod gas_snapshot;
+pub mod junit;
+pub mod list;
 pub mod pretty_printing;
 pub mod scarb;
 pub mod shared_cache;
 pub mod test_filter;
 mod warn;
+pub mod watch;
 
 pub const CAIRO_EDITION: &str = "2023_11";
 
@@ -48,9 +56,92 @@ pub(crate) fn replace_id_with_params<'a>(
     }
 }
 
+/// Expected result of the test, `[[tool.snforge.expected_panic]]` overrides the `#[should_panic]` attribute
+fn expected_result_of(
+    test_name: &str,
+    expected_result: ExpectedTestResult,
+    expected_panics: &[ExpectedPanic],
+) -> ExpectedTestResult {
+    match expected_panics
+        .iter()
+        .find(|expected_panic| expected_panic.matches(test_name))
+    {
+        Some(expected_panic) => ExpectedTestResult::Panics(expected_panic.value().clone()),
+        None => expected_result,
+    }
+}
+
+/// Names of the test cases run for the test, with their parametrized cases.
+/// Cases of parametrized tests are named `{test name}[{case name}]`.
+fn cases_of<'a>(
+    test_name: &str,
+    fuzzed: bool,
+    parametrized_cases: &'a [ParametrizedCase],
+) -> Vec<(String, Option<&'a ParametrizedCase>)> {
+    let mut cases: Vec<_> = parametrized_cases
+        .iter()
+        .filter(|parametrized| parametrized.matches(test_name))
+        .map(|parametrized| {
+            (
+                format!("{test_name}[{}]", parametrized.name()),
+                Some(parametrized),
+            )
+        })
+        .collect();
+
+    // Parametrized tests are fuzzed as well only if they have the `#[fuzzer]` attribute
+    if cases.is_empty() || fuzzed {
+        cases.push((test_name.to_string(), None));
+    }
+    cases
+}
+
+/// Tests referred to by their full names in Scarb.toml have to exist in the package, even if filtered out
+fn ensure_tests_exist<'a>(
+    test_names: impl IntoIterator<Item = &'a str>,
+    option: &str,
+    test_crates: &[CompiledTestCrateRaw],
+    package_name: &str,
+) -> Result<()> {
+    for test_name in test_names {
+        let exists = test_crates
+            .iter()
+            .flat_map(|test_crate| &test_crate.test_cases)
+            .any(|case| case.name == test_name);
+        if !exists {
+            bail!("Test = {test_name} from `{option}` in Scarb.toml not found in package {package_name}, tests have to be referred to by their full names");
+        }
+    }
+    Ok(())
+}
+
+/// Tags in Scarb.toml refer to tests or modules by their full names, which have to exist in the package
+fn ensure_tagged_tests_exist(
+    test_tags: &[TestTag],
+    test_crates: &[CompiledTestCrateRaw],
+    package_name: &str,
+) -> Result<()> {
+    for test_tag in test_tags {
+        let exists = test_crates
+            .iter()
+            .flat_map(|test_crate| &test_crate.test_cases)
+            .any(|case| test_tag.matches(&case.name));
+        if !exists {
+            bail!("Test or module = {} from `test_tag` in Scarb.toml not found in package {package_name}, tests and modules have to be referred to by their full names", test_tag.test());
+        }
+    }
+    Ok(())
+}
+
 async fn to_runnable(
     compiled_test_crate: CompiledTestCrateRaw,
+    package_name: &str,
     fork_targets: &[ForkTarget],
+    parametrized_cases: &[ParametrizedCase],
+    test_timeouts: &[TestTimeout],
+    expected_panics: &[ExpectedPanic],
+    invariant_tests: &[String],
+    setup_fixtures: &[String],
     block_number_map: &mut BlockNumberMap,
 ) -> Result<CompiledTestCrateRunnable> {
     let mut test_cases = vec![];
@@ -66,23 +157,64 @@ async fn to_runnable(
             None
         };
 
-        test_cases.push(TestCaseRunnable {
+        let timeout = test_timeouts
+            .iter()
+            .find(|timeout| timeout.matches(&case.name))
+            .map(TestTimeout::timeout);
+        let expected_result = expected_result_of(&case.name, case.expected_result, expected_panics);
+
+        let invariant = invariant_tests.contains(&case.name);
+        let setup_fixture = setup_fixture_of(&case.name, setup_fixtures).map(str::to_string);
+        let is_setup_fixture = setup_fixtures.contains(&case.name);
+
+        let test_case = TestCaseRunnable {
             name: case.name,
             available_gas: case.available_gas,
             ignored: case.ignored,
-            expected_result: case.expected_result,
+            expected_result,
             fork_config,
             fuzzer_config: case.fuzzer_config,
             test_details: case.test_details,
-        });
+            fixed_arguments: None,
+            timeout,
+            invariant,
+            setup_fixture,
+            is_setup_fixture,
+        };
+
+        for (name, parametrized) in cases_of(
+            &test_case.name,
+            test_case.fuzzer_config.is_some(),
+            parametrized_cases,
+        ) {
+            test_cases.push(TestCaseRunnable {
+                name,
+                fixed_arguments: parametrized.map(|parametrized| FixedArguments {
+                    function_name: test_case.name.clone(),
+                    calldata: parametrized.arguments().to_vec(),
+                }),
+                ..test_case.clone()
+            });
+        }
     }
 
     Ok(CompiledTestCrateRunnable {
         sierra_program: compiled_test_crate.sierra_program,
         test_cases,
+        tests_location: compiled_test_crate.tests_location,
+        package_name: package_name.to_string(),
     })
 }
 
+/// Number of test cases of the crate, excluding setup fixtures
+fn tests_count(test_crate: &CompiledTestCrateRunnable) -> usize {
+    test_crate
+        .test_cases
+        .iter()
+        .filter(|case| !case.is_setup_fixture)
+        .count()
+}
+
 /// Run the tests in the package at the given path
 ///
 /// # Arguments
@@ -93,7 +225,12 @@ async fn to_runnable(
 /// * `runner_config` - A configuration of the test runner
 /// * `runner_params` - A struct with parameters required to run tests e.g. map with contracts
 /// * `fork_target` - A configuration of forks used in tests
-#[allow(clippy::implicit_hasher)]
+/// * `parametrized_cases` - Fixed arguments of parametrized tests
+/// * `test_timeouts` - Time limits of specific tests, overriding the one in `runner_config`
+/// * `expected_panics` - Expected panics of specific tests, overriding their `#[should_panic]` attributes
+/// * `invariant_tests` - Full names of tests executing random sequences of calls to target contracts
+/// * `setup_fixtures` - Full names of tests run before the tests of their modules, which start from the state they leave
+#[allow(clippy::implicit_hasher, clippy::too_many_arguments)]
 pub async fn run(
     package_name: &str,
     snforge_target_dir_path: &Utf8Path,
@@ -101,37 +238,112 @@ pub async fn run(
     runner_config: Arc<RunnerConfig>,
     runner_params: Arc<RunnerParams>,
     fork_targets: &[ForkTarget],
+    parametrized_cases: &[ParametrizedCase],
+    test_timeouts: &[TestTimeout],
+    expected_panics: &[ExpectedPanic],
+    invariant_tests: &[String],
+    setup_fixtures: &[String],
     block_number_map: &mut BlockNumberMap,
 ) -> Result<Vec<TestCrateSummary>> {
     let test_crates = load_test_artifacts(snforge_target_dir_path, package_name)
         .context("Failed to load test artifacts, make sure to use scarb >=2.5.4")?;
-    let all_tests: usize = test_crates.iter().map(|tc| tc.test_cases.len()).sum();
+    // Setup fixtures are not counted as tests
+    let is_test = |name: &String| !setup_fixtures.contains(name);
+    let all_tests: usize = test_crates
+        .iter()
+        .map(|tc| {
+            tc.test_cases
+                .iter()
+                .filter(|case| is_test(&case.name))
+                .count()
+        })
+        .sum();
+
+    ensure_tests_exist(
+        parametrized_cases.iter().map(ParametrizedCase::test),
+        "test_case",
+        &test_crates,
+        package_name,
+    )?;
+    ensure_tests_exist(
+        test_timeouts.iter().map(TestTimeout::test),
+        "test_timeout",
+        &test_crates,
+        package_name,
+    )?;
+    ensure_tests_exist(
+        expected_panics.iter().map(ExpectedPanic::test),
+        "expected_panic",
+        &test_crates,
+        package_name,
+    )?;
+    ensure_tests_exist(
+        invariant_tests.iter().map(String::as_str),
+        "invariant_tests",
+        &test_crates,
+        package_name,
+    )?;
+    ensure_tests_exist(
+        setup_fixtures.iter().map(String::as_str),
+        "setup_fixtures",
+        &test_crates,
+        package_name,
+    )?;
+    ensure_tagged_tests_exist(tests_filter.test_tags(), &test_crates, package_name)?;
 
     let test_crates = test_crates
         .into_iter()
-        .map(|tc| tests_filter.filter_tests(tc))
+        .map(|tc| tests_filter.filter_tests(tc, setup_fixtures))
         .collect::<Result<Vec<CompiledTestCrateRaw>>>()?;
-    let not_filtered: usize = test_crates.iter().map(|tc| tc.test_cases.len()).sum();
+    let not_filtered: usize = test_crates
+        .iter()
+        .map(|tc| {
+            tc.test_cases
+                .iter()
+                .filter(|case| is_test(&case.name))
+                .count()
+        })
+        .sum();
     let filtered = all_tests - not_filtered;
 
     warn_if_available_gas_used_with_incompatible_scarb_version(&test_crates)?;
     warn_if_incompatible_rpc_version(&test_crates, fork_targets).await?;
 
+    let mut runnable_test_crates = vec![];
+    for compiled_test_crate in test_crates {
+        runnable_test_crates.push(
+            to_runnable(
+                compiled_test_crate,
+                package_name,
+                fork_targets,
+                parametrized_cases,
+                test_timeouts,
+                expected_panics,
+                invariant_tests,
+                setup_fixtures,
+                block_number_map,
+            )
+            .await?,
+        );
+    }
+
     pretty_printing::print_collected_tests_count(
-        test_crates.iter().map(|tests| tests.test_cases.len()).sum(),
+        runnable_test_crates.iter().map(tests_count).sum(),
+        filtered,
         package_name,
+        runner_config.output_format,
     );
 
     let mut summaries = vec![];
 
-    for compiled_test_crate in test_crates {
+    for compiled_test_crate in runnable_test_crates {
         pretty_printing::print_running_tests(
             compiled_test_crate.tests_location,
-            compiled_test_crate.test_cases.len(),
+            tests_count(&compiled_test_crate),
+            package_name,
+            runner_config.output_format,
         );
 
-        let compiled_test_crate =
-            to_runnable(compiled_test_crate, fork_targets, block_number_map).await?;
         let compiled_test_crate = Arc::new(compiled_test_crate);
         let runner_config = runner_config.clone();
         let runner_params = runner_params.clone();
@@ -159,8 +371,6 @@ pub async fn run(
         }
     }
 
-    pretty_printing::print_test_summary(&summaries, filtered);
-
     let any_fuzz_test_was_run = summaries.iter().any(|crate_summary| {
         crate_summary
             .test_case_summaries
@@ -169,9 +379,14 @@ pub async fn run(
             .any(|summary| summary.is_passed() || summary.is_failed())
     });
 
-    if any_fuzz_test_was_run {
-        pretty_printing::print_test_seed(runner_config.fuzzer_seed);
-    }
+    pretty_printing::print_test_summary(
+        &summaries,
+        filtered,
+        package_name,
+        any_fuzz_test_was_run.then_some(runner_config.fuzzer_seed),
+        tests_filter.shard(),
+        runner_config.output_format,
+    );
 
     Ok(summaries)
 }
@@ -179,9 +394,13 @@ pub async fn run(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::compiled_raw::{CompiledTestCrateRaw, CrateLocation, TestCaseRaw};
+    use crate::compiled_raw::{CompiledTestCrateRaw, TestCaseRaw};
     use cairo_lang_sierra::{ids::GenericTypeId, program::Program};
-    use forge_runner::{compiled_runnable::TestDetails, expected_result::ExpectedTestResult};
+    use forge_runner::{
+        compiled_runnable::{CrateLocation, TestDetails},
+        expected_result::ExpectedTestResult,
+    };
+    use std::time::Duration;
 
     #[tokio::test]
     async fn to_runnable_unparsable_url() {
@@ -219,11 +438,19 @@ mod tests {
             tests_location: CrateLocation::Lib,
         };
 
-        assert!(
-            to_runnable(mocked_tests, &[], &mut BlockNumberMap::default())
-                .await
-                .is_err()
-        );
+        assert!(to_runnable(
+            mocked_tests,
+            "crate1",
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
+            &mut BlockNumberMap::default()
+        )
+        .await
+        .is_err());
     }
 
     #[tokio::test]
@@ -260,6 +487,7 @@ mod tests {
 
         assert!(to_runnable(
             mocked_tests,
+            "crate1",
             &[ForkTarget::new(
                 "definitely_non_existing".to_string(),
                 RawForkParams {
@@ -268,9 +496,64 @@ mod tests {
                     block_id_value: "120".to_string(),
                 },
             )],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
             &mut BlockNumberMap::default()
         )
         .await
         .is_err());
     }
+
+    #[tokio::test]
+    async fn to_runnable_uses_test_timeouts() {
+        let test_case = |name: &str| TestCaseRaw {
+            name: name.to_string(),
+            available_gas: None,
+            ignored: false,
+            expected_result: ExpectedTestResult::Success,
+            fork_config: None,
+            fuzzer_config: None,
+            test_details: TestDetails::default(),
+        };
+        let mocked_tests = CompiledTestCrateRaw {
+            sierra_program: Program {
+                type_declarations: vec![],
+                libfunc_declarations: vec![],
+                statements: vec![],
+                funcs: vec![],
+            },
+            test_cases: vec![
+                test_case("crate1::heavy"),
+                test_case("crate1::light"),
+                test_case("crate1::nested::heavy"),
+            ],
+            tests_location: CrateLocation::Lib,
+        };
+
+        let runnable = to_runnable(
+            mocked_tests,
+            "crate1",
+            &[],
+            &[],
+            &[TestTimeout::new(
+                "crate1::heavy".to_string(),
+                Duration::from_secs(300),
+            )],
+            &[],
+            &[],
+            &[],
+            &mut BlockNumberMap::default(),
+        )
+        .await
+        .unwrap();
+        let timeouts: Vec<_> = runnable
+            .test_cases
+            .iter()
+            .map(|case| case.timeout)
+            .collect();
+        assert_eq!(timeouts, vec![Some(Duration::from_secs(300)), None, None]);
+    }
 }
@@ -0,0 +1,98 @@
+use crate::compiled_raw::{CompiledTestCrateRaw, RawForkConfig, RawForkParams};
+use crate::scarb::config::{ExpectedPanic, ForkTarget, ParametrizedCase};
+use crate::scarb::load_test_artifacts;
+use crate::test_filter::TestsFilter;
+use crate::{cases_of, expected_result_of, replace_id_with_params};
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use forge_runner::compiled_runnable::{CrateLocation, FuzzerConfig};
+use forge_runner::expected_result::ExpectedTestResult;
+
+/// Test collected by `snforge test --list`, as it would be run
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListedTest {
+    pub name: String,
+    pub location: CrateLocation,
+    pub ignored: bool,
+    pub available_gas: Option<usize>,
+    pub expected_result: ExpectedTestResult,
+    pub fuzzer_config: Option<FuzzerConfig>,
+    pub fork: Option<ListedFork>,
+}
+
+/// Fork used by a listed test, resolved from `[[tool.snforge.fork]]` if referred to by name
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListedFork {
+    pub name: Option<String>,
+    pub params: RawForkParams,
+}
+
+/// Collect and filter the tests in the package at the given path the same way as `run` does, without running them.
+/// Forks are not validated, so no requests are sent to the RPC nodes.
+///
+/// # Arguments
+///
+/// * `package_name` - Name of the package specified in Scarb.toml
+/// * `snforge_target_dir_path` - Absolute path to the directory with snforge test artifacts (usually `{package_path}/target/{profile_name}/snforge`)
+/// * `tests_filter` - `TestFilter` structure used to determine what tests to list
+/// * `fork_target` - A configuration of forks used in tests
+/// * `parametrized_cases` - Fixed arguments of parametrized tests
+/// * `setup_fixtures` - Full names of setup fixtures, which are not listed as tests
+/// * `expected_panics` - Expected panics of specific tests, overriding their `#[should_panic]` attributes
+pub fn list_tests(
+    package_name: &str,
+    snforge_target_dir_path: &Utf8Path,
+    tests_filter: &TestsFilter,
+    fork_targets: &[ForkTarget],
+    parametrized_cases: &[ParametrizedCase],
+    setup_fixtures: &[String],
+    expected_panics: &[ExpectedPanic],
+) -> Result<Vec<ListedTest>> {
+    let test_crates = load_test_artifacts(snforge_target_dir_path, package_name)
+        .context("Failed to load test artifacts, make sure to use scarb >=2.5.4")?;
+
+    let mut listed = vec![];
+    for test_crate in test_crates {
+        let CompiledTestCrateRaw {
+            test_cases,
+            tests_location,
+            ..
+        } = tests_filter.filter_tests(test_crate, setup_fixtures)?;
+
+        for case in test_cases {
+            if setup_fixtures.contains(&case.name) {
+                continue;
+            }
+            let fork = case
+                .fork_config
+                .as_ref()
+                .map(|fork_config| -> Result<ListedFork> {
+                    Ok(ListedFork {
+                        name: match fork_config {
+                            RawForkConfig::Id(name) => Some(name.clone()),
+                            RawForkConfig::Params(_) => None,
+                        },
+                        params: replace_id_with_params(fork_config, fork_targets)?.clone(),
+                    })
+                })
+                .transpose()?;
+            let expected_result =
+                expected_result_of(&case.name, case.expected_result, expected_panics);
+
+            for (name, _) in cases_of(&case.name, case.fuzzer_config.is_some(), parametrized_cases)
+            {
+                listed.push(ListedTest {
+                    name,
+                    location: tests_location,
+                    ignored: case.ignored,
+                    available_gas: case.available_gas,
+                    expected_result: expected_result.clone(),
+                    fuzzer_config: case.fuzzer_config.clone(),
+                    fork: fork.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(listed)
+}
@@ -1,17 +1,25 @@
 use anyhow::{anyhow, bail, Context, Result};
-use camino::Utf8Path;
-use clap::{Parser, Subcommand, ValueEnum};
-use forge::scarb::config::ForgeConfig;
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use forge::coverage::{CoverageReport, COVERAGE_REPORT_PATH};
+use forge::gas_snapshot::{validate_tolerance, GasSnapshot, GAS_SNAPSHOT_FILE};
+use forge::junit::JUnitReport;
+use forge::list::list_tests;
+use forge::scarb::config::{validate_tag, ForgeConfig};
 use forge::scarb::{
     build_contracts_with_scarb, build_test_artifacts_with_scarb, config_from_scarb_for_package,
 };
-use forge::shared_cache::{clean_cache, set_cached_failed_tests_names};
-use forge::test_filter::TestsFilter;
+use forge::shared_cache::{
+    clean_cache, clear_fuzz_corpus, fuzz_corpus_entries, set_cached_failed_tests_names,
+};
+use forge::test_filter::{Shard, TagFilter, TestsFilter, TestsFilterFlags};
+use forge::watch::SourcesWatcher;
 use forge::{pretty_printing, run};
 use forge_runner::contracts_data::ContractsData;
-use forge_runner::test_case_summary::{AnyTestCaseSummary, TestCaseSummary};
+use forge_runner::fuzz_corpus::FuzzCorpus;
+use forge_runner::test_case_summary::{AnyTestCaseSummary, TestCaseSummary, TestTime};
 use forge_runner::test_crate_summary::TestCrateSummary;
-use forge_runner::{RunnerConfig, RunnerParams, CACHE_DIR};
+use forge_runner::{OutputFormat, RunnerConfig, RunnerParams, CACHE_DIR};
 use rand::{thread_rng, RngCore};
 use scarb_api::{
     get_contracts_map,
@@ -23,9 +31,11 @@ use scarb_ui::args::PackagesFilter;
 use forge::block_number_map::BlockNumberMap;
 use semver::{Comparator, Op, Version, VersionReq};
 use shared::print::print_as_warning;
+use std::collections::{BTreeMap, HashSet};
 use std::env;
 use std::sync::Arc;
 use std::thread::available_parallelism;
+use std::time::Duration;
 use tokio::runtime::Builder;
 use universal_sierra_compiler_api::UniversalSierraCompilerCommand;
 
@@ -55,6 +65,19 @@ enum ForgeSubcommand {
     },
     /// Clean Forge cache directory
     CleanCache {},
+    /// Manage failing fuzz test inputs, which are replayed before generating new random ones
+    FuzzCorpus {
+        #[command(subcommand)]
+        command: FuzzCorpusCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum FuzzCorpusCommand {
+    /// List stored failing fuzz test inputs
+    List {},
+    /// Remove all stored failing fuzz test inputs
+    Clear {},
 }
 
 #[derive(ValueEnum, Debug, Clone)]
@@ -64,7 +87,22 @@ enum ColorOption {
     Never,
 }
 
-#[derive(Parser, Debug)]
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum FormatOption {
+    Human,
+    Json,
+}
+
+impl From<FormatOption> for OutputFormat {
+    fn from(value: FormatOption) -> Self {
+        match value {
+            FormatOption::Human => OutputFormat::Human,
+            FormatOption::Json => OutputFormat::Json,
+        }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
 #[allow(clippy::struct_excessive_bools)]
 struct TestArgs {
     /// Name used to filter tests
@@ -72,6 +110,18 @@ struct TestArgs {
     /// Use exact matches for `test_filter`
     #[arg(short, long)]
     exact: bool,
+    /// Treat `test_filter` as a regular expression matched against test names
+    #[arg(long, conflicts_with = "exact", requires = "test_filter")]
+    regex: bool,
+    /// Skip tests whose names contain the given pattern, can be passed multiple times
+    #[arg(long, value_name = "PATTERN")]
+    skip: Vec<String>,
+    /// Run only tests with the given tag, can be passed multiple times
+    #[arg(long, value_name = "TAG", value_parser = validate_tag_value)]
+    tag: Vec<String>,
+    /// Skip tests with the given tag, can be passed multiple times
+    #[arg(long, value_name = "TAG", value_parser = validate_tag_value)]
+    exclude_tag: Vec<String>,
 
     /// Stop executing tests after the first failed test
     #[arg(short = 'x', long)]
@@ -98,14 +148,38 @@ struct TestArgs {
     #[arg(long)]
     detailed_resources: bool,
 
+    /// Display wall-clock time of every test, with time spent on fork RPC requests
+    #[arg(long)]
+    show_time: bool,
+
+    /// Display the given number of slowest tests after running all tests
+    #[arg(long, value_name = "N")]
+    report_slowest: Option<usize>,
+
     /// Control when colored output is used
     #[arg(value_enum, long, default_value_t = ColorOption::Auto, value_name="WHEN")]
     color: ColorOption,
 
+    /// Format of the printed test results
+    #[arg(value_enum, long, default_value_t = FormatOption::Human)]
+    format: FormatOption,
+
     /// Run tests that failed during the last run
     #[arg(long)]
     rerun_failed: bool,
 
+    /// Run only the given part of the tests, e.g. `2/4` runs the second of four disjoint parts
+    #[arg(long, value_name = "INDEX/TOTAL")]
+    shard: Option<Shard>,
+
+    /// List collected tests with their attributes instead of running them
+    #[arg(long, conflicts_with = "watch")]
+    list: bool,
+
+    /// Keep running and rerun tests whenever Cairo sources or Scarb.toml of the workspace change
+    #[arg(long)]
+    watch: bool,
+
     /// Save execution traces of all test which have passed and are not fuzz tests
     #[arg(long)]
     save_trace_data: bool,
@@ -117,6 +191,36 @@ struct TestArgs {
     /// Number of maximum steps during a single test. For fuzz tests this value is applied to each subtest separately.
     #[arg(long)]
     max_n_steps: Option<u32>,
+
+    /// Time limit (in seconds) of a single test. For fuzz tests this value is applied to each subtest separately.
+    #[arg(long, value_name = "SECONDS", value_parser = validate_timeout_value)]
+    timeout: Option<u64>,
+
+    /// Save test results as a JUnit XML report under the given path
+    #[arg(long, value_name = "PATH")]
+    junit_report: Option<Utf8PathBuf>,
+
+    /// Collect code coverage of the tested packages, print its summary and save it as an LCOV report
+    #[arg(long)]
+    coverage: bool,
+
+    /// Display resources used by every contract function called in tests
+    #[arg(long)]
+    gas_report: bool,
+
+    /// Increase verbosity of the output, `-vvv` displays call traces of failed tests and `-vvvv` of all tests
+    #[arg(short, long, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Save gas used by passed tests to the gas snapshot file
+    #[arg(long)]
+    snapshot: bool,
+    /// Compare gas used by passed tests against the gas snapshot file instead of saving it
+    #[arg(long, requires = "snapshot")]
+    check: bool,
+    /// Allowed change of gas usage (in percents) when checking the gas snapshot
+    #[arg(long, requires = "check", value_name = "PERCENT", value_parser = validate_tolerance_value)]
+    tolerance: Option<f64>,
 }
 
 fn validate_fuzzer_runs_value(val: &str) -> Result<u32> {
@@ -129,6 +233,28 @@ fn validate_fuzzer_runs_value(val: &str) -> Result<u32> {
     Ok(parsed_val)
 }
 
+fn validate_timeout_value(val: &str) -> Result<u64> {
+    let parsed_val: u64 = val
+        .parse()
+        .map_err(|_| anyhow!("Failed to parse {val} as u64"))?;
+    if parsed_val == 0 {
+        bail!("Timeout must be greater than 0")
+    }
+    Ok(parsed_val)
+}
+
+fn validate_tolerance_value(val: &str) -> Result<f64> {
+    let parsed_val: f64 = val
+        .parse()
+        .map_err(|_| anyhow!("Failed to parse {val} as f64"))?;
+    validate_tolerance(parsed_val)
+}
+
+fn validate_tag_value(val: &str) -> Result<String> {
+    validate_tag(val)?;
+    Ok(val.to_string())
+}
+
 fn extract_failed_tests(
     tests_summaries: Vec<TestCrateSummary>,
 ) -> impl Iterator<Item = AnyTestCaseSummary> {
@@ -138,38 +264,104 @@ fn extract_failed_tests(
         .filter(|test_case_summary| {
             matches!(
                 test_case_summary,
-                AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed { .. })
-                    | AnyTestCaseSummary::Single(TestCaseSummary::Failed { .. })
+                AnyTestCaseSummary::Fuzzing(
+                    TestCaseSummary::Failed { .. } | TestCaseSummary::TimedOut { .. }
+                ) | AnyTestCaseSummary::Single(
+                    TestCaseSummary::Failed { .. } | TestCaseSummary::TimedOut { .. }
+                )
             )
         })
 }
 
-#[allow(clippy::too_many_arguments)]
-#[allow(clippy::fn_params_excessive_bools)]
+/// Names and wall-clock times of all run tests
+fn extract_test_times(tests_summaries: &[TestCrateSummary]) -> Vec<(String, TestTime)> {
+    tests_summaries
+        .iter()
+        .flat_map(|test_file_summary| &test_file_summary.test_case_summaries)
+        .filter_map(|test_case_summary| {
+            Some((
+                test_case_summary.name()?.to_string(),
+                test_case_summary.time()?,
+            ))
+        })
+        .collect()
+}
+
 fn combine_configs(
     workspace_root: &Utf8Path,
-    exit_first: bool,
-    fuzzer_runs: Option<u32>,
-    fuzzer_seed: Option<u64>,
-    detailed_resources: bool,
-    save_trace_data: bool,
-    build_profile: bool,
-    max_n_steps: Option<u32>,
+    args: &TestArgs,
     forge_config: &ForgeConfig,
 ) -> RunnerConfig {
     RunnerConfig::new(
         workspace_root.to_path_buf(),
-        exit_first || forge_config.exit_first,
-        fuzzer_runs
+        args.exit_first || forge_config.exit_first,
+        args.fuzzer_runs
             .or(forge_config.fuzzer_runs)
             .unwrap_or(FUZZER_RUNS_DEFAULT),
-        fuzzer_seed
+        args.fuzzer_seed
             .or(forge_config.fuzzer_seed)
             .unwrap_or_else(|| thread_rng().next_u64()),
-        detailed_resources || forge_config.detailed_resources,
-        save_trace_data || forge_config.save_trace_data,
-        build_profile || forge_config.build_profile,
-        max_n_steps.or(forge_config.max_n_steps),
+        args.detailed_resources || forge_config.detailed_resources,
+        args.save_trace_data || forge_config.save_trace_data,
+        args.build_profile || forge_config.build_profile,
+        args.max_n_steps.or(forge_config.max_n_steps),
+    )
+    .with_show_time(args.show_time)
+    .with_timeout(
+        args.timeout
+            .or(forge_config.timeout)
+            .map(Duration::from_secs),
+    )
+    .with_output_format(OutputFormat::from(args.format))
+    .with_coverage(args.coverage || forge_config.coverage)
+    .with_gas_report(args.gas_report || forge_config.gas_report)
+    .with_verbosity(args.verbose)
+}
+
+fn tests_filter(
+    args: &TestArgs,
+    forge_config: &ForgeConfig,
+    workspace_root: &Utf8Path,
+) -> TestsFilter {
+    // Tags passed as arguments override the ones from Scarb.toml
+    let tag_filter = TagFilter::new(
+        if args.tag.is_empty() {
+            forge_config.tags.clone()
+        } else {
+            args.tag.clone()
+        },
+        if args.exclude_tag.is_empty() {
+            forge_config.exclude_tags.clone()
+        } else {
+            args.exclude_tag.clone()
+        },
+        forge_config.test_tag.clone(),
+    );
+
+    TestsFilter::from_flags(
+        TestsFilterFlags {
+            test_name_filter: args.test_filter.clone(),
+            exact_match: args.exact,
+            regex_match: args.regex,
+            only_ignored: args.only_ignored,
+            include_ignored: args.include_ignored,
+            rerun_failed: args.rerun_failed,
+            shard: args.shard,
+            skip: args.skip.clone(),
+            tag_filter,
+        },
+        workspace_root.join(CACHE_DIR),
+    )
+}
+
+/// Filter selecting all tests of the package, including the ignored ones
+fn all_tests_filter(workspace_root: &Utf8Path) -> TestsFilter {
+    TestsFilter::from_flags(
+        TestsFilterFlags {
+            include_ignored: true,
+            ..Default::default()
+        },
+        workspace_root.join(CACHE_DIR),
     )
 }
 
@@ -215,6 +407,13 @@ fn test_workspace(args: TestArgs) -> Result<bool> {
         .join(&scarb_metadata.current_profile)
         .join("snforge");
 
+    // Tests of every package are run from its root, paths from the command line are relative to the original directory
+    let current_dir = Utf8PathBuf::try_from(env::current_dir()?)?;
+    let cli_junit_report_path = args
+        .junit_report
+        .as_ref()
+        .map(|path| current_dir.join(path));
+
     let packages: Vec<PackageMetadata> = args
         .packages_filter
         .match_many(&scarb_metadata)
@@ -222,8 +421,10 @@ fn test_workspace(args: TestArgs) -> Result<bool> {
 
     let filter = PackagesFilter::generate_for::<Metadata>(packages.iter());
 
-    build_test_artifacts_with_scarb(filter.clone())?;
-    build_contracts_with_scarb(filter.clone())?;
+    let output_format = OutputFormat::from(args.format);
+
+    build_test_artifacts_with_scarb(filter.clone(), output_format)?;
+    build_contracts_with_scarb(filter.clone(), output_format)?;
 
     let cores = if let Ok(available_cores) = available_parallelism() {
         available_cores.get()
@@ -237,10 +438,26 @@ fn test_workspace(args: TestArgs) -> Result<bool> {
         .enable_all()
         .build()?;
 
-    let all_failed_tests = rt.block_on({
+    let result = rt.block_on({
         rt.spawn(async move {
             let mut block_number_map = BlockNumberMap::default();
             let mut all_failed_tests = vec![];
+            let mut test_times = vec![];
+            let mut junit_reports: BTreeMap<Utf8PathBuf, JUnitReport> = BTreeMap::new();
+            let mut coverage_report: Option<CoverageReport> = None;
+            let gas_snapshot_path = workspace_root.join(GAS_SNAPSHOT_FILE);
+            let previous_gas_snapshot = if args.check {
+                Some(GasSnapshot::load(&gas_snapshot_path)?)
+            } else {
+                None
+            };
+            // Entries of tests which are not run are kept, so running a part of the tests does not wipe them
+            let mut gas_snapshot = if args.snapshot && !args.check {
+                GasSnapshot::load_or_default(&gas_snapshot_path)?
+            } else {
+                GasSnapshot::default()
+            };
+            let mut gas_snapshot_ok = true;
             for package in &packages {
                 env::set_current_dir(&package.root)?;
 
@@ -250,58 +467,199 @@ fn test_workspace(args: TestArgs) -> Result<bool> {
 
                 let contracts_data = ContractsData::try_from(contracts)?;
 
-                let runner_config = Arc::new(combine_configs(
-                    &workspace_root,
-                    args.exit_first,
-                    args.fuzzer_runs,
-                    args.fuzzer_seed,
-                    args.detailed_resources,
-                    args.save_trace_data,
-                    args.build_profile,
-                    args.max_n_steps,
-                    &forge_config,
+                let runner_config =
+                    Arc::new(combine_configs(&workspace_root, &args, &forge_config));
+                let fuzz_corpus_path =
+                    FuzzCorpus::path(&workspace_root.join(CACHE_DIR), &package.name);
+                let runner_params = Arc::new(RunnerParams::new(
+                    contracts_data,
+                    env::vars().collect(),
+                    FuzzCorpus::load(&fuzz_corpus_path)?,
                 ));
-                let runner_params =
-                    Arc::new(RunnerParams::new(contracts_data, env::vars().collect()));
 
                 let tests_file_summaries = run(
                     &package.name,
                     &snforge_target_dir_path,
-                    &TestsFilter::from_flags(
-                        args.test_filter.clone(),
-                        args.exact,
-                        args.only_ignored,
-                        args.include_ignored,
-                        args.rerun_failed,
-                        workspace_root.join(CACHE_DIR),
-                    ),
-                    runner_config,
-                    runner_params,
+                    &tests_filter(&args, &forge_config, &workspace_root),
+                    runner_config.clone(),
+                    runner_params.clone(),
                     &forge_config.fork,
+                    &forge_config.test_case,
+                    &forge_config.test_timeout,
+                    &forge_config.expected_panic,
+                    &forge_config.invariant_tests,
+                    &forge_config.setup_fixtures,
                     &mut block_number_map,
                 )
                 .await?;
 
+                runner_params.fuzz_corpus().save(&fuzz_corpus_path)?;
+
+                if runner_config.coverage {
+                    let coverage = runner_params.coverage().data();
+                    pretty_printing::print_coverage_summary(
+                        &coverage,
+                        &package.name,
+                        output_format,
+                    );
+                    coverage_report
+                        .get_or_insert_with(Default::default)
+                        .add_package(&package.root, &package.name, &coverage);
+                }
+
+                if runner_config.gas_report {
+                    pretty_printing::print_gas_report(
+                        &runner_params.gas_report().report(),
+                        &package.name,
+                        output_format,
+                    );
+                }
+
+                if args.snapshot {
+                    let collected_tests: HashSet<String> = list_tests(
+                        &package.name,
+                        &snforge_target_dir_path,
+                        &all_tests_filter(&workspace_root),
+                        &forge_config.fork,
+                        &forge_config.test_case,
+                        &forge_config.setup_fixtures,
+                        &forge_config.expected_panic,
+                    )?
+                    .into_iter()
+                    .map(|test| test.name)
+                    .collect();
+                    let mut package_gas_snapshot = GasSnapshot::default();
+                    package_gas_snapshot.add_package(&package.name, &tests_file_summaries);
+
+                    if let Some(previous_gas_snapshot) = &previous_gas_snapshot {
+                        let tolerance = args
+                            .tolerance
+                            .or(forge_config.gas_snapshot_tolerance)
+                            .unwrap_or_default();
+                        let check = package_gas_snapshot.check(
+                            previous_gas_snapshot,
+                            &package.name,
+                            &collected_tests,
+                            tolerance,
+                        );
+                        pretty_printing::print_gas_snapshot_check(&check, tolerance, output_format);
+                        gas_snapshot_ok &= check.is_ok();
+                    } else {
+                        gas_snapshot.remove_stale_tests(&package.name, &collected_tests);
+                        gas_snapshot.extend(package_gas_snapshot);
+                    }
+                }
+
+                // Path from Scarb.toml is relative to the workspace root
+                if let Some(junit_report_path) = cli_junit_report_path.clone().or_else(|| {
+                    forge_config
+                        .junit_report
+                        .as_ref()
+                        .map(|path| workspace_root.join(path))
+                }) {
+                    junit_reports
+                        .entry(junit_report_path)
+                        .or_default()
+                        .add_package(&package.name, &tests_file_summaries);
+                }
+
+                test_times.extend(extract_test_times(&tests_file_summaries));
                 all_failed_tests.extend(extract_failed_tests(tests_file_summaries));
             }
             set_cached_failed_tests_names(&all_failed_tests, &workspace_root.join(CACHE_DIR))?;
+            for (path, junit_report) in &junit_reports {
+                junit_report.save(path)?;
+            }
+            if args.snapshot && !args.check {
+                gas_snapshot.save(&gas_snapshot_path)?;
+                pretty_printing::print_gas_snapshot_saved(&gas_snapshot_path, output_format);
+            }
+            if let Some(coverage_report) = coverage_report {
+                let path = workspace_root.join(COVERAGE_REPORT_PATH);
+                coverage_report.save(&path)?;
+                pretty_printing::print_coverage_report_saved(&path, output_format);
+            }
             pretty_printing::print_latest_blocks_numbers(
                 block_number_map.get_url_to_latest_block_number(),
+                output_format,
             );
+            if let Some(count) = args.report_slowest {
+                pretty_printing::print_slowest_tests(test_times, count, output_format);
+            }
 
-            Ok::<_, anyhow::Error>(all_failed_tests)
+            Ok::<_, anyhow::Error>((all_failed_tests, gas_snapshot_ok))
         })
-    })??;
+    });
+    // Runs of timed out tests are left running in the background, they must not block exiting
+    rt.shutdown_background();
+    let (all_failed_tests, gas_snapshot_ok) = result??;
 
-    pretty_printing::print_failures(&all_failed_tests);
+    pretty_printing::print_failures(&all_failed_tests, output_format);
 
-    Ok(all_failed_tests.is_empty())
+    Ok(all_failed_tests.is_empty() && gas_snapshot_ok)
 }
 
-#[allow(clippy::too_many_lines)]
-fn main_execution() -> Result<bool> {
-    let cli = Cli::parse();
+fn list_workspace(args: &TestArgs) -> Result<bool> {
+    let scarb_metadata = ScarbCommand::metadata().inherit_stderr().run()?;
+    let workspace_root = scarb_metadata.workspace.root.clone();
+    let snforge_target_dir_path = target_dir_for_workspace(&scarb_metadata)
+        .join(&scarb_metadata.current_profile)
+        .join("snforge");
 
+    let packages: Vec<PackageMetadata> = args
+        .packages_filter
+        .match_many(&scarb_metadata)
+        .context("Failed to find any packages matching the specified filter")?;
+
+    let filter = PackagesFilter::generate_for::<Metadata>(packages.iter());
+    let output_format = OutputFormat::from(args.format);
+
+    // Contracts are not needed to collect tests
+    build_test_artifacts_with_scarb(filter, output_format)?;
+
+    for package in &packages {
+        env::set_current_dir(&package.root)?;
+
+        let forge_config = config_from_scarb_for_package(&scarb_metadata, &package.id)?;
+        let tests = list_tests(
+            &package.name,
+            &snforge_target_dir_path,
+            &tests_filter(args, &forge_config, &workspace_root),
+            &forge_config.fork,
+            &forge_config.test_case,
+            &forge_config.setup_fixtures,
+            &forge_config.expected_panic,
+        )?;
+
+        pretty_printing::print_listed_tests(&tests, &package.name, output_format);
+    }
+
+    Ok(true)
+}
+
+fn watch_workspace(args: TestArgs) -> Result<bool> {
+    let scarb_metadata = ScarbCommand::metadata().inherit_stderr().run()?;
+    let workspace_root = scarb_metadata.workspace.root;
+    let current_dir = env::current_dir()?;
+    let output_format = OutputFormat::from(args.format);
+    let mut watcher = SourcesWatcher::new(&workspace_root);
+
+    loop {
+        // Errors (e.g. failed compilation) do not stop watching, tests are rerun after the next change
+        if let Err(error) = test_workspace(args.clone()) {
+            pretty_printing::print_error_message(&error, output_format);
+        }
+        // Tests of every package are run from its root, packages are selected from the original directory
+        env::set_current_dir(&current_dir)?;
+
+        pretty_printing::print_watching(&workspace_root, output_format);
+        let changed = watcher.wait_for_changes();
+        pretty_printing::print_sources_changed(&changed, output_format);
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+fn main_execution(cli: Cli) -> Result<bool> {
     ScarbCommand::new().ensure_available()?;
     UniversalSierraCompilerCommand::ensure_available()?;
 
@@ -314,16 +672,34 @@ fn main_execution() -> Result<bool> {
             clean_cache()?;
             Ok(true)
         }
+        ForgeSubcommand::FuzzCorpus { command } => {
+            match command {
+                FuzzCorpusCommand::List {} => {
+                    pretty_printing::print_fuzz_corpus(&fuzz_corpus_entries()?);
+                }
+                FuzzCorpusCommand::Clear {} => clear_fuzz_corpus()?,
+            }
+            Ok(true)
+        }
+        ForgeSubcommand::Test { args } if args.list => list_workspace(&args),
+        ForgeSubcommand::Test { args } if args.watch => watch_workspace(args),
         ForgeSubcommand::Test { args } => test_workspace(args),
     }
 }
 
 fn main() {
-    match main_execution() {
+    let cli = Cli::parse();
+    // Errors are printed as JSON events too, so the output stays valid newline-delimited JSON
+    let output_format = match &cli.subcommand {
+        ForgeSubcommand::Test { args } => OutputFormat::from(args.format),
+        _ => OutputFormat::Human,
+    };
+
+    match main_execution(cli) {
         Ok(true) => std::process::exit(0),
         Ok(false) => std::process::exit(1),
         Err(error) => {
-            pretty_printing::print_error_message(&error);
+            pretty_printing::print_error_message(&error, output_format);
             std::process::exit(2);
         }
     };
@@ -339,24 +715,12 @@ mod tests {
         let workspace_root: Utf8PathBuf = Default::default();
         let config = combine_configs(
             &workspace_root,
-            false,
-            None,
-            None,
-            false,
-            false,
-            false,
-            None,
+            &TestArgs::parse_from(["test"]),
             &Default::default(),
         );
         let config2 = combine_configs(
             &workspace_root,
-            false,
-            None,
-            None,
-            false,
-            false,
-            false,
-            None,
+            &TestArgs::parse_from(["test"]),
             &Default::default(),
         );
 
@@ -370,13 +734,7 @@ mod tests {
         let workspace_root: Utf8PathBuf = Default::default();
         let config = combine_configs(
             &workspace_root,
-            false,
-            None,
-            None,
-            false,
-            false,
-            false,
-            None,
+            &TestArgs::parse_from(["test"]),
             &Default::default(),
         );
         assert_eq!(
@@ -389,7 +747,7 @@ mod tests {
                 false,
                 false,
                 false,
-                None
+                None,
             )
         );
     }
@@ -405,18 +763,25 @@ mod tests {
             save_trace_data: true,
             build_profile: true,
             max_n_steps: Some(1_000_000),
+            junit_report: None,
+            coverage: true,
+            gas_snapshot_tolerance: None,
+            gas_report: true,
+            test_case: vec![],
+            invariant_tests: vec![],
+            setup_fixtures: vec![],
+            timeout: Some(60),
+            test_timeout: vec![],
+            expected_panic: vec![],
+            test_tag: vec![],
+            tags: vec![],
+            exclude_tags: vec![],
         };
         let workspace_root: Utf8PathBuf = Default::default();
 
         let config = combine_configs(
             &workspace_root,
-            false,
-            None,
-            None,
-            false,
-            false,
-            false,
-            None,
+            &TestArgs::parse_from(["test"]),
             &config_from_scarb,
         );
         assert_eq!(
@@ -429,8 +794,11 @@ mod tests {
                 true,
                 true,
                 true,
-                Some(1_000_000)
+                Some(1_000_000),
             )
+            .with_timeout(Some(Duration::from_secs(60)))
+            .with_coverage(true)
+            .with_gas_report(true)
         );
     }
 
@@ -447,16 +815,41 @@ mod tests {
             save_trace_data: false,
             build_profile: false,
             max_n_steps: Some(1234),
+            junit_report: None,
+            coverage: false,
+            gas_snapshot_tolerance: None,
+            gas_report: false,
+            test_case: vec![],
+            invariant_tests: vec![],
+            setup_fixtures: vec![],
+            timeout: Some(60),
+            test_timeout: vec![],
+            expected_panic: vec![],
+            test_tag: vec![],
+            tags: vec![],
+            exclude_tags: vec![],
         };
         let config = combine_configs(
             &workspace_root,
-            true,
-            Some(100),
-            Some(32),
-            true,
-            true,
-            true,
-            Some(1_000_000),
+            &TestArgs::parse_from([
+                "test",
+                "--exit-first",
+                "--fuzzer-runs",
+                "100",
+                "--fuzzer-seed",
+                "32",
+                "--detailed-resources",
+                "--show-time",
+                "--save-trace-data",
+                "--build-profile",
+                "--max-n-steps",
+                "1000000",
+                "--timeout",
+                "30",
+                "--coverage",
+                "--gas-report",
+                "-vvvv",
+            ]),
             &config_from_scarb,
         );
 
@@ -470,8 +863,13 @@ mod tests {
                 true,
                 true,
                 true,
-                Some(1_000_000)
+                Some(1_000_000),
             )
+            .with_show_time(true)
+            .with_timeout(Some(Duration::from_secs(30)))
+            .with_coverage(true)
+            .with_gas_report(true)
+            .with_verbosity(4)
         );
     }
 }
@@ -1,36 +1,177 @@
-use crate::compiled_raw::CrateLocation;
+use crate::gas_snapshot::SnapshotCheck;
+use crate::list::ListedTest;
+use crate::test_filter::Shard;
 use anyhow::Error;
+use camino::{Utf8Path, Utf8PathBuf};
 use console::style;
+use forge_runner::compiled_runnable::CrateLocation;
+use forge_runner::coverage::{CoverageData, FunctionCoverage};
+use forge_runner::fuzz_corpus::FuzzCorpusEntry;
+use forge_runner::gas_report::FunctionGasReport;
+use forge_runner::json_events::{self, TestEvent};
+use forge_runner::test_case_summary::TestTime;
+use forge_runner::OutputFormat;
 use forge_runner::{test_case_summary::AnyTestCaseSummary, test_crate_summary::TestCrateSummary};
 use starknet_api::block::BlockNumber;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+pub fn print_error_message(error: &Error, output_format: OutputFormat) {
+    if output_format == OutputFormat::Json {
+        TestEvent::Error {
+            message: &format!("{error:#}"),
+        }
+        .print();
+        return;
+    }
 
-pub fn print_error_message(error: &Error) {
     let error_tag = style("ERROR").red();
     println!("[{error_tag}] {error:#}");
 }
 
-pub(crate) fn print_collected_tests_count(tests_num: usize, package_name: &str) {
+pub(crate) fn print_collected_tests_count(
+    tests_num: usize,
+    filtered: usize,
+    package_name: &str,
+    output_format: OutputFormat,
+) {
+    if output_format == OutputFormat::Json {
+        TestEvent::Collected {
+            package: package_name,
+            tests: tests_num,
+            filtered,
+        }
+        .print();
+        return;
+    }
     let plain_text = format!("\n\nCollected {tests_num} test(s) from {package_name} package");
     println!("{}", style(plain_text).bold());
 }
 
-pub(crate) fn print_running_tests(test_crate_file: CrateLocation, tests_num: usize) {
-    let dir_name = match test_crate_file {
+fn dir_name(test_crate_file: CrateLocation) -> &'static str {
+    match test_crate_file {
         CrateLocation::Lib => "src",
         CrateLocation::Tests => "tests",
-    };
+    }
+}
+
+pub(crate) fn print_running_tests(
+    test_crate_file: CrateLocation,
+    tests_num: usize,
+    package_name: &str,
+    output_format: OutputFormat,
+) {
+    let dir_name = dir_name(test_crate_file);
+    if output_format == OutputFormat::Json {
+        TestEvent::SuiteStarted {
+            package: package_name,
+            location: dir_name,
+            tests: tests_num,
+        }
+        .print();
+        return;
+    }
     let plain_text = format!("Running {tests_num} test(s) from {dir_name}/");
 
     println!("{}", style(plain_text).bold());
 }
 
-pub(crate) fn print_test_summary(summaries: &[TestCrateSummary], filtered: usize) {
+pub fn print_listed_tests(tests: &[ListedTest], package_name: &str, output_format: OutputFormat) {
+    if output_format == OutputFormat::Json {
+        for test in tests {
+            TestEvent::TestListed {
+                name: &test.name,
+                package: package_name,
+                location: dir_name(test.location),
+                ignored: test.ignored,
+                available_gas: test.available_gas,
+                expected_result: test.expected_result.to_string(),
+                fuzzer: test
+                    .fuzzer_config
+                    .as_ref()
+                    .map(|fuzzer_config| json_events::Fuzzer {
+                        runs: fuzzer_config.fuzzer_runs,
+                        seed: fuzzer_config.fuzzer_seed,
+                    }),
+                fork: test.fork.as_ref().map(|fork| json_events::Fork {
+                    name: fork.name.as_deref(),
+                    url: &fork.params.url,
+                    block_id_type: &fork.params.block_id_type,
+                    block_id_value: &fork.params.block_id_value,
+                }),
+            }
+            .print();
+        }
+        return;
+    }
+
+    let plain_text = format!(
+        "\n\nCollected {} test(s) from {package_name} package",
+        tests.len()
+    );
+    println!("{}", style(plain_text).bold());
+
+    for test in tests {
+        let mut attributes = vec![format!("{}/", dir_name(test.location))];
+        if test.ignored {
+            attributes.push("ignored".to_string());
+        }
+        if let Some(fuzzer_config) = &test.fuzzer_config {
+            attributes.push(format!(
+                "fuzzer: runs {}, seed {}",
+                fuzzer_config.fuzzer_runs, fuzzer_config.fuzzer_seed
+            ));
+        }
+        if let Some(fork) = &test.fork {
+            let name = fork
+                .name
+                .as_ref()
+                .map(|name| format!("{name} "))
+                .unwrap_or_default();
+            attributes.push(format!(
+                "fork: {name}{} at block {} {}",
+                fork.params.url,
+                fork.params.block_id_type.to_lowercase(),
+                fork.params.block_id_value
+            ));
+        }
+        if let Some(available_gas) = test.available_gas {
+            attributes.push(format!("available gas: {available_gas}"));
+        }
+        attributes.push(format!("expected: {}", test.expected_result));
+
+        println!("{} ({})", test.name, attributes.join(", "));
+    }
+}
+
+pub(crate) fn print_test_summary(
+    summaries: &[TestCrateSummary],
+    filtered: usize,
+    package_name: &str,
+    fuzzer_seed: Option<u64>,
+    shard: Option<Shard>,
+    output_format: OutputFormat,
+) {
     let passed: usize = summaries.iter().map(TestCrateSummary::count_passed).sum();
     let failed: usize = summaries.iter().map(TestCrateSummary::count_failed).sum();
     let skipped: usize = summaries.iter().map(TestCrateSummary::count_skipped).sum();
     let ignored: usize = summaries.iter().map(TestCrateSummary::count_ignored).sum();
 
+    if output_format == OutputFormat::Json {
+        TestEvent::Summary {
+            package: package_name,
+            passed,
+            failed,
+            skipped,
+            ignored,
+            filtered,
+            fuzzer_seed,
+            shard: shard.map(|shard| shard.to_string()),
+        }
+        .print();
+        return;
+    }
+
     println!(
         "{}: {} passed, {} failed, {} skipped, {} ignored, {} filtered out",
         style("Tests").bold(),
@@ -40,28 +181,72 @@ pub(crate) fn print_test_summary(summaries: &[TestCrateSummary], filtered: usize
         ignored,
         filtered,
     );
-}
 
-pub(crate) fn print_test_seed(seed: u64) {
-    println!("{}: {seed}", style("Fuzzer seed").bold());
+    if let Some(seed) = fuzzer_seed {
+        println!("{}: {seed}", style("Fuzzer seed").bold());
+    }
+    if let Some(shard) = shard {
+        println!("{}: {shard}", style("Shard").bold());
+    }
 }
 
-pub fn print_failures(all_failed_tests: &[AnyTestCaseSummary]) {
-    if all_failed_tests.is_empty() {
-        return;
-    }
+pub fn print_failures(all_failed_tests: &[AnyTestCaseSummary], output_format: OutputFormat) {
     let failed_tests_names = all_failed_tests
         .iter()
         .map(|any_test_case_summary| any_test_case_summary.name().unwrap());
 
+    if output_format == OutputFormat::Json {
+        TestEvent::Finished {
+            failures: failed_tests_names.collect(),
+        }
+        .print();
+        return;
+    }
+    if all_failed_tests.is_empty() {
+        return;
+    }
+
     println!("\nFailures:");
     for name in failed_tests_names {
         println!("    {name}");
     }
 }
 
+/// Prints `count` tests with the longest wall-clock time, splitting it into VM execution and fork RPC requests
+pub fn print_slowest_tests(
+    mut test_times: Vec<(String, TestTime)>,
+    count: usize,
+    output_format: OutputFormat,
+) {
+    if output_format == OutputFormat::Json || test_times.is_empty() || count == 0 {
+        return;
+    }
+    test_times.sort_by(|(_, a), (_, b)| b.total.cmp(&a.total));
+    test_times.truncate(count);
+
+    println!("\n{}", style("Slowest tests:").bold());
+    for (name, time) in test_times {
+        println!(
+            "    {name} (total: {}, execution: {}, rpc: {})",
+            format_duration(time.total),
+            format_duration(time.execution()),
+            format_duration(time.rpc)
+        );
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}s", duration.as_secs_f64())
+}
+
 #[allow(clippy::implicit_hasher)]
-pub fn print_latest_blocks_numbers(url_to_latest_block_number_map: &HashMap<String, BlockNumber>) {
+pub fn print_latest_blocks_numbers(
+    url_to_latest_block_number_map: &HashMap<String, BlockNumber>,
+    output_format: OutputFormat,
+) {
+    if output_format == OutputFormat::Json {
+        return;
+    }
     if !url_to_latest_block_number_map.is_empty() {
         println!();
     }
@@ -69,3 +254,310 @@ pub fn print_latest_blocks_numbers(url_to_latest_block_number_map: &HashMap<Stri
         println!("Latest block number = {latest_block_number} for url = {url}");
     }
 }
+
+pub fn print_coverage_summary(
+    coverage: &CoverageData,
+    package_name: &str,
+    output_format: OutputFormat,
+) {
+    if output_format == OutputFormat::Json {
+        TestEvent::Coverage {
+            package: package_name,
+            coverage,
+        }
+        .print();
+        return;
+    }
+
+    let (executed_functions, functions, executed_statements, statements) =
+        functions_summary(&coverage.functions);
+
+    println!(
+        "\n{}",
+        style(format!("Coverage of {package_name} package:")).bold()
+    );
+    println!(
+        "    functions: {executed_functions}/{functions} ({}), sierra statements: {executed_statements}/{statements} ({})",
+        percentage(executed_functions, functions),
+        percentage(executed_statements, statements),
+    );
+
+    for (contract_name, contract) in &coverage.contracts {
+        let called = contract
+            .entry_points
+            .values()
+            .filter(|calls| **calls > 0)
+            .count();
+        println!(
+            "    {contract_name}: {called}/{} functions called ({})",
+            contract.entry_points.len(),
+            percentage(called, contract.entry_points.len()),
+        );
+        if !contract.functions.is_empty() {
+            let (_, _, executed_statements, statements) = functions_summary(&contract.functions);
+            println!(
+                "        sierra statements: {executed_statements}/{statements} ({})",
+                percentage(executed_statements, statements),
+            );
+        }
+        for (function_name, calls) in &contract.entry_points {
+            println!("        {function_name}: {calls} call(s)");
+        }
+    }
+}
+
+/// Executed and all functions and statements
+fn functions_summary(
+    functions: &BTreeMap<String, FunctionCoverage>,
+) -> (usize, usize, usize, usize) {
+    let executed_functions = functions
+        .values()
+        .filter(|function| function.is_executed())
+        .count();
+    let statements = functions.values().map(|function| function.statements).sum();
+    let executed_statements = functions
+        .values()
+        .map(|function| function.executed_statements.len())
+        .sum();
+
+    (
+        executed_functions,
+        functions.len(),
+        executed_statements,
+        statements,
+    )
+}
+
+pub fn print_coverage_report_saved(path: &Utf8Path, output_format: OutputFormat) {
+    if output_format == OutputFormat::Json {
+        return;
+    }
+    println!("\nCoverage report saved to {path}");
+}
+
+pub fn print_gas_report(
+    report: &BTreeMap<String, BTreeMap<String, FunctionGasReport>>,
+    package_name: &str,
+    output_format: OutputFormat,
+) {
+    if output_format == OutputFormat::Json || report.is_empty() {
+        return;
+    }
+
+    println!(
+        "\n{}",
+        style(format!("Gas report for {package_name} package:")).bold()
+    );
+    for (contract_name, functions) in report {
+        println!("\n{}", style(contract_name).bold());
+        println!("{}", gas_report_table(functions));
+    }
+}
+
+fn gas_report_table(functions: &BTreeMap<String, FunctionGasReport>) -> String {
+    let header = [
+        "Function",
+        "Calls",
+        "Min steps",
+        "Mean steps",
+        "Median steps",
+        "Max steps",
+        "Min gas",
+        "Mean gas",
+        "Median gas",
+        "Max gas",
+    ]
+    .map(ToString::to_string);
+    let rows: Vec<[String; 10]> = functions
+        .iter()
+        .map(|(name, function)| {
+            [
+                name.clone(),
+                function.calls.to_string(),
+                function.steps.min.to_string(),
+                format!("{:.2}", function.steps.mean),
+                format!("{:.1}", function.steps.median),
+                function.steps.max.to_string(),
+                function.gas.min.to_string(),
+                format!("{:.2}", function.gas.mean),
+                format!("{:.1}", function.gas.median),
+                function.gas.max.to_string(),
+            ]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[column].len())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    [&header]
+        .into_iter()
+        .chain(&rows)
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(column, (cell, width))| {
+                    // Function names are aligned to the left, numbers to the right
+                    if column == 0 {
+                        format!("{cell:<width$}")
+                    } else {
+                        format!("{cell:>width$}")
+                    }
+                })
+                .collect();
+            format!("| {} |", cells.join(" | "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn print_gas_snapshot_check(
+    check: &SnapshotCheck,
+    tolerance: f64,
+    output_format: OutputFormat,
+) {
+    if output_format == OutputFormat::Json {
+        return;
+    }
+
+    println!("\n{}", style("Gas snapshot check:").bold());
+    if check.diffs.is_empty() && check.missing.is_empty() && check.stale.is_empty() {
+        println!("    No changes in gas usage");
+        return;
+    }
+
+    for diff in &check.diffs {
+        let percentage = diff.percentage().map_or_else(
+            || "-".to_string(),
+            |percentage| format!("{percentage:+.2}%"),
+        );
+        let line = format!(
+            "    {} {}: {} -> {} ({:+}, {percentage})",
+            diff.package,
+            diff.test,
+            diff.previous,
+            diff.current,
+            diff.delta()
+        );
+        if diff.exceeds(tolerance) {
+            println!("{}", style(line).red());
+        } else {
+            println!("{line}");
+        }
+    }
+    for (package, test) in &check.missing {
+        println!("    {package} {test}: not found in the snapshot");
+    }
+    for (package, test) in &check.stale {
+        println!("    {package} {test}: test no longer exists, run `snforge test --snapshot` to remove it");
+    }
+
+    if !check.is_ok() {
+        println!(
+            "{}",
+            style(format!(
+                "{} test(s) exceeded the gas tolerance of {tolerance}%",
+                check.exceeded
+            ))
+            .red()
+        );
+    }
+}
+
+pub fn print_gas_snapshot_saved(path: &Utf8Path, output_format: OutputFormat) {
+    if output_format == OutputFormat::Json {
+        return;
+    }
+    println!("\nGas snapshot saved to {path}");
+}
+
+pub fn print_watching(workspace_root: &Utf8Path, output_format: OutputFormat) {
+    if output_format == OutputFormat::Json {
+        return;
+    }
+    println!(
+        "\n{} for changes in {workspace_root}, press Ctrl+C to stop",
+        style("Watching").bold()
+    );
+}
+
+pub fn print_sources_changed(changed: &[Utf8PathBuf], output_format: OutputFormat) {
+    if output_format == OutputFormat::Json {
+        return;
+    }
+    for path in changed {
+        println!("{}: {path}", style("Changed").bold());
+    }
+}
+
+pub fn print_fuzz_corpus(corpus: &BTreeMap<String, Vec<FuzzCorpusEntry>>) {
+    if corpus.values().all(Vec::is_empty) {
+        println!("Fuzz corpus is empty");
+        return;
+    }
+
+    for (package, entries) in corpus {
+        println!("Fuzz corpus of {package} package:");
+        for entry in entries {
+            println!(
+                "    {} (seed: {}, arguments: [{}])",
+                entry.test,
+                entry.seed,
+                entry.arguments.join(", ")
+            );
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn percentage(part: usize, total: usize) -> String {
+    if total == 0 {
+        return "-".to_string();
+    }
+    format!("{:.2}%", part as f64 / total as f64 * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use forge_runner::gas_report::Statistics;
+    use indoc::indoc;
+
+    #[test]
+    fn formats_gas_report_table() {
+        let functions = BTreeMap::from([(
+            "get_balance".to_string(),
+            FunctionGasReport {
+                calls: 2,
+                steps: Statistics {
+                    min: 100,
+                    mean: 200.0,
+                    median: 200.0,
+                    max: 300,
+                },
+                gas: Statistics {
+                    min: 1,
+                    mean: 2.0,
+                    median: 2.0,
+                    max: 3,
+                },
+            },
+        )]);
+
+        assert_eq!(
+            gas_report_table(&functions),
+            indoc! {"
+                | Function    | Calls | Min steps | Mean steps | Median steps | Max steps | Min gas | Mean gas | Median gas | Max gas |
+                | get_balance |     2 |       100 |     200.00 |        200.0 |       300 |       1 |     2.00 |        2.0 |       3 |"
+            }
+        );
+    }
+}
@@ -4,6 +4,7 @@ use crate::compiled_raw::CompiledTestCrateRaw;
 use crate::scarb::config::{ForgeConfig, RawForgeConfig};
 use anyhow::{anyhow, Context, Result};
 use camino::Utf8Path;
+use forge_runner::OutputFormat;
 use scarb_api::ScarbCommand;
 use scarb_ui::args::PackagesFilter;
 
@@ -34,8 +35,11 @@ pub fn config_from_scarb_for_package(
         .context("Invalid config in Scarb.toml: ")
 }
 
-pub fn build_contracts_with_scarb(filter: PackagesFilter) -> Result<()> {
-    ScarbCommand::new_with_stdio()
+pub fn build_contracts_with_scarb(
+    filter: PackagesFilter,
+    output_format: OutputFormat,
+) -> Result<()> {
+    scarb_command_for_output_format(output_format)
         .arg("build")
         .packages_filter(filter)
         .run()
@@ -43,8 +47,11 @@ pub fn build_contracts_with_scarb(filter: PackagesFilter) -> Result<()> {
     Ok(())
 }
 
-pub fn build_test_artifacts_with_scarb(filter: PackagesFilter) -> Result<()> {
-    ScarbCommand::new_with_stdio()
+pub fn build_test_artifacts_with_scarb(
+    filter: PackagesFilter,
+    output_format: OutputFormat,
+) -> Result<()> {
+    scarb_command_for_output_format(output_format)
         .arg("snforge-test-collector")
         .packages_filter(filter)
         .run()
@@ -52,6 +59,15 @@ pub fn build_test_artifacts_with_scarb(filter: PackagesFilter) -> Result<()> {
     Ok(())
 }
 
+fn scarb_command_for_output_format(output_format: OutputFormat) -> ScarbCommand {
+    let mut command = ScarbCommand::new_with_stdio();
+    // Keep the output machine-readable, Scarb messages are printed as JSON too
+    if output_format == OutputFormat::Json {
+        command.json();
+    }
+    command
+}
+
 pub(crate) fn load_test_artifacts(
     snforge_target_dir_path: &Utf8Path,
     package_name: &str,
@@ -68,13 +84,17 @@ pub(crate) fn load_test_artifacts(
 mod tests {
     use super::*;
     use crate::compiled_raw::RawForkParams;
-    use crate::scarb::config::ForkTarget;
+    use crate::scarb::config::{ExpectedPanic, ForkTarget, ParametrizedCase, TestTag, TestTimeout};
     use assert_fs::fixture::{FileWriteStr, PathChild, PathCopy};
     use assert_fs::TempDir;
+    use cairo_felt::Felt252;
     use camino::Utf8PathBuf;
+    use forge_runner::expected_result::ExpectedPanicValue;
     use indoc::{formatdoc, indoc};
+    use regex::Regex;
     use scarb_api::metadata::MetadataCommandExt;
     use std::str::FromStr;
+    use std::time::Duration;
     use test_utils::tempdir_with_tool_versions;
 
     fn setup_package(package_name: &str) -> TempDir {
@@ -179,7 +199,20 @@ mod tests {
                 max_n_steps: None,
                 detailed_resources: false,
                 save_trace_data: false,
-                build_profile: false
+                build_profile: false,
+                junit_report: None,
+                coverage: false,
+                gas_snapshot_tolerance: None,
+                gas_report: false,
+                test_case: vec![],
+                invariant_tests: vec![],
+                setup_fixtures: vec![],
+                timeout: None,
+                test_timeout: vec![],
+                expected_panic: vec![],
+                test_tag: vec![],
+                tags: vec![],
+                exclude_tags: vec![],
             }
         );
     }
@@ -351,4 +384,405 @@ mod tests {
                 .unwrap_err();
         assert!(format!("{err:?}").contains("block_id.tag can only be equal to Latest"));
     }
+
+    #[test]
+    fn get_forge_config_for_package_with_test_cases() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [[tool.snforge.test_case]]
+            test = "tests::test_sum"
+            args = [1, "0x3"]
+
+            [[tool.snforge.test_case]]
+            test = "tests::test_sum"
+            name = "negative"
+            args = [-1, "2"]
+
+            [[tool.snforge.test_case]]
+            test = "tests::test_sum"
+            args = [5, 7]
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+
+        let config =
+            config_from_scarb_for_package(&scarb_metadata, &scarb_metadata.workspace.members[0])
+                .unwrap();
+
+        assert_eq!(
+            config.test_case,
+            vec![
+                ParametrizedCase::new(
+                    "tests::test_sum".to_string(),
+                    "case_1".to_string(),
+                    vec![Felt252::from(1), Felt252::from(3)],
+                ),
+                ParametrizedCase::new(
+                    "tests::test_sum".to_string(),
+                    "negative".to_string(),
+                    vec![Felt252::from(-1), Felt252::from(2)],
+                ),
+                ParametrizedCase::new(
+                    "tests::test_sum".to_string(),
+                    "case_2".to_string(),
+                    vec![Felt252::from(5), Felt252::from(7)],
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn get_forge_config_for_package_fails_on_same_test_case_name() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [[tool.snforge.test_case]]
+            test = "tests::test_sum"
+            args = [1, 3]
+
+            [[tool.snforge.test_case]]
+            test = "tests::test_sum"
+            name = "case_1"
+            args = [3, 5]
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+
+        let err =
+            config_from_scarb_for_package(&scarb_metadata, &scarb_metadata.workspace.members[0])
+                .unwrap_err();
+        assert!(format!("{err:?}")
+            .contains("Test case name = case_1 is duplicated for test = tests::test_sum"));
+    }
+
+    #[test]
+    fn get_forge_config_for_package_fails_on_setup_fixtures_in_same_module() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [tool.snforge]
+            setup_fixtures = ["simple_package::tests::setup", "simple_package::tests::deploy"]
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+
+        let err =
+            config_from_scarb_for_package(&scarb_metadata, &scarb_metadata.workspace.members[0])
+                .unwrap_err();
+        assert!(format!("{err:?}").contains(
+            "Setup fixtures = simple_package::tests::setup and simple_package::tests::deploy are in the same module"
+        ));
+    }
+
+    #[test]
+    fn get_forge_config_for_package_with_timeout() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [tool.snforge]
+            timeout = 60
+
+            [[tool.snforge.test_timeout]]
+            test = "simple_package::tests::test_fib"
+            timeout = 300
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+
+        let config =
+            config_from_scarb_for_package(&scarb_metadata, &scarb_metadata.workspace.members[0])
+                .unwrap();
+
+        assert_eq!(config.timeout, Some(60));
+        assert_eq!(
+            config.test_timeout,
+            vec![TestTimeout::new(
+                "simple_package::tests::test_fib".to_string(),
+                Duration::from_secs(300)
+            )]
+        );
+    }
+
+    #[test]
+    fn get_forge_config_for_package_err_on_zero_test_timeout() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [[tool.snforge.test_timeout]]
+            test = "simple_package::tests::test_fib"
+            timeout = 0
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+
+        let err =
+            config_from_scarb_for_package(&scarb_metadata, &scarb_metadata.workspace.members[0])
+                .unwrap_err();
+
+        assert!(format!("{err:?}")
+            .contains("Timeout of test = simple_package::tests::test_fib must be greater than 0"));
+    }
+
+    #[test]
+    fn get_forge_config_for_package_with_expected_panics() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [[tool.snforge.expected_panic]]
+            test = "simple_package::tests::test_withdraw"
+            message = "Insufficient balance"
+
+            [[tool.snforge.expected_panic]]
+            test = "simple_package::tests::test_transfer"
+            pattern = "^Insufficient (balance|allowance)$"
+
+            [[tool.snforge.expected_panic]]
+            test = "simple_package::tests::test_approve"
+            prefix = ["0x1", 2]
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+
+        let config =
+            config_from_scarb_for_package(&scarb_metadata, &scarb_metadata.workspace.members[0])
+                .unwrap();
+
+        assert_eq!(
+            config.expected_panic,
+            vec![
+                ExpectedPanic::new(
+                    "simple_package::tests::test_withdraw".to_string(),
+                    ExpectedPanicValue::Message("Insufficient balance".to_string())
+                ),
+                ExpectedPanic::new(
+                    "simple_package::tests::test_transfer".to_string(),
+                    ExpectedPanicValue::MessagePattern(
+                        Regex::new("^Insufficient (balance|allowance)$").unwrap()
+                    )
+                ),
+                ExpectedPanic::new(
+                    "simple_package::tests::test_approve".to_string(),
+                    ExpectedPanicValue::Prefix(vec![Felt252::from(1), Felt252::from(2)])
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn get_forge_config_for_package_fails_on_ambiguous_expected_panic() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [[tool.snforge.expected_panic]]
+            test = "simple_package::tests::test_withdraw"
+            message = "Insufficient balance"
+            contains = "balance"
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+
+        let err =
+            config_from_scarb_for_package(&scarb_metadata, &scarb_metadata.workspace.members[0])
+                .unwrap_err();
+        assert!(format!("{err:?}").contains(
+            "Expected panic of test = simple_package::tests::test_withdraw must set exactly one of message, contains, pattern or prefix"
+        ));
+    }
+
+    #[test]
+    fn get_forge_config_for_package_fails_on_invalid_expected_panic_pattern() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [[tool.snforge.expected_panic]]
+            test = "simple_package::tests::test_withdraw"
+            pattern = "Insufficient (balance"
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+
+        let err =
+            config_from_scarb_for_package(&scarb_metadata, &scarb_metadata.workspace.members[0])
+                .unwrap_err();
+        assert!(format!("{err:?}").contains(
+            "Expected panic pattern = Insufficient (balance of test = simple_package::tests::test_withdraw is not a valid regular expression"
+        ));
+    }
+
+    #[test]
+    fn get_forge_config_for_package_with_tags() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [[tool.snforge.test_tag]]
+            test = "simple_package::integration"
+            tags = ["integration", "slow"]
+
+            [tool.snforge]
+            tags = ["integration"]
+            exclude_tags = ["slow"]
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+
+        let config =
+            config_from_scarb_for_package(&scarb_metadata, &scarb_metadata.workspace.members[0])
+                .unwrap();
+
+        assert_eq!(
+            config.test_tag,
+            vec![TestTag::new(
+                "simple_package::integration".to_string(),
+                vec!["integration".to_string(), "slow".to_string()]
+            )]
+        );
+        assert_eq!(config.tags, vec!["integration".to_string()]);
+        assert_eq!(config.exclude_tags, vec!["slow".to_string()]);
+    }
+
+    #[test]
+    fn get_forge_config_for_package_fails_on_invalid_tag() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [tool.snforge]
+            exclude_tags = ["Slow Tests"]
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+
+        let err =
+            config_from_scarb_for_package(&scarb_metadata, &scarb_metadata.workspace.members[0])
+                .unwrap_err();
+        assert!(format!("{err:?}").contains("Tag = Slow Tests is not valid"));
+    }
+
+    #[test]
+    fn get_forge_config_for_package_fails_on_invalid_test_tag() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [[tool.snforge.test_tag]]
+            test = "simple_package::integration"
+            tags = ["Slow Tests"]
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+
+        let err =
+            config_from_scarb_for_package(&scarb_metadata, &scarb_metadata.workspace.members[0])
+                .unwrap_err();
+        assert!(format!("{err:?}").contains("Tag = Slow Tests is not valid"));
+    }
 }
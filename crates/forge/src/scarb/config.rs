@@ -1,8 +1,15 @@
 use crate::compiled_raw::RawForkParams;
-use anyhow::{bail, Result};
+use crate::gas_snapshot::validate_tolerance;
+use anyhow::{anyhow, bail, Context, Result};
+use cairo_felt::Felt252;
+use camino::Utf8PathBuf;
+use forge_runner::expected_result::ExpectedPanicValue;
 use itertools::Itertools;
+use num_traits::Num;
+use regex::Regex;
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 #[allow(clippy::module_name_repetitions)]
 #[allow(clippy::struct_excessive_bools)]
@@ -24,6 +31,32 @@ pub struct ForgeConfig {
     pub fork: Vec<ForkTarget>,
     /// Limit of steps
     pub max_n_steps: Option<u32>,
+    /// Path of the JUnit XML report to be written
+    pub junit_report: Option<Utf8PathBuf>,
+    /// Collect code coverage, print its summary and save it as an LCOV report
+    pub coverage: bool,
+    /// Allowed change of gas usage (in percents) when checking the gas snapshot
+    pub gas_snapshot_tolerance: Option<f64>,
+    /// Display resources used by every contract function called in tests
+    pub gas_report: bool,
+    /// Fixed arguments of parametrized tests
+    pub test_case: Vec<ParametrizedCase>,
+    /// Full names of tests executing random sequences of calls to target contracts
+    pub invariant_tests: Vec<String>,
+    /// Full names of tests run before the tests of their modules, which start from the state they leave
+    pub setup_fixtures: Vec<String>,
+    /// Wall-clock time limit of a test run, in seconds
+    pub timeout: Option<u64>,
+    /// Time limits of specific tests, overriding `timeout`
+    pub test_timeout: Vec<TestTimeout>,
+    /// Expected panics of specific tests, overriding `#[should_panic]`
+    pub expected_panic: Vec<ExpectedPanic>,
+    /// Tags of specific tests and modules
+    pub test_tag: Vec<TestTag>,
+    /// Run only tests with any of these tags
+    pub tags: Vec<String>,
+    /// Do not run tests with any of these tags
+    pub exclude_tags: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -49,6 +82,152 @@ impl ForkTarget {
     }
 }
 
+/// Case of a parametrized test, run with fixed arguments.
+/// Defined in `[[tool.snforge.test_case]]` of Scarb.toml, as test attributes are collected by Scarb
+/// which does not support a `#[test_case]` attribute.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParametrizedCase {
+    test: String,
+    name: String,
+    arguments: Vec<Felt252>,
+}
+
+impl ParametrizedCase {
+    #[must_use]
+    pub fn new(test: String, name: String, arguments: Vec<Felt252>) -> Self {
+        Self {
+            test,
+            name,
+            arguments,
+        }
+    }
+
+    /// Full name of the test function
+    #[must_use]
+    pub fn test(&self) -> &str {
+        &self.test
+    }
+
+    /// Name of the case, appended to the test name in brackets
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Serialized values of all arguments of the test function
+    #[must_use]
+    pub fn arguments(&self) -> &[Felt252] {
+        &self.arguments
+    }
+
+    /// Whether the case belongs to the test with the given full name
+    #[must_use]
+    pub fn matches(&self, test_name: &str) -> bool {
+        test_name == self.test
+    }
+}
+
+/// Time limit of a test, overriding the global timeout
+#[derive(Debug, PartialEq, Clone)]
+pub struct TestTimeout {
+    test: String,
+    timeout: Duration,
+}
+
+impl TestTimeout {
+    #[must_use]
+    pub fn new(test: String, timeout: Duration) -> Self {
+        Self { test, timeout }
+    }
+
+    /// Full name of the test function
+    #[must_use]
+    pub fn test(&self) -> &str {
+        &self.test
+    }
+
+    #[must_use]
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Whether the timeout applies to the test with the given full name
+    #[must_use]
+    pub fn matches(&self, test_name: &str) -> bool {
+        test_name == self.test
+    }
+}
+
+/// Panic expected from a test, overriding its `#[should_panic]` attribute.
+/// Defined in `[[tool.snforge.expected_panic]]` of Scarb.toml, as `#[should_panic]` collected by Scarb
+/// only supports the `expected` argument.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ExpectedPanic {
+    test: String,
+    value: ExpectedPanicValue,
+}
+
+impl ExpectedPanic {
+    #[must_use]
+    pub fn new(test: String, value: ExpectedPanicValue) -> Self {
+        Self { test, value }
+    }
+
+    /// Full name of the test function
+    #[must_use]
+    pub fn test(&self) -> &str {
+        &self.test
+    }
+
+    #[must_use]
+    pub fn value(&self) -> &ExpectedPanicValue {
+        &self.value
+    }
+
+    /// Whether the expectation applies to the test with the given full name
+    #[must_use]
+    pub fn matches(&self, test_name: &str) -> bool {
+        test_name == self.test
+    }
+}
+
+/// Tags of a test, or of all tests in a module.
+/// Defined in `[[tool.snforge.test_tag]]` of Scarb.toml, as test attributes are collected by Scarb
+/// which does not support a `#[tags]` attribute.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TestTag {
+    test: String,
+    tags: Vec<String>,
+}
+
+impl TestTag {
+    #[must_use]
+    pub fn new(test: String, tags: Vec<String>) -> Self {
+        Self { test, tags }
+    }
+
+    /// Full name of the test function or of the module
+    #[must_use]
+    pub fn test(&self) -> &str {
+        &self.test
+    }
+
+    #[must_use]
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Whether the tags apply to the test with the given full name,
+    /// either directly or through one of the modules the test is in
+    #[must_use]
+    pub fn matches(&self, test_name: &str) -> bool {
+        test_name == self.test
+            || test_name
+                .strip_prefix(&self.test)
+                .is_some_and(|rest| rest.starts_with("::"))
+    }
+}
+
 /// Represents forge config deserialized from Scarb.toml using basic types like String etc.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Deserialize, Debug, PartialEq, Default)]
@@ -74,6 +253,42 @@ pub(crate) struct RawForgeConfig {
     pub fork: Vec<RawForkTarget>,
     /// Limit of steps
     pub max_n_steps: Option<u32>,
+    /// Path of the JUnit XML report to be written
+    pub junit_report: Option<Utf8PathBuf>,
+    #[serde(default)]
+    /// Collect code coverage, print its summary and save it as an LCOV report
+    pub coverage: bool,
+    /// Allowed change of gas usage (in percents) when checking the gas snapshot
+    pub gas_snapshot_tolerance: Option<f64>,
+    #[serde(default)]
+    /// Display resources used by every contract function called in tests
+    pub gas_report: bool,
+    #[serde(default)]
+    /// Fixed arguments of parametrized tests
+    pub test_case: Vec<RawParametrizedCase>,
+    #[serde(default)]
+    /// Full names of tests executing random sequences of calls to target contracts
+    pub invariant_tests: Vec<String>,
+    #[serde(default)]
+    /// Full names of tests run before the tests of their modules, which start from the state they leave
+    pub setup_fixtures: Vec<String>,
+    /// Wall-clock time limit of a test run, in seconds
+    pub timeout: Option<u64>,
+    #[serde(default)]
+    /// Time limits of specific tests, overriding `timeout`
+    pub test_timeout: Vec<RawTestTimeout>,
+    #[serde(default)]
+    /// Expected panics of specific tests, overriding `#[should_panic]`
+    pub expected_panic: Vec<RawExpectedPanic>,
+    #[serde(default)]
+    /// Tags of specific tests and modules
+    pub test_tag: Vec<RawTestTag>,
+    #[serde(default)]
+    /// Run only tests with any of these tags
+    pub tags: Vec<String>,
+    #[serde(default)]
+    /// Do not run tests with any of these tags
+    pub exclude_tags: Vec<String>,
 }
 
 #[derive(Deserialize, Debug, PartialEq, Default, Clone)]
@@ -83,6 +298,223 @@ pub(crate) struct RawForkTarget {
     pub block_id: HashMap<String, String>,
 }
 
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub(crate) struct RawParametrizedCase {
+    pub test: String,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub args: Vec<RawArgument>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub(crate) struct RawTestTimeout {
+    pub test: String,
+    /// Time limit in seconds
+    pub timeout: u64,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub(crate) struct RawTestTag {
+    /// Full name of the test or of the module
+    pub test: String,
+    pub tags: Vec<String>,
+}
+
+/// Exactly one of the ways of matching the panic has to be set
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+pub(crate) struct RawExpectedPanic {
+    pub test: String,
+    /// Exact `ByteArray` panic message
+    pub message: Option<String>,
+    /// Part of the `ByteArray` panic message
+    pub contains: Option<String>,
+    /// Regular expression matching the `ByteArray` panic message
+    pub pattern: Option<String>,
+    /// Felts the panic data starts with
+    pub prefix: Option<Vec<RawArgument>>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Clone)]
+#[serde(untagged)]
+pub(crate) enum RawArgument {
+    Number(i64),
+    Text(String),
+}
+
+impl TryFrom<&RawArgument> for Felt252 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &RawArgument) -> Result<Self, Self::Error> {
+        match value {
+            RawArgument::Number(number) => Ok(Felt252::from(*number)),
+            RawArgument::Text(text) => match text.strip_prefix("0x") {
+                Some(hex) => Felt252::from_str_radix(hex, 16),
+                None => Felt252::from_str_radix(text, 10),
+            }
+            .map_err(|_| anyhow!("Failed to parse test case argument = {text} as felt252")),
+        }
+    }
+}
+
+/// Resolves names of the cases, unnamed cases of a test are named `case_1`, `case_2`, ...
+fn parametrized_cases(raw_cases: Vec<RawParametrizedCase>) -> Result<Vec<ParametrizedCase>> {
+    let mut cases: Vec<ParametrizedCase> = vec![];
+    let mut unnamed_counts: HashMap<String, usize> = HashMap::new();
+
+    for raw_case in raw_cases {
+        let name = match raw_case.name {
+            Some(name) => {
+                if name.is_empty()
+                    || !name
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+                {
+                    bail!("Test case name = {name} is not valid. Names can only contain lowercase letters, digits and underscores");
+                }
+                name
+            }
+            None => {
+                let count = unnamed_counts.entry(raw_case.test.clone()).or_default();
+                *count += 1;
+                format!("case_{count}")
+            }
+        };
+
+        if cases
+            .iter()
+            .any(|case| case.test == raw_case.test && case.name == name)
+        {
+            bail!(
+                "Test case name = {name} is duplicated for test = {}",
+                raw_case.test
+            );
+        }
+
+        let arguments = raw_case
+            .args
+            .iter()
+            .map(Felt252::try_from)
+            .collect::<Result<_>>()?;
+
+        cases.push(ParametrizedCase::new(raw_case.test, name, arguments));
+    }
+
+    Ok(cases)
+}
+
+fn test_timeouts(raw_timeouts: Vec<RawTestTimeout>) -> Result<Vec<TestTimeout>> {
+    let mut timeouts: Vec<TestTimeout> = vec![];
+    for raw_timeout in raw_timeouts {
+        if raw_timeout.timeout == 0 {
+            bail!(
+                "Timeout of test = {} must be greater than 0",
+                raw_timeout.test
+            );
+        }
+        if timeouts
+            .iter()
+            .any(|timeout| timeout.test == raw_timeout.test)
+        {
+            bail!("Timeout of test = {} is duplicated", raw_timeout.test);
+        }
+        timeouts.push(TestTimeout::new(
+            raw_timeout.test,
+            Duration::from_secs(raw_timeout.timeout),
+        ));
+    }
+    Ok(timeouts)
+}
+
+fn expected_panics(raw_panics: Vec<RawExpectedPanic>) -> Result<Vec<ExpectedPanic>> {
+    let mut panics: Vec<ExpectedPanic> = vec![];
+    for raw_panic in raw_panics {
+        let value = match raw_panic {
+            RawExpectedPanic {
+                message: Some(ref message),
+                contains: None,
+                pattern: None,
+                prefix: None,
+                ..
+            } => ExpectedPanicValue::Message(message.clone()),
+            RawExpectedPanic {
+                message: None,
+                contains: Some(ref message),
+                pattern: None,
+                prefix: None,
+                ..
+            } => ExpectedPanicValue::MessageContaining(message.clone()),
+            RawExpectedPanic {
+                message: None,
+                contains: None,
+                pattern: Some(ref pattern),
+                prefix: None,
+                ..
+            } => ExpectedPanicValue::MessagePattern(Regex::new(pattern).with_context(|| {
+                format!(
+                    "Expected panic pattern = {pattern} of test = {} is not a valid regular expression",
+                    raw_panic.test
+                )
+            })?),
+            RawExpectedPanic {
+                message: None,
+                contains: None,
+                pattern: None,
+                prefix: Some(ref prefix),
+                ..
+            } => ExpectedPanicValue::Prefix(
+                prefix.iter().map(Felt252::try_from).collect::<Result<_>>()?,
+            ),
+            _ => bail!(
+                "Expected panic of test = {} must set exactly one of message, contains, pattern or prefix",
+                raw_panic.test
+            ),
+        };
+        if panics.iter().any(|panic| panic.test == raw_panic.test) {
+            bail!("Expected panic of test = {} is duplicated", raw_panic.test);
+        }
+        panics.push(ExpectedPanic::new(raw_panic.test, value));
+    }
+    Ok(panics)
+}
+
+fn test_tags(raw_tags: Vec<RawTestTag>) -> Result<Vec<TestTag>> {
+    raw_tags
+        .into_iter()
+        .map(|raw_tag| {
+            for tag in &raw_tag.tags {
+                validate_tag(tag)?;
+            }
+            Ok(TestTag::new(raw_tag.test, raw_tag.tags))
+        })
+        .collect()
+}
+
+/// Setup fixture is used by the tests of its module, so a module can have only one
+fn setup_fixtures(setup_fixtures: Vec<String>) -> Result<Vec<String>> {
+    let mut modules: HashMap<&str, &str> = HashMap::new();
+    for setup_fixture in &setup_fixtures {
+        let Some((module, _)) = setup_fixture.rsplit_once("::") else {
+            bail!("Setup fixture = {setup_fixture} has to be referred to by its full name");
+        };
+        if let Some(other) = modules.insert(module, setup_fixture) {
+            bail!("Setup fixtures = {other} and {setup_fixture} are in the same module, a module can have only one setup fixture");
+        }
+    }
+    Ok(setup_fixtures)
+}
+
+/// Tags are used on the command line, so they are restricted to a simple set of characters
+pub fn validate_tag(tag: &str) -> Result<()> {
+    if tag.is_empty()
+        || !tag
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+    {
+        bail!("Tag = {tag} is not valid. Tags can only contain lowercase letters, digits, underscores and hyphens");
+    }
+    Ok(())
+}
+
 fn validate_raw_fork_config(raw_config: RawForgeConfig) -> Result<RawForgeConfig> {
     let forks = &raw_config.fork;
 
@@ -117,6 +549,9 @@ impl TryFrom<RawForgeConfig> for ForgeConfig {
 
     fn try_from(value: RawForgeConfig) -> Result<Self, Self::Error> {
         let value = validate_raw_fork_config(value)?;
+        for tag in value.tags.iter().chain(&value.exclude_tags) {
+            validate_tag(tag)?;
+        }
         let mut fork_targets = vec![];
 
         for raw_fork_target in value.fork {
@@ -142,6 +577,25 @@ impl TryFrom<RawForgeConfig> for ForgeConfig {
             build_profile: value.build_profile,
             fork: fork_targets,
             max_n_steps: value.max_n_steps,
+            junit_report: value.junit_report,
+            coverage: value.coverage,
+            gas_snapshot_tolerance: value
+                .gas_snapshot_tolerance
+                .map(validate_tolerance)
+                .transpose()?,
+            gas_report: value.gas_report,
+            test_case: parametrized_cases(value.test_case)?,
+            invariant_tests: value.invariant_tests,
+            setup_fixtures: setup_fixtures(value.setup_fixtures)?,
+            timeout: match value.timeout {
+                Some(0) => bail!("Timeout must be greater than 0"),
+                timeout => timeout,
+            },
+            test_timeout: test_timeouts(value.test_timeout)?,
+            expected_panic: expected_panics(value.expected_panic)?,
+            test_tag: test_tags(value.test_tag)?,
+            tags: value.tags,
+            exclude_tags: value.exclude_tags,
         })
     }
 }
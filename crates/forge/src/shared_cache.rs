@@ -3,8 +3,10 @@ pub const PREV_TESTS_FAILED: &str = ".prev_tests_failed";
 
 use anyhow::{Ok, Result};
 use camino::Utf8PathBuf;
+use forge_runner::fuzz_corpus::{FuzzCorpus, FuzzCorpusEntry, FUZZ_CORPUS_DIR};
 use forge_runner::test_case_summary::AnyTestCaseSummary;
 use scarb_api::{metadata::MetadataCommandExt, ScarbCommand};
+use std::collections::BTreeMap;
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader, BufWriter, Write};
 
@@ -55,3 +57,38 @@ pub fn clean_cache() -> Result<()> {
     }
     Ok(())
 }
+
+/// Failing fuzz inputs stored for every package of the workspace, keyed by the package name
+pub fn fuzz_corpus_entries() -> Result<BTreeMap<String, Vec<FuzzCorpusEntry>>> {
+    let scarb_metadata = ScarbCommand::metadata().inherit_stderr().run()?;
+    let corpus_dir = scarb_metadata
+        .workspace
+        .root
+        .join(CACHE_DIR)
+        .join(FUZZ_CORPUS_DIR);
+
+    let mut entries = BTreeMap::new();
+    if !corpus_dir.exists() {
+        return Ok(entries);
+    }
+    for file in corpus_dir.read_dir_utf8()? {
+        let path = file?.into_path();
+        if let (Some(package), Some("json")) = (path.file_stem(), path.extension()) {
+            entries.insert(package.to_string(), FuzzCorpus::load(&path)?.entries());
+        }
+    }
+    Ok(entries)
+}
+
+pub fn clear_fuzz_corpus() -> Result<()> {
+    let scarb_metadata = ScarbCommand::metadata().inherit_stderr().run()?;
+    let corpus_dir = scarb_metadata
+        .workspace
+        .root
+        .join(CACHE_DIR)
+        .join(FUZZ_CORPUS_DIR);
+    if corpus_dir.exists() {
+        fs::remove_dir_all(corpus_dir)?;
+    }
+    Ok(())
+}
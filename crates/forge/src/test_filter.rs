@@ -1,9 +1,15 @@
-use crate::compiled_raw::CompiledTestCrateRaw;
+use crate::compiled_raw::{CompiledTestCrateRaw, TestCaseRaw};
+use crate::scarb::config::TestTag;
 use crate::shared_cache::cached_failed_tests_names;
-use anyhow::Result;
+use anyhow::{anyhow, bail, Context, Result};
 use camino::Utf8PathBuf;
 use forge_runner::compiled_runnable::TestCaseRunnable;
+use forge_runner::fixtures::runs_before;
 use forge_runner::TestCaseFilter;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, PartialEq)]
 // Specifies what tests should be included
@@ -14,6 +20,12 @@ pub struct TestsFilter {
     ignored_filter: IgnoredFilter,
     // based on rerun_failed flag
     last_failed_filter: bool,
+    // based on `--shard` argument
+    shard: Option<Shard>,
+    // based on `--skip` arguments
+    skip: Vec<String>,
+    // based on tags of the tests
+    tag_filter: TagFilter,
 
     cache_dir_path: Utf8PathBuf,
 }
@@ -23,6 +35,124 @@ pub(crate) enum NameFilter {
     All,
     Match(String),
     ExactMatch(String),
+    RegexMatch(String),
+}
+
+/// Tag implicitly assigned to all tests using a fork
+pub const FORK_TAG: &str = "fork";
+
+/// Selects tests by their tags, assigned in `[[tool.snforge.test_tag]]` of Scarb.toml
+#[derive(Debug, PartialEq, Default)]
+pub struct TagFilter {
+    /// Only tests with any of these tags are selected, all tests if empty
+    include: Vec<String>,
+    /// Tests with any of these tags are never selected
+    exclude: Vec<String>,
+    test_tags: Vec<TestTag>,
+}
+
+impl TagFilter {
+    #[must_use]
+    pub fn new(include: Vec<String>, exclude: Vec<String>, test_tags: Vec<TestTag>) -> Self {
+        Self {
+            include,
+            exclude,
+            test_tags,
+        }
+    }
+
+    fn tags_of<'a>(&'a self, test_case: &TestCaseRaw) -> Vec<&'a str> {
+        let fork_tag = test_case.fork_config.as_ref().map(|_| FORK_TAG);
+        self.test_tags
+            .iter()
+            .filter(|test_tag| test_tag.matches(&test_case.name))
+            .flat_map(|test_tag| test_tag.tags().iter().map(String::as_str))
+            .chain(fork_tag)
+            .collect()
+    }
+
+    fn matches(&self, test_case: &TestCaseRaw) -> bool {
+        if self.include.is_empty() && self.exclude.is_empty() {
+            return true;
+        }
+        let tags = self.tags_of(test_case);
+        let has_any = |selected: &[String]| selected.iter().any(|tag| tags.contains(&tag.as_str()));
+
+        (self.include.is_empty() || has_any(&self.include)) && !has_any(&self.exclude)
+    }
+}
+
+/// Part of the tests selected by `--shard <index>/<total>`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Shard {
+    /// Index of the shard, starting from 1
+    index: u64,
+    total: u64,
+}
+
+impl Shard {
+    /// Tests are assigned to shards by a hash of their full names, which is stable between runs and machines
+    #[must_use]
+    pub fn contains(&self, test_name: &str) -> bool {
+        stable_hash(test_name) % self.total == self.index - 1
+    }
+}
+
+impl FromStr for Shard {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let (index, total) = value
+            .split_once('/')
+            .ok_or_else(|| anyhow!("Shard has to be in the <index>/<total> format"))?;
+        let index: u64 = index
+            .parse()
+            .map_err(|_| anyhow!("Failed to parse shard index = {index} as u64"))?;
+        let total: u64 = total
+            .parse()
+            .map_err(|_| anyhow!("Failed to parse total number of shards = {total} as u64"))?;
+
+        if index == 0 || index > total {
+            bail!("Shard index has to be between 1 and the total number of shards = {total}");
+        }
+
+        Ok(Self { index, total })
+    }
+}
+
+impl fmt::Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.total)
+    }
+}
+
+/// 64-bit FNV-1a hash
+fn stable_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Flags selecting the tests to run, all tests except the ignored ones are selected by default
+#[derive(Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct TestsFilterFlags {
+    /// Name used to filter tests
+    pub test_name_filter: Option<String>,
+    /// Use exact matches for `test_name_filter`
+    pub exact_match: bool,
+    /// Treat `test_name_filter` as a regular expression
+    pub regex_match: bool,
+    /// Select only tests marked with `#[ignore]`
+    pub only_ignored: bool,
+    /// Select tests regardless of `#[ignore]`
+    pub include_ignored: bool,
+    /// Select only tests that failed during the last run
+    pub rerun_failed: bool,
+    pub shard: Option<Shard>,
+    /// Skip tests whose names contain any of these patterns
+    pub skip: Vec<String>,
+    pub tag_filter: TagFilter,
 }
 
 #[derive(Debug, PartialEq)]
@@ -34,19 +164,26 @@ pub(crate) enum IgnoredFilter {
 
 impl TestsFilter {
     #[must_use]
-    #[allow(clippy::fn_params_excessive_bools)]
-    pub fn from_flags(
-        test_name_filter: Option<String>,
-        exact_match: bool,
-        only_ignored: bool,
-        include_ignored: bool,
-        rerun_failed: bool,
-        cache_dir_path: Utf8PathBuf,
-    ) -> Self {
+    pub fn from_flags(flags: TestsFilterFlags, cache_dir_path: Utf8PathBuf) -> Self {
+        let TestsFilterFlags {
+            test_name_filter,
+            exact_match,
+            regex_match,
+            only_ignored,
+            include_ignored,
+            rerun_failed,
+            shard,
+            skip,
+            tag_filter,
+        } = flags;
         assert!(
             !(only_ignored && include_ignored),
             "Arguments only_ignored and include_ignored cannot be both true"
         );
+        assert!(
+            !(exact_match && regex_match),
+            "Arguments exact_match and regex_match cannot be both true"
+        );
 
         let ignored_filter = if include_ignored {
             IgnoredFilter::All
@@ -61,6 +198,11 @@ impl TestsFilter {
                 test_name_filter
                     .expect("Argument test_name_filter cannot be None with exact_match"),
             )
+        } else if regex_match {
+            NameFilter::RegexMatch(
+                test_name_filter
+                    .expect("Argument test_name_filter cannot be None with regex_match"),
+            )
         } else if let Some(name) = test_name_filter {
             NameFilter::Match(name)
         } else {
@@ -71,15 +213,35 @@ impl TestsFilter {
             name_filter,
             ignored_filter,
             last_failed_filter: rerun_failed,
+            shard,
+            skip,
+            tag_filter,
             cache_dir_path,
         }
     }
 
+    #[must_use]
+    pub fn shard(&self) -> Option<Shard> {
+        self.shard
+    }
+
+    /// Tags assigned to tests and modules in Scarb.toml
+    pub(crate) fn test_tags(&self) -> &[TestTag] {
+        &self.tag_filter.test_tags
+    }
+
+    /// Setup fixtures are never selected by themselves, they are kept if any of the selected tests uses them
     pub(crate) fn filter_tests(
         &self,
         test_crate: CompiledTestCrateRaw,
+        setup_fixtures: &[String],
     ) -> Result<CompiledTestCrateRaw> {
-        let mut cases = test_crate.test_cases;
+        let all_cases = test_crate.test_cases;
+        let mut cases: Vec<TestCaseRaw> = all_cases
+            .iter()
+            .filter(|tc| !setup_fixtures.contains(&tc.name))
+            .cloned()
+            .collect();
 
         cases = match &self.name_filter {
             NameFilter::All => cases,
@@ -90,13 +252,33 @@ impl TestsFilter {
             NameFilter::ExactMatch(name) => {
                 cases.into_iter().filter(|tc| tc.name == *name).collect()
             }
+            NameFilter::RegexMatch(pattern) => {
+                let regex = Regex::new(pattern).with_context(|| {
+                    format!("Failed to parse test filter = {pattern} as a regular expression")
+                })?;
+                cases
+                    .into_iter()
+                    .filter(|tc| regex.is_match(&tc.name))
+                    .collect()
+            }
         };
 
+        cases.retain(|tc| !self.skip.iter().any(|skipped| tc.name.contains(skipped)));
+        cases.retain(|tc| self.tag_filter.matches(tc));
+
         if self.last_failed_filter {
             cases = match cached_failed_tests_names(&self.cache_dir_path)? {
                 Some(result) => cases
                     .into_iter()
-                    .filter(|tc| result.iter().any(|name| name == &tc.name))
+                    .filter(|tc| {
+                        // Cases of parametrized tests are named `{test name}[{case name}]`
+                        result.iter().any(|name| {
+                            name == &tc.name
+                                || name
+                                    .strip_prefix(&tc.name)
+                                    .is_some_and(|case| case.starts_with('['))
+                        })
+                    })
                     .collect(),
                 None => cases,
             }
@@ -108,6 +290,25 @@ impl TestsFilter {
             IgnoredFilter::Ignored => cases.into_iter().filter(|tc| tc.ignored).collect(),
         };
 
+        if let Some(shard) = self.shard {
+            cases = cases
+                .into_iter()
+                .filter(|tc| shard.contains(&tc.name))
+                .collect();
+        }
+
+        // Setup fixtures are run along with the tests starting from their state,
+        // so every shard runs the fixtures its tests use
+        let selected: HashSet<String> = cases.into_iter().map(|tc| tc.name).collect();
+        cases = all_cases
+            .into_iter()
+            .filter(|tc| {
+                selected.contains(&tc.name)
+                    || (setup_fixtures.contains(&tc.name)
+                        && selected.iter().any(|name| runs_before(&tc.name, name)))
+            })
+            .collect();
+
         Ok(CompiledTestCrateRaw {
             test_cases: cases,
             ..test_crate
@@ -119,7 +320,8 @@ impl TestCaseFilter for TestsFilter {
     fn should_be_run(&self, test_case: &TestCaseRunnable) -> bool {
         match self.ignored_filter {
             IgnoredFilter::All => true,
-            IgnoredFilter::Ignored => test_case.ignored,
+            // Setup fixtures are needed by the ignored tests as well
+            IgnoredFilter::Ignored => test_case.ignored || test_case.is_setup_fixture,
             IgnoredFilter::NotIgnored => !test_case.ignored,
         }
     }
@@ -127,10 +329,11 @@ impl TestCaseFilter for TestsFilter {
 
 #[cfg(test)]
 mod tests {
-    use crate::compiled_raw::{CompiledTestCrateRaw, CrateLocation, TestCaseRaw};
-    use crate::test_filter::TestsFilter;
+    use crate::compiled_raw::{CompiledTestCrateRaw, RawForkConfig, TestCaseRaw};
+    use crate::scarb::config::TestTag;
+    use crate::test_filter::{Shard, TagFilter, TestsFilter, TestsFilterFlags};
     use cairo_lang_sierra::program::Program;
-    use forge_runner::compiled_runnable::TestDetails;
+    use forge_runner::compiled_runnable::{CrateLocation, TestDetails};
     use forge_runner::expected_result::ExpectedTestResult;
 
     fn program_for_testing() -> Program {
@@ -145,13 +348,26 @@ mod tests {
     #[test]
     #[should_panic(expected = "Arguments only_ignored and include_ignored cannot be both true")]
     fn from_flags_only_ignored_and_include_ignored_both_true() {
-        let _ = TestsFilter::from_flags(None, false, true, true, false, Default::default());
+        let _ = TestsFilter::from_flags(
+            TestsFilterFlags {
+                only_ignored: true,
+                include_ignored: true,
+                ..Default::default()
+            },
+            Default::default(),
+        );
     }
 
     #[test]
     #[should_panic(expected = "Argument test_name_filter cannot be None with exact_match")]
     fn from_flags_exact_match_true_without_test_filter_name() {
-        let _ = TestsFilter::from_flags(None, true, false, false, false, Default::default());
+        let _ = TestsFilter::from_flags(
+            TestsFilterFlags {
+                exact_match: true,
+                ..Default::default()
+            },
+            Default::default(),
+        );
     }
 
     #[test]
@@ -201,14 +417,15 @@ mod tests {
         };
 
         let tests_filter = TestsFilter::from_flags(
-            Some("do".to_string()),
-            false,
-            false,
-            false,
-            false,
+            TestsFilterFlags {
+                test_name_filter: Some("do".to_string()),
+                ..Default::default()
+            },
             Default::default(),
         );
-        let filtered = tests_filter.filter_tests(mocked_tests.clone()).unwrap();
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
         assert_eq!(
             filtered.test_cases,
             vec![TestCaseRaw {
@@ -223,14 +440,15 @@ mod tests {
         );
 
         let tests_filter = TestsFilter::from_flags(
-            Some("te2::run".to_string()),
-            false,
-            false,
-            false,
-            false,
+            TestsFilterFlags {
+                test_name_filter: Some("te2::run".to_string()),
+                ..Default::default()
+            },
             Default::default(),
         );
-        let filtered = tests_filter.filter_tests(mocked_tests.clone()).unwrap();
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
         assert_eq!(
             filtered.test_cases,
             vec![TestCaseRaw {
@@ -245,14 +463,15 @@ mod tests {
         );
 
         let tests_filter = TestsFilter::from_flags(
-            Some("thing".to_string()),
-            false,
-            false,
-            false,
-            false,
+            TestsFilterFlags {
+                test_name_filter: Some("thing".to_string()),
+                ..Default::default()
+            },
             Default::default(),
         );
-        let filtered = tests_filter.filter_tests(mocked_tests.clone()).unwrap();
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
         assert_eq!(
             filtered.test_cases,
             vec![
@@ -296,25 +515,27 @@ mod tests {
         );
 
         let tests_filter = TestsFilter::from_flags(
-            Some("nonexistent".to_string()),
-            false,
-            false,
-            false,
-            false,
+            TestsFilterFlags {
+                test_name_filter: Some("nonexistent".to_string()),
+                ..Default::default()
+            },
             Default::default(),
         );
-        let filtered = tests_filter.filter_tests(mocked_tests.clone()).unwrap();
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
         assert_eq!(filtered.test_cases, vec![]);
 
         let tests_filter = TestsFilter::from_flags(
-            Some(String::new()),
-            false,
-            false,
-            false,
-            false,
+            TestsFilterFlags {
+                test_name_filter: Some(String::new()),
+                ..Default::default()
+            },
             Default::default(),
         );
-        let filtered = tests_filter.filter_tests(mocked_tests.clone()).unwrap();
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
         assert_eq!(
             filtered.test_cases,
             vec![
@@ -367,25 +588,27 @@ mod tests {
         };
 
         let tests_filter = TestsFilter::from_flags(
-            Some(String::new()),
-            false,
-            false,
-            false,
-            false,
+            TestsFilterFlags {
+                test_name_filter: Some(String::new()),
+                ..Default::default()
+            },
             Default::default(),
         );
-        let filtered = tests_filter.filter_tests(mocked_tests.clone()).unwrap();
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
         assert_eq!(filtered.test_cases, vec![]);
 
         let tests_filter = TestsFilter::from_flags(
-            Some("thing".to_string()),
-            false,
-            false,
-            false,
-            false,
+            TestsFilterFlags {
+                test_name_filter: Some("thing".to_string()),
+                ..Default::default()
+            },
             Default::default(),
         );
-        let filtered = tests_filter.filter_tests(mocked_tests.clone()).unwrap();
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
         assert_eq!(filtered.test_cases, vec![]);
     }
 
@@ -436,36 +659,42 @@ mod tests {
         };
 
         let tests_filter = TestsFilter::from_flags(
-            Some(String::new()),
-            true,
-            false,
-            false,
-            false,
+            TestsFilterFlags {
+                test_name_filter: Some(String::new()),
+                exact_match: true,
+                ..Default::default()
+            },
             Default::default(),
         );
-        let filtered = tests_filter.filter_tests(mocked_tests.clone()).unwrap();
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
         assert_eq!(filtered.test_cases, vec![]);
 
         let tests_filter = TestsFilter::from_flags(
-            Some("thing".to_string()),
-            true,
-            false,
-            false,
-            false,
+            TestsFilterFlags {
+                test_name_filter: Some("thing".to_string()),
+                exact_match: true,
+                ..Default::default()
+            },
             Default::default(),
         );
-        let filtered = tests_filter.filter_tests(mocked_tests.clone()).unwrap();
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
         assert_eq!(filtered.test_cases, vec![]);
 
         let tests_filter = TestsFilter::from_flags(
-            Some("do_thing".to_string()),
-            true,
-            false,
-            false,
-            false,
+            TestsFilterFlags {
+                test_name_filter: Some("do_thing".to_string()),
+                exact_match: true,
+                ..Default::default()
+            },
             Default::default(),
         );
-        let filtered = tests_filter.filter_tests(mocked_tests.clone()).unwrap();
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
         assert_eq!(
             filtered.test_cases,
             vec![TestCaseRaw {
@@ -480,14 +709,16 @@ mod tests {
         );
 
         let tests_filter = TestsFilter::from_flags(
-            Some("crate1::do_thing".to_string()),
-            true,
-            false,
-            false,
-            false,
+            TestsFilterFlags {
+                test_name_filter: Some("crate1::do_thing".to_string()),
+                exact_match: true,
+                ..Default::default()
+            },
             Default::default(),
         );
-        let filtered = tests_filter.filter_tests(mocked_tests.clone()).unwrap();
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
         assert_eq!(
             filtered.test_cases,
             vec![TestCaseRaw {
@@ -502,25 +733,29 @@ mod tests {
         );
 
         let tests_filter = TestsFilter::from_flags(
-            Some("crate3::run_other_thing".to_string()),
-            true,
-            false,
-            false,
-            false,
+            TestsFilterFlags {
+                test_name_filter: Some("crate3::run_other_thing".to_string()),
+                exact_match: true,
+                ..Default::default()
+            },
             Default::default(),
         );
-        let filtered = tests_filter.filter_tests(mocked_tests.clone()).unwrap();
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
         assert_eq!(filtered.test_cases, vec![]);
 
         let tests_filter = TestsFilter::from_flags(
-            Some("outer::crate3::run_other_thing".to_string()),
-            true,
-            false,
-            false,
-            false,
+            TestsFilterFlags {
+                test_name_filter: Some("outer::crate3::run_other_thing".to_string()),
+                exact_match: true,
+                ..Default::default()
+            },
             Default::default(),
         );
-        let filtered = tests_filter.filter_tests(mocked_tests.clone()).unwrap();
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
         assert_eq!(
             filtered.test_cases,
             vec![TestCaseRaw {
@@ -580,9 +815,14 @@ mod tests {
             tests_location: CrateLocation::Tests,
         };
 
-        let tests_filter =
-            TestsFilter::from_flags(None, false, true, false, false, Default::default());
-        let filtered = tests_filter.filter_tests(mocked_tests).unwrap();
+        let tests_filter = TestsFilter::from_flags(
+            TestsFilterFlags {
+                only_ignored: true,
+                ..Default::default()
+            },
+            Default::default(),
+        );
+        let filtered = tests_filter.filter_tests(mocked_tests, &[]).unwrap();
         assert_eq!(
             filtered.test_cases,
             vec![
@@ -653,9 +893,14 @@ mod tests {
             tests_location: CrateLocation::Tests,
         };
 
-        let tests_filter =
-            TestsFilter::from_flags(None, false, false, true, false, Default::default());
-        let filtered = tests_filter.filter_tests(mocked_tests).unwrap();
+        let tests_filter = TestsFilter::from_flags(
+            TestsFilterFlags {
+                include_ignored: true,
+                ..Default::default()
+            },
+            Default::default(),
+        );
+        let filtered = tests_filter.filter_tests(mocked_tests, &[]).unwrap();
         assert_eq!(
             filtered.test_cases,
             vec![
@@ -698,4 +943,268 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn filtering_keeps_setup_fixtures_of_selected_tests() {
+        let test_case = |name: &str| TestCaseRaw {
+            name: name.to_string(),
+            available_gas: None,
+            ignored: false,
+            expected_result: ExpectedTestResult::Success,
+            fork_config: None,
+            fuzzer_config: None,
+            test_details: TestDetails::default(),
+        };
+        let mocked_tests = CompiledTestCrateRaw {
+            sierra_program: program_for_testing(),
+            test_cases: vec![
+                test_case("crate1::setup"),
+                test_case("crate1::erc20::setup"),
+                test_case("crate1::erc20::transfer"),
+                test_case("crate1::erc721::setup"),
+                test_case("crate1::erc721::transfer"),
+            ],
+            tests_location: CrateLocation::Lib,
+        };
+        let setup_fixtures = vec![
+            "crate1::setup".to_string(),
+            "crate1::erc20::setup".to_string(),
+            "crate1::erc721::setup".to_string(),
+        ];
+
+        let tests_filter = TestsFilter::from_flags(
+            TestsFilterFlags {
+                test_name_filter: Some("erc20::transfer".to_string()),
+                ..Default::default()
+            },
+            Default::default(),
+        );
+        let filtered = tests_filter
+            .filter_tests(mocked_tests, &setup_fixtures)
+            .unwrap();
+
+        assert_eq!(
+            filtered.test_cases,
+            vec![
+                test_case("crate1::setup"),
+                test_case("crate1::erc20::setup"),
+                test_case("crate1::erc20::transfer"),
+            ]
+        );
+    }
+
+    #[test]
+    fn filtering_does_not_select_setup_fixtures_by_themselves() {
+        let test_case = |name: &str| TestCaseRaw {
+            name: name.to_string(),
+            available_gas: None,
+            ignored: false,
+            expected_result: ExpectedTestResult::Success,
+            fork_config: None,
+            fuzzer_config: None,
+            test_details: TestDetails::default(),
+        };
+        let mocked_tests = CompiledTestCrateRaw {
+            sierra_program: program_for_testing(),
+            test_cases: vec![
+                test_case("crate1::erc20::setup"),
+                test_case("crate1::erc20::transfer"),
+                test_case("crate1::erc721::setup"),
+            ],
+            tests_location: CrateLocation::Lib,
+        };
+        let setup_fixtures = vec!["crate1::erc20::setup".to_string()];
+
+        let tests_filter = TestsFilter::from_flags(
+            TestsFilterFlags {
+                test_name_filter: Some("setup".to_string()),
+                ..Default::default()
+            },
+            Default::default(),
+        );
+        let filtered = tests_filter
+            .filter_tests(mocked_tests, &setup_fixtures)
+            .unwrap();
+
+        // Tests named `setup` are regular tests unless listed in `setup_fixtures`
+        assert_eq!(
+            filtered.test_cases,
+            vec![test_case("crate1::erc721::setup")]
+        );
+    }
+
+    #[test]
+    fn parsing_shard() {
+        assert_eq!(
+            "2/3".parse::<Shard>().unwrap(),
+            Shard { index: 2, total: 3 }
+        );
+        assert_eq!("2/3".parse::<Shard>().unwrap().to_string(), "2/3");
+
+        for invalid in ["0/3", "4/3", "1", "a/3", "1/-3"] {
+            assert!(invalid.parse::<Shard>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn shards_run_every_test_exactly_once() {
+        let test_case = |name: String| TestCaseRaw {
+            name,
+            available_gas: None,
+            ignored: false,
+            expected_result: ExpectedTestResult::Success,
+            fork_config: None,
+            fuzzer_config: None,
+            test_details: TestDetails::default(),
+        };
+        let names: Vec<String> = (0..100).map(|i| format!("crate1::test_{i}")).collect();
+
+        let mut sharded_names = vec![];
+        for index in 1..=3 {
+            let mocked_tests = CompiledTestCrateRaw {
+                sierra_program: program_for_testing(),
+                test_cases: names.iter().cloned().map(test_case).collect(),
+                tests_location: CrateLocation::Lib,
+            };
+            let tests_filter = TestsFilter::from_flags(
+                TestsFilterFlags {
+                    shard: Some(Shard { index, total: 3 }),
+                    ..Default::default()
+                },
+                Default::default(),
+            );
+            let filtered = tests_filter.filter_tests(mocked_tests, &[]).unwrap();
+
+            assert!(!filtered.test_cases.is_empty());
+            sharded_names.extend(filtered.test_cases.into_iter().map(|tc| tc.name));
+        }
+        sharded_names.sort_by_key(|name| names.iter().position(|n| n == name));
+
+        assert_eq!(sharded_names, names);
+    }
+
+    #[test]
+    fn filtering_with_regex_and_skip() {
+        let test_case = |name: &str| TestCaseRaw {
+            name: name.to_string(),
+            available_gas: None,
+            ignored: false,
+            expected_result: ExpectedTestResult::Success,
+            fork_config: None,
+            fuzzer_config: None,
+            test_details: TestDetails::default(),
+        };
+        let mocked_tests = CompiledTestCrateRaw {
+            sierra_program: program_for_testing(),
+            test_cases: vec![
+                test_case("crate1::erc20::test_transfer"),
+                test_case("crate1::erc20::test_transfer_from"),
+                test_case("crate1::erc721::test_transfer"),
+                test_case("crate1::erc721::test_mint"),
+            ],
+            tests_location: CrateLocation::Lib,
+        };
+
+        let tests_filter = TestsFilter::from_flags(
+            TestsFilterFlags {
+                test_name_filter: Some(r"erc\d+::test_transfer$".to_string()),
+                regex_match: true,
+                skip: vec!["erc721".to_string()],
+                ..Default::default()
+            },
+            Default::default(),
+        );
+        let filtered = tests_filter
+            .filter_tests(mocked_tests.clone(), &[])
+            .unwrap();
+        assert_eq!(
+            filtered.test_cases,
+            vec![test_case("crate1::erc20::test_transfer")]
+        );
+
+        let tests_filter = TestsFilter::from_flags(
+            TestsFilterFlags {
+                test_name_filter: Some("(".to_string()),
+                regex_match: true,
+                ..Default::default()
+            },
+            Default::default(),
+        );
+        let err = tests_filter.filter_tests(mocked_tests, &[]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to parse test filter = ( as a regular expression"
+        );
+    }
+
+    #[test]
+    fn filtering_by_tags() {
+        let test_case = |name: &str, forked: bool| TestCaseRaw {
+            name: name.to_string(),
+            available_gas: None,
+            ignored: false,
+            expected_result: ExpectedTestResult::Success,
+            fork_config: forked.then(|| RawForkConfig::Id("mainnet".to_string())),
+            fuzzer_config: None,
+            test_details: TestDetails::default(),
+        };
+        let mocked_tests = CompiledTestCrateRaw {
+            sierra_program: program_for_testing(),
+            test_cases: vec![
+                test_case("crate1::unit::test_add", false),
+                test_case("crate1::integration::test_deposit", false),
+                test_case("crate1::integration::test_withdraw", false),
+                test_case("crate1::integration::test_mainnet_balance", true),
+            ],
+            tests_location: CrateLocation::Lib,
+        };
+        let test_tags = vec![
+            TestTag::new(
+                "crate1::integration".to_string(),
+                vec!["integration".to_string()],
+            ),
+            TestTag::new(
+                "crate1::integration::test_withdraw".to_string(),
+                vec!["slow".to_string()],
+            ),
+            // Modules and tests are matched by their full names only
+            TestTag::new("test_add".to_string(), vec!["slow".to_string()]),
+        ];
+        let filter_tags = |include: &[&str], exclude: &[&str]| {
+            let tests_filter = TestsFilter::from_flags(
+                TestsFilterFlags {
+                    tag_filter: TagFilter::new(
+                        include.iter().map(ToString::to_string).collect(),
+                        exclude.iter().map(ToString::to_string).collect(),
+                        test_tags.clone(),
+                    ),
+                    ..Default::default()
+                },
+                Default::default(),
+            );
+            tests_filter
+                .filter_tests(mocked_tests.clone(), &[])
+                .unwrap()
+                .test_cases
+                .into_iter()
+                .map(|tc| tc.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            filter_tags(&["integration"], &["slow", "fork"]),
+            vec!["crate1::integration::test_deposit"]
+        );
+        assert_eq!(
+            filter_tags(&["fork", "slow"], &[]),
+            vec![
+                "crate1::integration::test_withdraw",
+                "crate1::integration::test_mainnet_balance"
+            ]
+        );
+        assert_eq!(
+            filter_tags(&[], &["integration"]),
+            vec!["crate1::unit::test_add"]
+        );
+    }
 }
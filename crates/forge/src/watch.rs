@@ -0,0 +1,115 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use std::collections::BTreeMap;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use walkdir::{DirEntry, WalkDir};
+
+/// How often the sources are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Watches Cairo sources and `Scarb.toml` manifests of the workspace for changes
+#[derive(Debug)]
+pub struct SourcesWatcher {
+    root: Utf8PathBuf,
+    sources: BTreeMap<Utf8PathBuf, SystemTime>,
+}
+
+impl SourcesWatcher {
+    #[must_use]
+    pub fn new(root: &Utf8Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            sources: watched_sources(root),
+        }
+    }
+
+    /// Blocks until any of the sources is created, modified or removed.
+    /// Changes made while waiting for the next poll are reported together.
+    pub fn wait_for_changes(&mut self) -> Vec<Utf8PathBuf> {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let sources = watched_sources(&self.root);
+            let changed = changed_sources(&self.sources, &sources);
+            self.sources = sources;
+
+            if !changed.is_empty() {
+                return changed;
+            }
+        }
+    }
+}
+
+fn watched_sources(root: &Utf8Path) -> BTreeMap<Utf8PathBuf, SystemTime> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !is_ignored_dir(entry))
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            let path = Utf8PathBuf::from_path_buf(entry.into_path()).ok()?;
+            is_watched(&path).then_some((path, modified))
+        })
+        .collect()
+}
+
+/// Build outputs and hidden directories (e.g. `.snfoundry_cache`, `.git`) are not watched
+fn is_ignored_dir(entry: &DirEntry) -> bool {
+    let name = entry.file_name().to_string_lossy();
+    entry.file_type().is_dir() && (name == "target" || name.starts_with('.'))
+}
+
+fn is_watched(path: &Utf8Path) -> bool {
+    path.extension() == Some("cairo") || path.file_name() == Some("Scarb.toml")
+}
+
+fn changed_sources(
+    previous: &BTreeMap<Utf8PathBuf, SystemTime>,
+    current: &BTreeMap<Utf8PathBuf, SystemTime>,
+) -> Vec<Utf8PathBuf> {
+    let removed = previous.keys().filter(|path| !current.contains_key(*path));
+    let created_or_modified = current
+        .iter()
+        .filter(|(path, modified)| previous.get(*path) != Some(*modified))
+        .map(|(path, _)| path);
+
+    removed.chain(created_or_modified).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_created_modified_and_removed_sources() {
+        let time = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let previous = BTreeMap::from([
+            (Utf8PathBuf::from("src/lib.cairo"), time(1)),
+            (Utf8PathBuf::from("src/removed.cairo"), time(1)),
+            (Utf8PathBuf::from("Scarb.toml"), time(1)),
+        ]);
+        let current = BTreeMap::from([
+            (Utf8PathBuf::from("src/lib.cairo"), time(2)),
+            (Utf8PathBuf::from("src/created.cairo"), time(2)),
+            (Utf8PathBuf::from("Scarb.toml"), time(1)),
+        ]);
+
+        assert_eq!(
+            changed_sources(&previous, &current),
+            vec![
+                Utf8PathBuf::from("src/removed.cairo"),
+                Utf8PathBuf::from("src/created.cairo"),
+                Utf8PathBuf::from("src/lib.cairo"),
+            ]
+        );
+    }
+
+    #[test]
+    fn watches_cairo_sources_and_manifests() {
+        assert!(is_watched(Utf8Path::new("pkg/src/lib.cairo")));
+        assert!(is_watched(Utf8Path::new("pkg/Scarb.toml")));
+        assert!(!is_watched(Utf8Path::new("pkg/Scarb.lock")));
+        assert!(!is_watched(Utf8Path::new("pkg/README.md")));
+    }
+}
@@ -2,8 +2,9 @@ use crate::runner::TestCase;
 use camino::Utf8PathBuf;
 use forge::block_number_map::BlockNumberMap;
 use forge::run;
-use forge::test_filter::TestsFilter;
+use forge::test_filter::{TestsFilter, TestsFilterFlags};
 use forge_runner::contracts_data::ContractsData;
+use forge_runner::fuzz_corpus::FuzzCorpus;
 use forge_runner::test_crate_summary::TestCrateSummary;
 use forge_runner::{RunnerConfig, RunnerParams};
 use std::path::PathBuf;
@@ -15,6 +16,32 @@ use tokio::runtime::Runtime;
 
 #[must_use]
 pub fn run_test_case(test: &TestCase) -> Vec<TestCrateSummary> {
+    run_test_case_with_config(test, &[], &[])
+}
+
+/// Runs the test case with tests of the given full names being invariant tests
+#[must_use]
+pub fn run_test_case_with_invariants(
+    test: &TestCase,
+    invariant_tests: &[String],
+) -> Vec<TestCrateSummary> {
+    run_test_case_with_config(test, invariant_tests, &[])
+}
+
+/// Runs the test case with tests of the given full names being setup fixtures
+#[must_use]
+pub fn run_test_case_with_setup_fixtures(
+    test: &TestCase,
+    setup_fixtures: &[String],
+) -> Vec<TestCrateSummary> {
+    run_test_case_with_config(test, &[], setup_fixtures)
+}
+
+fn run_test_case_with_config(
+    test: &TestCase,
+    invariant_tests: &[String],
+    setup_fixtures: &[String],
+) -> Vec<TestCrateSummary> {
     let test_build_output = Command::new("scarb")
         .current_dir(test.path().unwrap())
         .arg("snforge-test-collector")
@@ -29,7 +56,7 @@ pub fn run_test_case(test: &TestCase) -> Vec<TestCrateSummary> {
     rt.block_on(run(
         "test_package",
         &test.path().unwrap().join("target/dev/snforge"),
-        &TestsFilter::from_flags(None, false, false, false, false, Default::default()),
+        &TestsFilter::from_flags(TestsFilterFlags::default(), Default::default()),
         Arc::new(RunnerConfig::new(
             Utf8PathBuf::from_path_buf(PathBuf::from(tempdir().unwrap().path())).unwrap(),
             false,
@@ -43,8 +70,14 @@ pub fn run_test_case(test: &TestCase) -> Vec<TestCrateSummary> {
         Arc::new(RunnerParams::new(
             ContractsData::try_from(test.contracts().unwrap()).unwrap(),
             test.env().clone(),
+            FuzzCorpus::default(),
         )),
         &[],
+        &[],
+        &[],
+        &[],
+        invariant_tests,
+        setup_fixtures,
         &mut BlockNumberMap::default(),
     ))
     .expect("Runner fail")
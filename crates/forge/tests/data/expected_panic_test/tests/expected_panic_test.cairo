@@ -0,0 +1,35 @@
+#[test]
+#[should_panic]
+fn panic_with_message() {
+    assert!(false, "Insufficient balance");
+}
+
+#[test]
+#[should_panic]
+fn panic_containing_message() {
+    assert!(false, "Insufficient balance: requested 100");
+}
+
+#[test]
+#[should_panic]
+fn panic_matching_pattern() {
+    assert!(false, "Insufficient balance: requested 100");
+}
+
+#[test]
+#[should_panic]
+fn panic_with_prefix() {
+    panic(array!['error', 'details']);
+}
+
+#[test]
+#[should_panic]
+fn panic_with_non_matching_message() {
+    assert!(false, "Insufficient allowance");
+}
+
+#[test]
+#[should_panic]
+fn expected_message_but_didnt_panic() {
+    assert(1 == 1, 'err');
+}
@@ -2,7 +2,7 @@ use fuzzing::adder;
 
 #[derive(Drop)]
 struct MyStruct {
-    a: felt252
+    a: Box<felt252>
 }
 
 #[test]
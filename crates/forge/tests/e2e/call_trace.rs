@@ -0,0 +1,59 @@
+use super::common::runner::{setup_package, test_runner};
+use indoc::indoc;
+use shared::test_utils::output_assert::assert_stdout_contains;
+
+#[test]
+fn call_traces_of_all_tests() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp)
+        .args(["-vvvv", "--exact", "tests::contract::call_and_invoke"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [PASS] tests::contract::call_and_invoke (gas: ~[..])
+        Call trace:
+            [steps: [..]] tests::contract::call_and_invoke
+                [steps: [..]] HelloStarknet::get_balance() (call, contract address: [..], caller address: [..])
+                    <- returned (0x0)
+                [steps: [..]] HelloStarknet::increase_balance(amount: 0x64) (call, contract address: [..], caller address: [..])
+                    <- returned ()
+                [steps: [..]] HelloStarknet::get_balance() (call, contract address: [..], caller address: [..])
+                    <- returned (0x64)
+        "},
+    );
+}
+
+#[test]
+fn call_traces_of_failed_tests() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp)
+        .args(["-vvv", "test_failing"])
+        .assert()
+        .code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [FAIL] tests::test_simple::test_failing
+        Call trace:
+            [steps: [..]] tests::test_simple::test_failing
+        "},
+    );
+}
+
+#[test]
+fn no_call_traces_of_passed_tests_with_lower_verbosity() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp)
+        .args(["-vvv", "call_and_invoke"])
+        .assert()
+        .success();
+
+    assert!(!String::from_utf8_lossy(&output.get_output().stdout).contains("Call trace:"));
+}
@@ -0,0 +1,111 @@
+use super::common::runner::{setup_package, test_runner};
+use assert_fs::fixture::PathChild;
+use indoc::indoc;
+use shared::test_utils::output_assert::assert_stdout_contains;
+use std::str::from_utf8;
+
+#[test]
+fn coverage_summary() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp).arg("--coverage").assert().code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+        [..]
+        Tests: 9 passed, 2 failed, 0 skipped, 2 ignored, 0 filtered out
+
+        Coverage of simple_package package:
+            functions: [..], sierra statements: [..]
+            HelloStarknet: 2/4 functions called (50.00%)
+                sierra statements: [..]
+                do_a_panic: 0 call(s)
+                do_a_panic_with: 0 call(s)
+                get_balance: 2 call(s)
+                increase_balance: 1 call(s)
+
+        Failures:
+            tests::test_simple::test_failing
+            tests::test_simple::test_another_failing
+        "},
+    );
+}
+
+#[test]
+fn coverage_lcov_report() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp).arg("--coverage").assert().code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]
+        Coverage report saved to [..]coverage/lcov.info
+        "},
+    );
+
+    let lcov = std::fs::read_to_string(temp.child("coverage/lcov.info")).unwrap();
+    let lib = temp.child("src/lib.cairo");
+    let hello_starknet = temp.child("src/hello_starknet.cairo");
+
+    assert!(lcov.contains(&format!("SF:{}\n", lib.path().display())));
+    assert!(lcov.contains("FN:3,simple_package::fib\n"));
+    assert!(!lcov.contains("FNDA:0,simple_package::fib\n"));
+    assert!(!lcov.contains("test_fib"));
+
+    assert!(lcov.contains(&format!("SF:{}\n", hello_starknet.path().display())));
+    assert!(lcov.contains(
+        "FN:26,simple_package::hello_starknet::HelloStarknet::IHelloStarknetImpl::get_balance\n"
+    ));
+    assert!(lcov.contains(
+        "FNDA:0,simple_package::hello_starknet::HelloStarknet::IHelloStarknetImpl::do_a_panic\n"
+    ));
+    assert!(!lcov.contains(
+        "FNDA:0,simple_package::hello_starknet::HelloStarknet::IHelloStarknetImpl::get_balance\n"
+    ));
+}
+
+#[test]
+fn coverage_json_event() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp)
+        .args(["--coverage", "--format", "json"])
+        .assert()
+        .code(1);
+
+    let stdout = from_utf8(&output.get_output().stdout).unwrap();
+    let coverage: serde_json::Value = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find(|event| event["event"] == "coverage")
+        .unwrap();
+
+    assert_eq!(coverage["package"], "simple_package");
+    let hello_starknet = &coverage["contracts"]["HelloStarknet"];
+    assert_eq!(hello_starknet["entry_points"]["get_balance"], 2);
+    assert_eq!(hello_starknet["entry_points"]["do_a_panic"], 0);
+
+    let functions = &hello_starknet["functions"];
+    let get_balance = &functions
+        ["simple_package::hello_starknet::HelloStarknet::IHelloStarknetImpl::get_balance"];
+    assert!(!get_balance["executed_statements"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+    let do_a_panic =
+        &functions["simple_package::hello_starknet::HelloStarknet::IHelloStarknetImpl::do_a_panic"];
+    assert!(do_a_panic["statements"].as_u64().unwrap() > 0);
+    assert!(do_a_panic["executed_statements"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+
+    let fib = &coverage["functions"]["simple_package::fib"];
+    assert!(fib["calls"].as_u64().unwrap() > 0);
+    assert!(!fib["executed_statements"].as_array().unwrap().is_empty());
+}
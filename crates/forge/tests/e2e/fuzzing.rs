@@ -1,6 +1,8 @@
-use super::common::runner::{setup_package, test_runner};
+use super::common::runner::{runner, setup_package, test_runner};
+use assert_fs::fixture::{FileWriteStr, PathChild};
 use indoc::indoc;
 use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains};
+use std::fs;
 
 #[test]
 fn fuzzing() {
@@ -21,7 +23,7 @@ fn fuzzing() {
         [PASS] fuzzing::tests::fuzzed_argument (runs: 256, [..]
         [PASS] fuzzing::tests::fuzzed_both_arguments (runs: 256, [..]
         [PASS] fuzzing::tests::passing [..]
-        [FAIL] fuzzing::tests::failing_fuzz (runs: 1, arguments: [[..], [..]])
+        [FAIL] fuzzing::tests::failing_fuzz (runs: 1, arguments: [[..], [..]], shrunk arguments: [[..], [..]])
 
         Failure data:
             0x726573756c74203d3d2061202b2062 ('result == a + b')
@@ -66,7 +68,7 @@ fn fuzzing_set_runs() {
         [PASS] fuzzing::tests::fuzzed_argument (runs: 10, [..]
         [PASS] fuzzing::tests::fuzzed_both_arguments (runs: 10, [..]
         [PASS] fuzzing::tests::passing [..]
-        [FAIL] fuzzing::tests::failing_fuzz (runs: 1, arguments: [[..], [..]])
+        [FAIL] fuzzing::tests::failing_fuzz (runs: 1, arguments: [[..], [..]], shrunk arguments: [[..], [..]])
 
         Failure data:
             0x726573756c74203d3d2061202b2062 ('result == a + b')
@@ -111,7 +113,7 @@ fn fuzzing_set_seed() {
         [PASS] fuzzing::tests::fuzzed_argument (runs: 256, [..]
         [PASS] fuzzing::tests::fuzzed_both_arguments (runs: 256, [..]
         [PASS] fuzzing::tests::passing [..]
-        [FAIL] fuzzing::tests::failing_fuzz (runs: 1, arguments: [[..], [..]])
+        [FAIL] fuzzing::tests::failing_fuzz (runs: 1, arguments: [[..], [..]], shrunk arguments: [[..], [..]])
 
         Failure data:
             0x726573756c74203d3d2061202b2062 ('result == a + b')
@@ -169,7 +171,7 @@ fn fuzzing_incorrect_function_args() {
         Collected 2 test(s) from fuzzing package
         Running 0 test(s) from src/
         Running 2 test(s) from tests/
-        [ERROR] Tried to use incorrect type for fuzzing. Type = tests::incorrect_args::MyStruct is not supported
+        [ERROR] Tried to use incorrect type for fuzzing. Type = Box<[..]felt252> is not supported
         "},
     );
 }
@@ -238,3 +240,167 @@ fn fuzzing_exit_first_single_fail() {
         "},
     );
 }
+
+#[test]
+fn fuzzing_replays_fuzz_corpus() {
+    let temp = setup_package("fuzzing");
+
+    test_runner(&temp)
+        .args(["failing_fuzz", "--fuzzer-seed", "1234"])
+        .assert()
+        .code(1);
+
+    let output = runner(&temp)
+        .args(["fuzz-corpus", "list"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        Fuzz corpus of fuzzing package:
+            fuzzing::tests::failing_fuzz (seed: 1234, arguments: [[..], [..]])
+        "},
+    );
+
+    let output = test_runner(&temp)
+        .args(["failing_fuzz", "--fuzzer-seed", "4321"])
+        .assert()
+        .code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 1 test(s) from fuzzing package
+        Running 1 test(s) from src/
+        [FAIL] fuzzing::tests::failing_fuzz (runs: 1, arguments: [[..], [..]], shrunk arguments: [[..], [..]])
+        [..]
+        Fuzzer seed: 4321
+        [..]
+        "},
+    );
+
+    runner(&temp)
+        .args(["fuzz-corpus", "clear"])
+        .assert()
+        .success();
+
+    let output = runner(&temp)
+        .args(["fuzz-corpus", "list"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        Fuzz corpus is empty
+        "},
+    );
+}
+
+#[test]
+fn parametrized_cases() {
+    let temp = setup_package("fuzzing");
+    let manifest_path = temp.child("Scarb.toml");
+    let scarb_toml = fs::read_to_string(&manifest_path).unwrap();
+    manifest_path
+        .write_str(&format!(
+            "{scarb_toml}{}",
+            indoc! {r#"
+
+            [[tool.snforge.test_case]]
+            test = "fuzzing::tests::failing_fuzz"
+            args = [1, 4]
+
+            [[tool.snforge.test_case]]
+            test = "fuzzing::tests::failing_fuzz"
+            name = "too_small"
+            args = [1, "0x1"]
+            "#}
+        ))
+        .unwrap();
+
+    let output = test_runner(&temp).arg("failing_fuzz").assert().code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 2 test(s) from fuzzing package
+        Running 2 test(s) from src/
+        [PASS] fuzzing::tests::failing_fuzz[case_1] (arguments: [1, 4]) (gas: ~[..])
+        [FAIL] fuzzing::tests::failing_fuzz[too_small] (arguments: [1, 1])
+
+        Failure data:
+            0x726573756c74203d3d2061202b2062 ('result == a + b')
+
+        Running 0 test(s) from tests/
+        Tests: 1 passed, 1 failed, 0 skipped, 0 ignored, [..] filtered out
+
+        Failures:
+            fuzzing::tests::failing_fuzz[too_small]
+        "},
+    );
+}
+
+#[test]
+fn parametrized_case_with_mismatched_arguments() {
+    let temp = setup_package("fuzzing");
+    let manifest_path = temp.child("Scarb.toml");
+    let scarb_toml = fs::read_to_string(&manifest_path).unwrap();
+    manifest_path
+        .write_str(&format!(
+            "{scarb_toml}{}",
+            indoc! {r#"
+
+            [[tool.snforge.test_case]]
+            test = "fuzzing::tests::failing_fuzz"
+            name = "too_few"
+            args = [1]
+
+            [[tool.snforge.test_case]]
+            test = "fuzzing::tests::failing_fuzz"
+            args = [1, 4]
+            "#}
+        ))
+        .unwrap();
+
+    let output = test_runner(&temp).arg("failing_fuzz").assert().code(2);
+
+    assert_stdout_contains(
+        output,
+        "[ERROR] Arguments = [1] of test case = fuzzing::tests::failing_fuzz[too_few] from `test_case` in Scarb.toml do not match the parameters of the test function, expected serialized values of types: [felt252, felt252]",
+    );
+}
+
+#[test]
+fn parametrized_case_of_unknown_test() {
+    let temp = setup_package("fuzzing");
+    let manifest_path = temp.child("Scarb.toml");
+    let scarb_toml = fs::read_to_string(&manifest_path).unwrap();
+    manifest_path
+        .write_str(&format!(
+            "{scarb_toml}{}",
+            indoc! {r#"
+
+            [[tool.snforge.test_case]]
+            test = "tests::failing_fuzz"
+            args = [1, 4]
+            "#}
+        ))
+        .unwrap();
+
+    let output = test_runner(&temp).assert().code(2);
+
+    assert_stdout_contains(
+        output,
+        "[ERROR] Test = tests::failing_fuzz from `test_case` in Scarb.toml not found in package fuzzing, tests have to be referred to by their full names",
+    );
+}
@@ -0,0 +1,33 @@
+use super::common::runner::{setup_package, test_runner};
+use indoc::indoc;
+use shared::test_utils::output_assert::assert_stdout_contains;
+
+#[test]
+fn gas_report_per_contract_function() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp).arg("--gas-report").assert().code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        Tests: 9 passed, 2 failed, 0 skipped, 2 ignored, 0 filtered out
+
+        Gas report for simple_package package:
+
+        HelloStarknet
+        | Function[..] | Calls | Min steps | Mean steps | Median steps | Max steps | Min gas | Mean gas | Median gas | Max gas |
+        | get_balance[..] |     2 |[..]|
+        | increase_balance[..] |     1 |[..]|
+        "},
+    );
+}
+
+#[test]
+fn no_gas_report_by_default() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp).assert().code(1);
+
+    assert!(!String::from_utf8_lossy(&output.get_output().stdout).contains("Gas report"));
+}
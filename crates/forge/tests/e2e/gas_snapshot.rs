@@ -0,0 +1,163 @@
+use super::common::runner::{setup_package, test_runner};
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use indoc::indoc;
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains};
+use std::fs;
+
+#[test]
+fn snapshot_saved_and_checked() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp)
+        .args(["test_fib", "--snapshot"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(output, "Gas snapshot saved to [..].gas-snapshot");
+
+    let snapshot = fs::read_to_string(temp.child(".gas-snapshot")).unwrap();
+    assert!(snapshot.starts_with("simple_package simple_package::tests::test_fib (gas: "));
+    assert_eq!(snapshot.lines().count(), 1);
+
+    let output = test_runner(&temp)
+        .args(["test_fib", "--snapshot", "--check"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        Gas snapshot check:
+            No changes in gas usage
+        "},
+    );
+}
+
+#[test]
+fn check_fails_when_tolerance_exceeded() {
+    let temp = setup_package("simple_package");
+    temp.child(".gas-snapshot")
+        .write_str("simple_package simple_package::tests::test_fib (gas: 1000)\n")
+        .unwrap();
+
+    let output = test_runner(&temp)
+        .args(["test_fib", "--snapshot", "--check"])
+        .assert()
+        .code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        Gas snapshot check:
+            simple_package simple_package::tests::test_fib: 1000 -> [..] ([..], [..]%)
+        1 test(s) exceeded the gas tolerance of 0%
+        "},
+    );
+
+    test_runner(&temp)
+        .args(["test_fib", "--snapshot", "--check", "--tolerance", "100"])
+        .assert()
+        .success();
+
+    // Check does not overwrite the snapshot
+    let snapshot = fs::read_to_string(temp.child(".gas-snapshot")).unwrap();
+    assert_eq!(
+        snapshot,
+        "simple_package simple_package::tests::test_fib (gas: 1000)\n"
+    );
+}
+
+#[test]
+fn check_without_snapshot_file() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp)
+        .args(["--snapshot", "--check"])
+        .assert()
+        .code(2);
+
+    assert_stdout_contains(
+        output,
+        "[ERROR] Failed to read gas snapshot from [..].gas-snapshot, run `snforge test --snapshot` to create it[..]",
+    );
+}
+
+#[test]
+fn snapshot_keeps_entries_of_tests_not_run() {
+    let temp = setup_package("simple_package");
+    temp.child(".gas-snapshot")
+        .write_str(indoc! {"
+            other_package tests::test_other (gas: 5)
+            simple_package simple_package::tests::test_fib (gas: 1000)
+            simple_package tests::test_simple::test_simple (gas: 7)
+            simple_package tests::test_simple::test_removed (gas: 3)
+        "})
+        .unwrap();
+
+    test_runner(&temp)
+        .args(["test_fib", "--snapshot"])
+        .assert()
+        .success();
+
+    let snapshot = fs::read_to_string(temp.child(".gas-snapshot")).unwrap();
+    let lines: Vec<&str> = snapshot.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "other_package tests::test_other (gas: 5)");
+    assert!(lines[1].starts_with("simple_package simple_package::tests::test_fib (gas: "));
+    assert_ne!(
+        lines[1],
+        "simple_package simple_package::tests::test_fib (gas: 1000)"
+    );
+    assert_eq!(
+        lines[2],
+        "simple_package tests::test_simple::test_simple (gas: 7)"
+    );
+}
+
+#[test]
+fn check_reports_stale_entries() {
+    let temp = setup_package("simple_package");
+
+    test_runner(&temp)
+        .args(["test_fib", "--snapshot"])
+        .assert()
+        .success();
+    let snapshot = fs::read_to_string(temp.child(".gas-snapshot")).unwrap();
+    temp.child(".gas-snapshot")
+        .write_str(&format!(
+            "{snapshot}simple_package tests::test_simple::test_removed (gas: 3)\n"
+        ))
+        .unwrap();
+
+    let output = test_runner(&temp)
+        .args(["test_fib", "--snapshot", "--check"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        Gas snapshot check:
+            simple_package tests::test_simple::test_removed: test no longer exists, run `snforge test --snapshot` to remove it
+        "},
+    );
+}
+
+#[test]
+fn negative_tolerance() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp)
+        .args(["--snapshot", "--check", "--tolerance=-5"])
+        .assert()
+        .code(2);
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        error: invalid value '-5' for '--tolerance <PERCENT>': Gas snapshot tolerance must be a non-negative number, got -5
+
+        For more information, try '--help'.
+        "},
+    );
+}
@@ -0,0 +1,120 @@
+use super::common::runner::{setup_package, test_runner};
+use serde_json::Value;
+
+fn snforge_events(stdout: &[u8]) -> Vec<Value> {
+    String::from_utf8(stdout.to_vec())
+        .unwrap()
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|value| value.get("event").is_some())
+        .collect()
+}
+
+fn events_of_kind<'a>(events: &'a [Value], kind: &str) -> Vec<&'a Value> {
+    events
+        .iter()
+        .filter(|event| event["event"] == kind)
+        .collect()
+}
+
+#[test]
+fn simple_package_json_events() {
+    let temp = setup_package("simple_package");
+    let output = test_runner(&temp)
+        .args(["--format", "json"])
+        .assert()
+        .code(1);
+
+    let events = snforge_events(&output.get_output().stdout);
+
+    assert_eq!(
+        events.first().unwrap(),
+        &serde_json::json!({"event": "collected", "package": "simple_package", "tests": 13, "filtered": 0})
+    );
+    assert_eq!(
+        events_of_kind(&events, "suite_started"),
+        vec![
+            &serde_json::json!({"event": "suite_started", "package": "simple_package", "location": "src", "tests": 2}),
+            &serde_json::json!({"event": "suite_started", "package": "simple_package", "location": "tests", "tests": 11}),
+        ]
+    );
+    assert_eq!(events_of_kind(&events, "test_started").len(), 11);
+    assert_eq!(events_of_kind(&events, "test_passed").len(), 9);
+    assert_eq!(events_of_kind(&events, "test_ignored").len(), 2);
+
+    let failed = events_of_kind(&events, "test_failed");
+    assert_eq!(failed.len(), 2);
+    assert!(failed
+        .iter()
+        .any(|event| event["name"] == "tests::test_simple::test_failing"));
+
+    let passed = events_of_kind(&events, "test_passed");
+    let test_fib = passed
+        .iter()
+        .find(|event| event["name"] == "simple_package::tests::test_fib")
+        .unwrap();
+    assert!(test_fib["gas"].is_u64());
+    assert!(test_fib["used_resources"]["steps"].is_u64());
+
+    assert_eq!(
+        events_of_kind(&events, "summary"),
+        vec![&serde_json::json!({
+            "event": "summary",
+            "package": "simple_package",
+            "passed": 9,
+            "failed": 2,
+            "skipped": 0,
+            "ignored": 2,
+            "filtered": 0
+        })]
+    );
+    assert_eq!(
+        events.last().unwrap(),
+        &serde_json::json!({
+            "event": "finished",
+            "failures": ["tests::test_simple::test_failing", "tests::test_simple::test_another_failing"]
+        })
+    );
+}
+
+#[test]
+fn exit_first_json_skipped_events() {
+    let temp = setup_package("exit_first");
+    let output = test_runner(&temp)
+        .args(["--format", "json", "--exit-first"])
+        .assert()
+        .code(1);
+
+    let events = snforge_events(&output.get_output().stdout);
+
+    let skipped = events_of_kind(&events, "test_skipped");
+    assert_eq!(skipped.len(), 1);
+    assert!(skipped[0]["name"].is_string());
+    assert_eq!(
+        events_of_kind(&events, "test_failed")[0]["name"],
+        "tests::ext_function_test::simple_test"
+    );
+}
+
+#[test]
+fn error_json_event() {
+    let temp = setup_package("simple_package");
+    let output = test_runner(&temp)
+        .args(["--format", "json", "--snapshot", "--check"])
+        .assert()
+        .code(2);
+
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    assert!(
+        !stdout.contains("[ERROR]"),
+        "Errors should not be printed as text"
+    );
+
+    let events = snforge_events(&output.get_output().stdout);
+    let error = events.last().unwrap();
+    assert_eq!(error["event"], "error");
+    assert!(error["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Failed to read gas snapshot from"));
+}
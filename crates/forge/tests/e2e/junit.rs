@@ -0,0 +1,67 @@
+use super::common::runner::{setup_package, test_runner};
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use std::fs;
+use toml_edit::{value, Document};
+
+#[test]
+fn junit_report_from_cli() {
+    let temp = setup_package("simple_package");
+
+    test_runner(&temp)
+        .args(["--junit-report", "reports/junit.xml"])
+        .assert()
+        .code(1);
+
+    let report = fs::read_to_string(temp.child("reports/junit.xml")).unwrap();
+
+    assert!(report.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    assert!(report.contains(r#"<testsuites name="snforge" tests="13" failures="2" skipped="2">"#));
+    assert!(report.contains(
+        r#"<testsuite name="simple_package::src" tests="2" failures="0" errors="0" skipped="1">"#
+    ));
+    assert!(report.contains(
+        r#"<testsuite name="simple_package::tests" tests="11" failures="2" errors="0" skipped="1">"#
+    ));
+    assert!(report.contains(
+        r#"<testcase name="tests::test_simple::test_failing" classname="tests::test_simple">"#
+    ));
+    assert!(report.contains(
+        r#"<failure message="0x6661696c696e6720636865636b (&apos;failing check&apos;)">"#
+    ));
+    assert!(report.contains(r#"<property name="gas" value=""#));
+}
+
+#[test]
+fn junit_report_from_cli_is_relative_to_current_dir() {
+    let temp = setup_package("simple_package");
+
+    test_runner(&temp)
+        .current_dir(temp.child("src"))
+        .args(["--junit-report", "junit.xml"])
+        .assert()
+        .code(1);
+
+    assert!(temp.child("src/junit.xml").exists());
+    assert!(!temp.child("junit.xml").exists());
+}
+
+#[test]
+fn junit_report_from_scarb_toml() {
+    let temp = setup_package("simple_package");
+    let manifest_path = temp.child("Scarb.toml");
+    let mut scarb_toml = fs::read_to_string(&manifest_path)
+        .unwrap()
+        .parse::<Document>()
+        .unwrap();
+    scarb_toml["tool"]["snforge"]["junit_report"] = value("junit.xml");
+    manifest_path.write_str(&scarb_toml.to_string()).unwrap();
+
+    test_runner(&temp)
+        .current_dir(temp.child("src"))
+        .assert()
+        .code(1);
+
+    let report = fs::read_to_string(temp.child("junit.xml")).unwrap();
+
+    assert!(report.contains(r#"<testsuites name="snforge" tests="13" failures="2" skipped="2">"#));
+}
@@ -0,0 +1,75 @@
+use super::common::runner::{setup_package, test_runner};
+use indoc::indoc;
+use shared::test_utils::output_assert::assert_stdout_contains;
+
+#[test]
+fn list_simple_package() {
+    let temp = setup_package("simple_package");
+    let output = test_runner(&temp)
+        .args(["--list", "ext_function_test"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 3 test(s) from simple_package package
+        tests::ext_function_test::test_my_test (tests/, expected: success)
+        tests::ext_function_test::ignored_test (tests/, ignored, expected: success)
+        tests::ext_function_test::test_simple (tests/, expected: success)
+        "},
+    );
+}
+
+#[test]
+fn list_does_not_run_tests() {
+    let temp = setup_package("simple_package");
+    let output = test_runner(&temp)
+        .args(["--list", "--ignored"])
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("Collected 2 test(s) from simple_package package"));
+    assert!(!stdout.contains("[PASS]"));
+    assert!(!stdout.contains("[FAIL]"));
+}
+
+#[test]
+fn list_json() {
+    let temp = setup_package("fuzzing");
+    let output = test_runner(&temp)
+        .args([
+            "--list",
+            "--format",
+            "json",
+            "--exact",
+            "fuzzing::tests::custom_fuzzer_config",
+        ])
+        .assert()
+        .success();
+
+    let events: Vec<serde_json::Value> = String::from_utf8(output.get_output().stdout.clone())
+        .unwrap()
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|value| value.get("event").is_some())
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![serde_json::json!({
+            "event": "test_listed",
+            "name": "fuzzing::tests::custom_fuzzer_config",
+            "package": "fuzzing",
+            "location": "src",
+            "ignored": false,
+            "expected_result": "success",
+            "fuzzer": {"runs": 10, "seed": 100}
+        })]
+    );
+}
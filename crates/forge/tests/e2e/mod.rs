@@ -2,15 +2,22 @@ pub(crate) mod common;
 
 mod build_profile;
 mod build_trace_data;
+mod call_trace;
 mod collection;
 mod color;
 mod components;
+mod coverage;
 mod diagnostics_and_plugins;
 mod env;
 mod fork_warning;
 mod forking;
 mod fuzzing;
+mod gas_report;
+mod gas_snapshot;
 mod io_operations;
+mod json_output;
+mod junit;
+mod list;
 mod running;
 mod steps;
 mod trace_print;
@@ -270,6 +270,83 @@ fn with_non_matching_filter() {
     );
 }
 
+#[test]
+fn with_tags_from_scarb_config() {
+    let temp = setup_package("simple_package");
+    let manifest_path = temp.child("Scarb.toml");
+    let scarb_toml = fs::read_to_string(&manifest_path).unwrap();
+    manifest_path
+        .write_str(&format!(
+            "{scarb_toml}{}",
+            indoc! {r#"
+
+            [[tool.snforge.test_tag]]
+            test = "tests::test_simple"
+            tags = ["simple"]
+
+            [[tool.snforge.test_tag]]
+            test = "tests::test_simple::test_failing"
+            tags = ["failing"]
+
+            [[tool.snforge.test_tag]]
+            test = "tests::test_simple::test_another_failing"
+            tags = ["failing"]
+            "#}
+        ))
+        .unwrap();
+
+    let output = test_runner(&temp)
+        .args(["--tag", "simple", "--exclude-tag", "failing"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 4 test(s) from simple_package package
+        Running 0 test(s) from src/
+        Running 4 test(s) from tests/
+        [PASS] tests::test_simple::test_simple [..]
+        [PASS] tests::test_simple::test_simple2 [..]
+        [PASS] tests::test_simple::test_two [..]
+        [PASS] tests::test_simple::test_two_and_two [..]
+        Tests: 4 passed, 0 failed, 0 skipped, 0 ignored, 9 filtered out
+        "},
+    );
+}
+
+#[test]
+fn test_tag_of_unknown_module() {
+    let temp = setup_package("simple_package");
+    let manifest_path = temp.child("Scarb.toml");
+    let scarb_toml = fs::read_to_string(&manifest_path).unwrap();
+    manifest_path
+        .write_str(&format!(
+            "{scarb_toml}{}",
+            indoc! {r#"
+
+            [[tool.snforge.test_tag]]
+            test = "test_simple"
+            tags = ["simple"]
+            "#}
+        ))
+        .unwrap();
+
+    let output = test_runner(&temp)
+        .args(["--tag", "simple"])
+        .assert()
+        .code(2);
+
+    assert_stdout_contains(
+        output,
+        "[ERROR] Test or module = test_simple from `test_tag` in Scarb.toml not found in package simple_package, tests and modules have to be referred to by their full names",
+    );
+}
+
 #[test]
 fn with_ignored_flag() {
     let temp = setup_package("simple_package");
@@ -830,6 +907,49 @@ fn should_panic() {
     );
 }
 
+#[test]
+fn expected_panic_from_scarb_config() {
+    let temp = tempdir_with_tool_versions().unwrap();
+    temp.copy_from(
+        "tests/data/expected_panic_test",
+        &["**/*.cairo", "**/*.toml"],
+    )
+    .unwrap();
+
+    let output = test_runner(&temp).assert().code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! { r#"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 6 test(s) from expected_panic_test package
+        Running 0 test(s) from src/
+        Running 6 test(s) from tests/
+        [PASS] tests::expected_panic_test::panic_with_message [..]
+        [PASS] tests::expected_panic_test::panic_containing_message [..]
+        [PASS] tests::expected_panic_test::panic_matching_pattern [..]
+        [PASS] tests::expected_panic_test::panic_with_prefix [..]
+        [FAIL] tests::expected_panic_test::panic_with_non_matching_message
+
+        Failure data:
+            Incorrect panic data
+            Actual:    [..] ("Insufficient allowance")
+            Expected:  message containing "balance"
+
+        [FAIL] tests::expected_panic_test::expected_message_but_didnt_panic
+
+        Failure data:
+            Expected to panic but didn't
+            Expected panic data:  "Insufficient balance"
+
+        Tests: 4 passed, 2 failed, 0 skipped, 0 ignored, 0 filtered out
+        "#},
+    );
+}
+
 #[test]
 fn printing_in_contracts() {
     let temp = setup_package("contract_printing");
@@ -861,6 +981,99 @@ fn printing_in_contracts() {
     );
 }
 
+#[test]
+fn slow_test_cancelled_after_its_timeout() {
+    let temp = setup_package("steps");
+    let lib_path = temp.child("src/lib.cairo");
+    let lib = fs::read_to_string(&lib_path).unwrap();
+    lib_path
+        .write_str(&format!(
+            "{lib}{}",
+            indoc! {r"
+
+            #[cfg(test)]
+            mod slow {
+                #[test]
+                fn looping() {
+                    let mut i: u64 = 0;
+                    loop {
+                        if i == 100_000_000 {
+                            break;
+                        }
+                        i = i + 1;
+                    }
+                }
+            }
+            "}
+        ))
+        .unwrap();
+    let manifest_path = temp.child("Scarb.toml");
+    let scarb_toml = fs::read_to_string(&manifest_path).unwrap();
+    manifest_path
+        .write_str(&format!(
+            "{scarb_toml}{}",
+            indoc! {r#"
+
+            [[tool.snforge.test_timeout]]
+            test = "steps::slow::looping"
+            timeout = 1
+            "#}
+        ))
+        .unwrap();
+
+    let output = test_runner(&temp)
+        .args(["slow::looping", "--max-n-steps", "4294967295"])
+        .timeout(std::time::Duration::from_secs(60))
+        .assert()
+        .code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 1 test(s) from steps package
+        Running 1 test(s) from src/
+        [TIMEOUT] steps::slow::looping
+
+        Failure data:
+            Test did not finish within the timeout of 1s
+
+        Tests: 0 passed, 1 failed, 0 skipped, 0 ignored, 4 filtered out
+
+        Failures:
+            steps::slow::looping
+        "},
+    );
+}
+
+#[test]
+fn test_timeout_of_unknown_test() {
+    let temp = setup_package("steps");
+    let manifest_path = temp.child("Scarb.toml");
+    let scarb_toml = fs::read_to_string(&manifest_path).unwrap();
+    manifest_path
+        .write_str(&format!(
+            "{scarb_toml}{}",
+            indoc! {r#"
+
+            [[tool.snforge.test_timeout]]
+            test = "tests::steps_570031"
+            timeout = 1
+            "#}
+        ))
+        .unwrap();
+
+    let output = test_runner(&temp).assert().code(2);
+
+    assert_stdout_contains(
+        output,
+        "[ERROR] Test = tests::steps_570031 from `test_timeout` in Scarb.toml not found in package steps, tests have to be referred to by their full names",
+    );
+}
+
 #[test]
 fn incompatible_snforge_std_version_warning() {
     let temp = setup_package("steps");
@@ -0,0 +1,155 @@
+use indoc::indoc;
+use std::path::Path;
+use test_utils::runner::{
+    assert_case_output_contains, assert_failed, assert_passed, Contract, TestCase,
+};
+use test_utils::running_tests::{run_test_case, run_test_case_with_setup_fixtures};
+use test_utils::test_case;
+
+#[test]
+fn tests_start_from_setup_state() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use snforge_std::{ declare, ContractClassTrait, start_warp, CheatTarget };
+            use starknet::{ ContractAddress, contract_address_const };
+
+            #[starknet::interface]
+            trait IHelloStarknet<TContractState> {
+                fn increase_balance(ref self: TContractState, amount: felt252);
+                fn get_balance(self: @TContractState) -> felt252;
+            }
+
+            fn dispatcher() -> IHelloStarknetDispatcher {
+                IHelloStarknetDispatcher { contract_address: contract_address_const::<1234>() }
+            }
+
+            #[test]
+            fn setup() {
+                let contract = declare("HelloStarknet");
+                contract.deploy_at(@array![], contract_address_const::<1234>()).unwrap();
+                dispatcher().increase_balance(5);
+                start_warp(CheatTarget::All, 1000);
+            }
+
+            #[test]
+            fn first_test() {
+                assert(dispatcher().get_balance() == 5, 'balance not set up');
+                dispatcher().increase_balance(10);
+                assert(dispatcher().get_balance() == 15, 'balance not increased');
+                assert(starknet::get_block_timestamp() == 1000, 'warp not set up');
+            }
+
+            #[test]
+            fn second_test() {
+                assert(dispatcher().get_balance() == 5, 'state shared between tests');
+                dispatcher().increase_balance(1);
+                assert(dispatcher().get_balance() == 6, 'balance not increased');
+            }
+        "#
+        ),
+        Contract::from_code_path(
+            "HelloStarknet".to_string(),
+            Path::new("tests/data/contracts/hello_starknet.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case_with_setup_fixtures(&test, &["tests::test_case::setup".to_string()]);
+
+    assert_passed(&result);
+    // Setup fixtures are not reported as tests
+    assert_eq!(
+        TestCase::find_test_result(&result)
+            .test_case_summaries
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn tests_named_setup_are_not_fixtures_without_opt_in() {
+    let test = test_case!(indoc!(
+        r"
+            #[test]
+            fn setup() {
+                assert(1 == 2, 'setup failed');
+            }
+
+            #[test]
+            fn other_test() {
+                assert(1 == 1, 'simple check');
+            }
+        "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_case_output_contains(&result, "setup", "setup failed");
+    assert_eq!(
+        TestCase::find_test_result(&result)
+            .test_case_summaries
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn tests_fail_when_setup_fails() {
+    let test = test_case!(indoc!(
+        r"
+            #[test]
+            fn setup() {
+                assert(1 == 2, 'setup failed');
+            }
+
+            #[test]
+            fn using_setup() {
+                assert(1 == 1, 'simple check');
+            }
+        "
+    ));
+
+    let result = run_test_case_with_setup_fixtures(&test, &["tests::test_case::setup".to_string()]);
+
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "using_setup",
+        "Setup fixture tests::test_case::setup failed",
+    );
+    assert_case_output_contains(&result, "using_setup", "setup failed");
+    assert_eq!(
+        TestCase::find_test_result(&result)
+            .test_case_summaries
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn tests_fail_when_setup_is_ignored() {
+    let test = test_case!(indoc!(
+        r"
+            #[test]
+            #[ignore]
+            fn setup() {
+                assert(1 == 1, 'simple check');
+            }
+
+            #[test]
+            fn using_setup() {
+                assert(1 == 1, 'simple check');
+            }
+        "
+    ));
+
+    let result = run_test_case_with_setup_fixtures(&test, &["tests::test_case::setup".to_string()]);
+
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "using_setup",
+        "Setup fixture tests::test_case::setup is ignored",
+    );
+}
@@ -1,3 +1,4 @@
+use cairo_felt::Felt252;
 use forge_runner::test_case_summary::{AnyTestCaseSummary, TestCaseSummary};
 use indoc::indoc;
 use test_utils::runner::{assert_passed, TestCase};
@@ -81,3 +82,136 @@ fn fuzzed_loop() {
     assert!((gas_info.mean - 28.).abs() < f64::EPSILON);
     assert!((gas_info.std_deviation - 15.99).abs() < 0.01);
 }
+
+#[test]
+fn fuzzed_signed_integers_and_bool() {
+    let test = test_case!(indoc!(
+        r"
+        #[test]
+        fn fuzzed_signed_integers_and_bool(a: i8, b: i128, c: bool) {
+            let a: felt252 = a.into();
+            let b: felt252 = b.into();
+            assert(a + b == b + a, 'a + b == b + a');
+            assert(c || !c, 'c || !c');
+        }
+    "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn fuzzed_structs_and_enums() {
+    let test = test_case!(indoc!(
+        r"
+        #[derive(Drop, Copy)]
+        struct Point {
+            x: u32,
+            y: i64,
+        }
+
+        #[derive(Drop, Copy)]
+        enum Shape {
+            Dot: Point,
+            Segment: (Point, Point),
+            Empty,
+        }
+
+        fn width(shape: Shape) -> u64 {
+            match shape {
+                Shape::Dot(_) => 0,
+                Shape::Segment(segment) => {
+                    let (a, b) = segment;
+                    if a.x > b.x {
+                        (a.x - b.x).into()
+                    } else {
+                        (b.x - a.x).into()
+                    }
+                },
+                Shape::Empty => 0,
+            }
+        }
+
+        #[test]
+        fn fuzzed_structs_and_enums(shape: Shape, point: Point) {
+            assert(width(shape) <= 0xffffffff, 'width too big');
+            assert(width(Shape::Dot(point)) == 0, 'dot has no width');
+        }
+    "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn fuzzed_arrays_and_contract_address() {
+    let test = test_case!(indoc!(
+        r"
+        use starknet::ContractAddress;
+
+        #[test]
+        fn fuzzed_arrays_and_contract_address(
+            values: Array<u16>, span: Span<felt252>, text: ByteArray, address: ContractAddress
+        ) {
+            assert(values.len() <= 16, 'array too long');
+            assert(span.len() <= 16, 'span too long');
+            assert(text.len() <= 64, 'byte array too long');
+
+            let mut i = 0;
+            loop {
+                if i == text.len() {
+                    break;
+                }
+                assert(text.at(i).is_some(), 'byte array malformed');
+                i += 1;
+            };
+
+            let address_felt: felt252 = address.into();
+            let parsed: ContractAddress = address_felt.try_into().unwrap();
+            assert(parsed == address, 'address out of range');
+        }
+    "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn shrinks_failing_arguments() {
+    let test = test_case!(indoc!(
+        r"
+        #[test]
+        #[fuzzer(runs: 256, seed: 100)]
+        fn shrinks_failing_arguments(a: u256, b: Array<u8>) {
+            assert(b.len() < 3, 'array too long');
+        }
+    "
+    ));
+
+    let result = run_test_case(&test);
+
+    let crate_summary = TestCase::find_test_result(&result);
+    let AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed {
+        test_statistics, ..
+    }) = &crate_summary.test_case_summaries[0]
+    else {
+        panic!()
+    };
+
+    // u256 shrunk to zero and the shortest failing array of zeros
+    assert_eq!(
+        test_statistics.shrunk_arguments,
+        Some(
+            vec![0, 0, 3, 0, 0, 0]
+                .into_iter()
+                .map(Felt252::from)
+                .collect()
+        )
+    );
+}
@@ -0,0 +1,269 @@
+use forge_runner::test_case_summary::{AnyTestCaseSummary, FuzzingStatistics, TestCaseSummary};
+use indoc::indoc;
+use test_utils::runner::{assert_failed, assert_passed, Contract, TestCase};
+use test_utils::running_tests::{run_test_case, run_test_case_with_invariants};
+use test_utils::test_case;
+
+fn counter_contract() -> Contract {
+    Contract::new(
+        "Counter",
+        indoc!(
+            r"
+            #[starknet::interface]
+            trait ICounter<TContractState> {
+                fn increase(ref self: TContractState, amount: u8);
+                fn reset(ref self: TContractState);
+                fn get(self: @TContractState) -> u32;
+            }
+
+            #[starknet::contract]
+            mod Counter {
+                #[storage]
+                struct Storage {
+                    value: u32,
+                }
+
+                #[abi(embed_v0)]
+                impl CounterImpl of super::ICounter<ContractState> {
+                    fn increase(ref self: ContractState, amount: u8) {
+                        self.value.write(self.value.read() + amount.into());
+                    }
+
+                    fn reset(ref self: ContractState) {
+                        self.value.write(0);
+                    }
+
+                    fn get(self: @ContractState) -> u32 {
+                        self.value.read()
+                    }
+                }
+            }
+            "
+        ),
+    )
+}
+
+#[test]
+fn invariant_holds() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use result::ResultTrait;
+            use starknet::contract_address_const;
+            use snforge_std::{declare, ContractClassTrait, target_contract, target_sender, next_call};
+
+            #[starknet::interface]
+            trait ICounter<TContractState> {
+                fn get(self: @TContractState) -> u32;
+            }
+
+            #[test]
+            fn invariant_value_bounded() {
+                let contract = declare("Counter");
+                let contract_address = contract.deploy(@array![]).unwrap();
+                target_contract(contract_address);
+                target_sender(contract_address_const::<123>());
+
+                let dispatcher = ICounterDispatcher { contract_address };
+                loop {
+                    assert(dispatcher.get() <= 16 * 255, 'value out of bounds');
+                    if !next_call() {
+                        break;
+                    }
+                };
+            }
+            "#
+        ),
+        counter_contract()
+    );
+
+    let result = run_test_case_with_invariants(
+        &test,
+        &["tests::test_case::invariant_value_bounded".to_string()],
+    );
+
+    assert_passed(&result);
+}
+
+#[test]
+fn invariant_broken() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use result::ResultTrait;
+            use snforge_std::{declare, ContractClassTrait, target_contract, next_call};
+
+            #[starknet::interface]
+            trait ICounter<TContractState> {
+                fn get(self: @TContractState) -> u32;
+            }
+
+            #[test]
+            fn invariant_value_small() {
+                let contract = declare("Counter");
+                let contract_address = contract.deploy(@array![]).unwrap();
+                target_contract(contract_address);
+
+                let dispatcher = ICounterDispatcher { contract_address };
+                loop {
+                    assert(dispatcher.get() < 300, 'value too big');
+                    if !next_call() {
+                        break;
+                    }
+                };
+            }
+            "#
+        ),
+        counter_contract()
+    );
+
+    let result = run_test_case_with_invariants(
+        &test,
+        &["tests::test_case::invariant_value_small".to_string()],
+    );
+
+    assert_failed(&result);
+
+    let AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed {
+        test_statistics:
+            FuzzingStatistics {
+                call_sequence: Some(call_sequence),
+                ..
+            },
+        ..
+    }) = &TestCase::find_test_result(&result).test_case_summaries[0]
+    else {
+        panic!("Invariant test should fail with a call sequence")
+    };
+
+    // Resets do not contribute to the failure, so shrinking removes them
+    assert!(!call_sequence.is_empty());
+    assert!(call_sequence
+        .iter()
+        .all(|call| call.starts_with("Counter::increase(")));
+}
+
+#[test]
+fn invariant_cheatcodes_outside_invariant_test() {
+    let test = test_case!(indoc!(
+        r"
+        use snforge_std::next_call;
+
+        #[test]
+        fn calls_without_targets() {
+            next_call();
+        }
+        "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_failed(&result);
+}
+
+#[test]
+fn invariant_calls_keep_prank_of_the_test() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use result::ResultTrait;
+            use starknet::{ContractAddress, contract_address_const};
+            use snforge_std::{
+                declare, ContractClassTrait, target_contract, target_sender, next_call, start_prank,
+                CheatTarget
+            };
+
+            #[starknet::interface]
+            trait ICallerRecorder<TContractState> {
+                fn record(ref self: TContractState);
+                fn get_last_caller(self: @TContractState) -> ContractAddress;
+            }
+
+            #[test]
+            fn invariant_prank_restored() {
+                let contract = declare("CallerRecorder");
+                let contract_address = contract.deploy(@array![]).unwrap();
+                target_contract(contract_address);
+                target_sender(contract_address_const::<123>());
+                start_prank(CheatTarget::One(contract_address), contract_address_const::<456>());
+
+                let dispatcher = ICallerRecorderDispatcher { contract_address };
+                loop {
+                    if !next_call() {
+                        break;
+                    }
+                    assert(
+                        dispatcher.get_last_caller() == contract_address_const::<123>(),
+                        'invariant call not pranked'
+                    );
+                    dispatcher.record();
+                    assert(
+                        dispatcher.get_last_caller() == contract_address_const::<456>(),
+                        'prank not restored'
+                    );
+                };
+            }
+            "#
+        ),
+        Contract::new(
+            "CallerRecorder",
+            indoc!(
+                r"
+                use starknet::ContractAddress;
+
+                #[starknet::interface]
+                trait ICallerRecorder<TContractState> {
+                    fn record(ref self: TContractState);
+                    fn get_last_caller(self: @TContractState) -> ContractAddress;
+                }
+
+                #[starknet::contract]
+                mod CallerRecorder {
+                    use starknet::{ContractAddress, get_caller_address};
+
+                    #[storage]
+                    struct Storage {
+                        last_caller: ContractAddress,
+                    }
+
+                    #[abi(embed_v0)]
+                    impl CallerRecorderImpl of super::ICallerRecorder<ContractState> {
+                        fn record(ref self: ContractState) {
+                            self.last_caller.write(get_caller_address());
+                        }
+
+                        fn get_last_caller(self: @ContractState) -> ContractAddress {
+                            self.last_caller.read()
+                        }
+                    }
+                }
+                "
+            ),
+        )
+    );
+
+    let result = run_test_case_with_invariants(
+        &test,
+        &["tests::test_case::invariant_prank_restored".to_string()],
+    );
+
+    assert_passed(&result);
+}
+
+#[test]
+fn test_named_like_invariant_is_not_invariant_test() {
+    let test = test_case!(indoc!(
+        r"
+        use snforge_std::next_call;
+
+        #[test]
+        fn invariant_not_listed() {
+            next_call();
+        }
+        "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_failed(&result);
+}
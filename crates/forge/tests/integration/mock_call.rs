@@ -139,3 +139,213 @@ fn mock_call_complex_types() {
     let result = run_test_case(&test);
     assert_passed(&result);
 }
+
+#[test]
+fn mock_call_calldata_span_and_sequence() {
+    let test = test_case!(
+        indoc!(
+            r#"
+        use result::ResultTrait;
+        use snforge_std::{
+            declare, ContractClassTrait, mock_call, mock_call_sequence, MockTarget, MockCalldata,
+            CheatSpan
+        };
+
+        #[starknet::interface]
+        trait IMockChecker<TContractState> {
+            fn get_thing(ref self: TContractState) -> felt252;
+        }
+
+        #[test]
+        fn mock_call_span() {
+            let contract = declare("MockChecker");
+            let contract_address = contract.deploy(@array![420]).unwrap();
+            let dispatcher = IMockCheckerDispatcher { contract_address };
+
+            mock_call(
+                MockTarget::Contract(contract_address),
+                selector!("get_thing"),
+                MockCalldata::Any,
+                421,
+                CheatSpan::Number(2)
+            );
+
+            assert(dispatcher.get_thing() == 421, 'Incorrect thing');
+            assert(dispatcher.get_thing() == 421, 'Incorrect thing');
+            assert(dispatcher.get_thing() == 420, 'Incorrect thing');
+        }
+
+        #[test]
+        fn mock_call_exact_calldata() {
+            let contract = declare("MockChecker");
+            let contract_address = contract.deploy(@array![420]).unwrap();
+            let dispatcher = IMockCheckerDispatcher { contract_address };
+
+            mock_call(
+                MockTarget::Contract(contract_address),
+                selector!("get_thing"),
+                MockCalldata::Exact(array![].span()),
+                421,
+                CheatSpan::Indefinite
+            );
+            mock_call(
+                MockTarget::Contract(contract_address),
+                selector!("get_thing"),
+                MockCalldata::Exact(array![1].span()),
+                422,
+                CheatSpan::Indefinite
+            );
+
+            assert(dispatcher.get_thing() == 421, 'Incorrect thing');
+        }
+
+        #[test]
+        fn mock_call_return_sequence() {
+            let contract = declare("MockChecker");
+            let contract_address = contract.deploy(@array![420]).unwrap();
+            let dispatcher = IMockCheckerDispatcher { contract_address };
+
+            mock_call_sequence(
+                MockTarget::Contract(contract_address),
+                selector!("get_thing"),
+                MockCalldata::Any,
+                array![1, 2, 3],
+                CheatSpan::Indefinite
+            );
+
+            assert(dispatcher.get_thing() == 1, 'Incorrect thing');
+            assert(dispatcher.get_thing() == 2, 'Incorrect thing');
+            assert(dispatcher.get_thing() == 3, 'Incorrect thing');
+            assert(dispatcher.get_thing() == 3, 'Incorrect thing');
+        }
+    "#
+        ),
+        Contract::from_code_path(
+            "MockChecker".to_string(),
+            Path::new("tests/data/contracts/mock_checker.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+    assert_passed(&result);
+}
+
+#[test]
+fn mock_call_panic() {
+    let test = test_case!(
+        indoc!(
+            r#"
+        use result::ResultTrait;
+        use snforge_std::{
+            declare, ContractClassTrait, mock_call_panic, MockTarget, MockCalldata, CheatSpan
+        };
+
+        #[starknet::interface]
+        trait IMockChecker<TContractState> {
+            fn get_thing(ref self: TContractState) -> felt252;
+        }
+
+        #[test]
+        fn mock_call_panic_safe_dispatcher() {
+            let contract = declare("MockChecker");
+            let contract_address = contract.deploy(@array![420]).unwrap();
+            let safe_dispatcher = IMockCheckerSafeDispatcher { contract_address };
+
+            mock_call_panic(
+                MockTarget::Contract(contract_address),
+                selector!("get_thing"),
+                MockCalldata::Any,
+                array!['mocked panic', 'data'],
+                CheatSpan::Number(1)
+            );
+
+            match safe_dispatcher.get_thing() {
+                Result::Ok(_) => panic_with_felt252('shouldve panicked'),
+                Result::Err(panic_data) => {
+                    assert(panic_data.len() == 2, 'Wrong panic_data len');
+                    assert(*panic_data.at(0) == 'mocked panic', *panic_data.at(0));
+                    assert(*panic_data.at(1) == 'data', *panic_data.at(1));
+                }
+            };
+
+            assert(safe_dispatcher.get_thing().unwrap() == 420, 'Incorrect thing');
+        }
+
+        #[test]
+        #[should_panic(expected: ('mocked panic', ))]
+        fn mock_call_panic_dispatcher() {
+            let contract = declare("MockChecker");
+            let contract_address = contract.deploy(@array![420]).unwrap();
+            let dispatcher = IMockCheckerDispatcher { contract_address };
+
+            mock_call_panic(
+                MockTarget::Contract(contract_address),
+                selector!("get_thing"),
+                MockCalldata::Any,
+                array!['mocked panic'],
+                CheatSpan::Indefinite
+            );
+
+            dispatcher.get_thing();
+        }
+    "#
+        ),
+        Contract::from_code_path(
+            "MockChecker".to_string(),
+            Path::new("tests/data/contracts/mock_checker.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+    assert_passed(&result);
+}
+
+#[test]
+fn mock_call_class() {
+    let test = test_case!(
+        indoc!(
+            r#"
+        use result::ResultTrait;
+        use snforge_std::{
+            declare, ContractClassTrait, mock_call, stop_class_mock_call, MockTarget, MockCalldata,
+            CheatSpan
+        };
+
+        #[starknet::interface]
+        trait IMockChecker<TContractState> {
+            fn get_thing(ref self: TContractState) -> felt252;
+        }
+
+        #[test]
+        fn mock_call_class() {
+            let contract = declare("MockChecker");
+
+            mock_call(
+                MockTarget::Class(contract.class_hash),
+                selector!("get_thing"),
+                MockCalldata::Any,
+                421,
+                CheatSpan::Indefinite
+            );
+
+            let contract_address = contract.deploy(@array![420]).unwrap();
+            let dispatcher = IMockCheckerDispatcher { contract_address };
+            assert(dispatcher.get_thing() == 421, 'Incorrect thing');
+
+            stop_class_mock_call(contract.class_hash, selector!("get_thing"));
+            assert(dispatcher.get_thing() == 420, 'Incorrect thing');
+        }
+    "#
+        ),
+        Contract::from_code_path(
+            "MockChecker".to_string(),
+            Path::new("tests/data/contracts/mock_checker.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+    assert_passed(&result);
+}
@@ -8,9 +8,11 @@ mod dict;
 mod dispatchers;
 mod elect;
 mod env;
+mod fixtures;
 mod fuzzing;
 mod gas;
 mod get_class_hash;
+mod invariant;
 mod l1_handler_executor;
 mod mock_call;
 mod prank;
@@ -24,7 +26,9 @@ mod setup_fork;
 mod should_panic;
 mod signing;
 mod spoof;
+mod spy_calls;
 mod spy_events;
+mod spy_messages_to_l1;
 mod store_load;
 mod syscalls;
 mod test_state;
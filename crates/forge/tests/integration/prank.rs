@@ -133,3 +133,57 @@ fn prank() {
 
     assert_passed(&result);
 }
+
+#[test]
+fn prank_class() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use result::ResultTrait;
+            use array::ArrayTrait;
+            use option::OptionTrait;
+            use traits::TryInto;
+            use starknet::ContractAddress;
+            use starknet::Felt252TryIntoContractAddress;
+            use snforge_std::{ declare, ContractClassTrait, start_prank, stop_prank, CheatTarget };
+
+            #[starknet::interface]
+            trait IPrankChecker<TContractState> {
+                fn get_caller_address(ref self: TContractState) -> felt252;
+            }
+
+            #[test]
+            fn test_prank_class() {
+                let contract = declare("PrankChecker");
+                let contract_address_1 = contract.deploy(@ArrayTrait::new()).unwrap();
+                let dispatcher_1 = IPrankCheckerDispatcher { contract_address: contract_address_1 };
+
+                let old_caller_address = dispatcher_1.get_caller_address();
+
+                let target_caller_address: ContractAddress = 123.try_into().unwrap();
+                start_prank(CheatTarget::Class(contract.class_hash), target_caller_address);
+
+                let contract_address_2 = contract.deploy(@ArrayTrait::new()).unwrap();
+                let dispatcher_2 = IPrankCheckerDispatcher { contract_address: contract_address_2 };
+
+                assert(dispatcher_1.get_caller_address() == 123, 'Wrong caller address');
+                assert(dispatcher_2.get_caller_address() == 123, 'Wrong caller address');
+
+                stop_prank(CheatTarget::Class(contract.class_hash));
+
+                assert(dispatcher_1.get_caller_address() == old_caller_address, 'Address did not change back');
+                assert(dispatcher_2.get_caller_address() == old_caller_address, 'Address did not change back');
+            }
+        "#
+        ),
+        Contract::from_code_path(
+            "PrankChecker".to_string(),
+            Path::new("tests/data/contracts/prank_checker.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
@@ -1,4 +1,5 @@
 use forge_runner::contracts_data::ContractsData;
+use forge_runner::fuzz_corpus::FuzzCorpus;
 use indoc::formatdoc;
 use std::path::Path;
 use std::path::PathBuf;
@@ -10,7 +11,7 @@ use camino::Utf8PathBuf;
 use forge::block_number_map::BlockNumberMap;
 use forge::run;
 use forge::scarb::config::ForkTarget;
-use forge::test_filter::TestsFilter;
+use forge::test_filter::{TestsFilter, TestsFilterFlags};
 use tempfile::tempdir;
 use tokio::runtime::Runtime;
 
@@ -117,7 +118,7 @@ fn fork_aliased_decorator() {
         .block_on(run(
             &String::from("test_package"),
             &test.path().unwrap().join("target/dev/snforge"),
-            &TestsFilter::from_flags(None, false, false, false, false, Default::default()),
+            &TestsFilter::from_flags(TestsFilterFlags::default(), Default::default()),
             Arc::new(RunnerConfig::new(
                 Utf8PathBuf::from_path_buf(PathBuf::from(tempdir().unwrap().path())).unwrap(),
                 false,
@@ -131,6 +132,7 @@ fn fork_aliased_decorator() {
             Arc::new(RunnerParams::new(
                 ContractsData::try_from(test.contracts().unwrap()).unwrap(),
                 test.env().clone(),
+                FuzzCorpus::default(),
             )),
             &[ForkTarget::new(
                 "FORK_NAME_FROM_SCARB_TOML".to_string(),
@@ -140,6 +142,11 @@ fn fork_aliased_decorator() {
                     block_id_value: "Latest".to_string(),
                 },
             )],
+            &[],
+            &[],
+            &[],
+            &[],
+            &[],
             &mut BlockNumberMap::default(),
         ))
         .expect("Runner fail");
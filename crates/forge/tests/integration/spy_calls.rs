@@ -0,0 +1,109 @@
+use indoc::indoc;
+use std::path::Path;
+use test_utils::runner::{assert_case_output_contains, assert_failed, assert_passed, Contract};
+use test_utils::running_tests::run_test_case;
+use test_utils::test_case;
+
+#[test]
+fn spy_calls_fetch_and_assert() {
+    let test = test_case!(
+        indoc!(
+            r#"
+        use array::ArrayTrait;
+        use result::ResultTrait;
+        use snforge_std::{
+            declare, ContractClassTrait, spy_calls, SpyOn, CallSpy, CallFetcher, CallAssertions,
+            test_address
+        };
+        use snforge_std::trace::{CallType, CallResult};
+
+        #[starknet::interface]
+        trait IMockChecker<TContractState> {
+            fn get_thing(ref self: TContractState) -> felt252;
+            fn get_constant_thing(ref self: TContractState) -> felt252;
+        }
+
+        #[test]
+        fn spy_calls_fetch_and_assert() {
+            let contract = declare("MockChecker");
+            let contract_address = contract.deploy(@array![420]).unwrap();
+            let dispatcher = IMockCheckerDispatcher { contract_address };
+
+            let mut spy = spy_calls(SpyOn::One(contract_address), array![selector!("get_thing")]);
+
+            dispatcher.get_thing();
+            dispatcher.get_thing();
+            dispatcher.get_constant_thing();
+
+            spy.fetch_calls();
+            assert(spy.calls.len() == 2, 'There should be two calls');
+
+            let call = spy.calls.at(0);
+            assert(*call.to == contract_address, 'Wrong to');
+            assert(*call.caller == test_address(), 'Wrong caller');
+            assert(*call.selector == selector!("get_thing"), 'Wrong selector');
+            assert(call.calldata.len() == 0, 'Wrong calldata');
+            assert(call.call_type == @CallType::Call, 'Wrong call type');
+            assert(call.result == @CallResult::Success(array![420]), 'Wrong result');
+
+            spy.assert_called(contract_address, selector!("get_thing"), array![], 2);
+            spy.assert_not_called(contract_address, selector!("get_constant_thing"), array![]);
+        }
+    "#
+        ),
+        Contract::from_code_path(
+            "MockChecker".to_string(),
+            Path::new("tests/data/contracts/mock_checker.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+    assert_passed(&result);
+}
+
+#[test]
+fn spy_calls_assert_called_fails() {
+    let test = test_case!(
+        indoc!(
+            r#"
+        use array::ArrayTrait;
+        use result::ResultTrait;
+        use snforge_std::{
+            declare, ContractClassTrait, spy_calls, SpyOn, CallSpy, CallAssertions
+        };
+
+        #[starknet::interface]
+        trait IMockChecker<TContractState> {
+            fn get_thing(ref self: TContractState) -> felt252;
+        }
+
+        #[test]
+        fn spy_calls_assert_called_fails() {
+            let contract = declare("MockChecker");
+            let contract_address = contract.deploy(@array![420]).unwrap();
+            let dispatcher = IMockCheckerDispatcher { contract_address };
+
+            let spy = spy_calls(SpyOn::All, array![]);
+
+            dispatcher.get_thing();
+
+            spy.assert_called(contract_address, selector!("get_thing"), array![], 2);
+        }
+    "#
+        ),
+        Contract::from_code_path(
+            "MockChecker".to_string(),
+            Path::new("tests/data/contracts/mock_checker.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "spy_calls_assert_called_fails",
+        "Expected 2 call(s)",
+    );
+}
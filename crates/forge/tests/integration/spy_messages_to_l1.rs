@@ -0,0 +1,92 @@
+use indoc::indoc;
+use std::path::Path;
+use test_utils::runner::{assert_passed, Contract};
+use test_utils::running_tests::run_test_case;
+use test_utils::test_case;
+
+#[test]
+fn spy_messages_to_l1_simple() {
+    let test = test_case!(
+        indoc!(
+            r#"
+        use array::ArrayTrait;
+        use result::ResultTrait;
+        use starknet::EthAddress;
+        use snforge_std::{
+            declare, ContractClassTrait, spy_messages_to_l1, SpyOn, MessageToL1, MessageToL1Spy,
+            MessageToL1Fetcher, MessageToL1Assertions
+        };
+
+        #[starknet::interface]
+        trait IGasChecker<TContractState> {
+            fn send_l1_message(self: @TContractState);
+        }
+
+        #[test]
+        fn spy_messages_to_l1_simple() {
+            let contract = declare("GasChecker");
+            let contract_address = contract.deploy(@ArrayTrait::new()).unwrap();
+            let dispatcher = IGasCheckerDispatcher { contract_address };
+
+            let mut spy = spy_messages_to_l1(SpyOn::One(contract_address));
+
+            dispatcher.send_l1_message();
+
+            spy.fetch_messages();
+            assert(spy.messages.len() == 1, 'There should be one message');
+
+            let (from, message) = spy.messages.at(0);
+            assert(*from == contract_address, 'Wrong from address');
+            let to_address: EthAddress = 1.try_into().unwrap();
+            assert(*message.to_address == to_address, 'Wrong to address');
+            assert(message.payload == @array![1], 'Wrong payload');
+
+            dispatcher.send_l1_message();
+
+            let expected_message = MessageToL1 { to_address, payload: array![1] };
+            spy.assert_sent(@array![(contract_address, expected_message)]);
+
+            let unexpected_message = MessageToL1 { to_address, payload: array![2] };
+            spy.assert_not_sent(@array![(contract_address, unexpected_message)]);
+        }
+    "#
+        ),
+        Contract::from_code_path(
+            "GasChecker".to_string(),
+            Path::new("tests/data/contracts/gas_checker.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+    assert_passed(&result);
+}
+
+#[test]
+fn spy_messages_to_l1_sent_from_test() {
+    let test = test_case!(indoc!(
+        r"
+        use array::ArrayTrait;
+        use snforge_std::{
+            spy_messages_to_l1, SpyOn, MessageToL1Spy, MessageToL1Fetcher, test_address
+        };
+
+        #[test]
+        fn spy_messages_to_l1_sent_from_test() {
+            let mut spy = spy_messages_to_l1(SpyOn::All);
+
+            starknet::send_message_to_l1_syscall(1, array![2, 3].span()).unwrap();
+
+            spy.fetch_messages();
+            assert(spy.messages.len() == 1, 'There should be one message');
+
+            let (from, message) = spy.messages.at(0);
+            assert(*from == test_address(), 'Wrong from address');
+            assert(message.payload == @array![2, 3], 'Wrong payload');
+        }
+    "
+    ),);
+
+    let result = run_test_case(&test);
+    assert_passed(&result);
+}
@@ -1,6 +1,6 @@
 use indoc::indoc;
 use std::path::Path;
-use test_utils::runner::{assert_passed, Contract};
+use test_utils::runner::{assert_case_output_contains, assert_failed, assert_passed, Contract};
 use test_utils::running_tests::run_test_case;
 use test_utils::test_case;
 
@@ -1003,3 +1003,65 @@ fn trace_l1_handler() {
 
     assert_passed(&result);
 }
+
+#[test]
+fn backtrace_of_failed_call() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use snforge_std::{declare, ContractClassTrait};
+            use starknet::ContractAddress;
+
+            #[starknet::interface]
+            trait ITraceInfoProxy<T> {
+                fn with_panic(self: @T, contract_address: ContractAddress);
+            }
+
+            #[test]
+            fn test_backtrace() {
+                let checker_address = declare("TraceInfoChecker").deploy(@array![]).unwrap();
+                let proxy_address = declare("TraceInfoProxy")
+                    .deploy(@array![checker_address.into()])
+                    .unwrap();
+
+                ITraceInfoProxyDispatcher { contract_address: proxy_address }
+                    .with_panic(checker_address);
+            }
+        "#
+        ),
+        Contract::from_code_path(
+            "TraceInfoProxy".to_string(),
+            Path::new("tests/data/contracts/trace_info_proxy.cairo"),
+        )
+        .unwrap(),
+        Contract::from_code_path(
+            "TraceInfoChecker".to_string(),
+            Path::new("tests/data/contracts/trace_info_checker.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "test_backtrace",
+        "Backtrace (outermost call first):",
+    );
+    assert_case_output_contains(
+        &result,
+        "test_backtrace",
+        "TraceInfoProxy::with_panic (contract address: ",
+    );
+    assert_case_output_contains(
+        &result,
+        "test_backtrace",
+        "TraceInfoChecker::panic (contract address: ",
+    );
+    assert_case_output_contains(
+        &result,
+        "test_backtrace",
+        ") <- failed here\n            panicked with panic",
+    );
+}
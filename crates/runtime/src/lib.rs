@@ -324,7 +324,7 @@ impl<Extension: ExtensionLogic> SyscallPtrAccess for ExtendedRuntime<Extension>
 
 impl<Extension: ExtensionLogic> ResourceTracker for ExtendedRuntime<Extension> {
     fn consumed(&self) -> bool {
-        self.extended_runtime.consumed()
+        self.extension.is_cancelled() || self.extended_runtime.consumed()
     }
 
     fn consume_step(&mut self) {
@@ -396,6 +396,11 @@ pub trait ExtensionLogic {
     ) {
     }
 
+    /// Whether the run was cancelled, the VM stops before its next step if any extension reports it
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+
     /// Different from `handle_cheadcode` because it cannot be overridden,
     /// always receives a signal and cannot return an error
     /// Signals are executed in reverse order to normal cheatcode handlers
@@ -14,7 +14,7 @@ use starknet_api::{
 };
 
 /// A simple implementation of `StateReader` using `HashMap`s as storage.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct DictStateReader {
     pub storage_view: HashMap<StorageEntry, StarkFelt>,
     pub address_to_nonce: HashMap<ContractAddress, Nonce>,
@@ -61,6 +61,16 @@ fn felt252_to_vec_length(vec_len: &Felt252) -> usize {
     vec_len.to_usize().expect("Invalid Vec length value")
 }
 
+/// Decodes data consisting of a single `ByteArray` serialized with its magic prefix,
+/// e.g. panic data of `panic!("message")`
+#[must_use]
+pub fn try_deserialize_byte_array(values: &[Felt252]) -> Option<String> {
+    match try_format_string(values)? {
+        (string, len) if len == values.len() => Some(string),
+        _ => None,
+    }
+}
+
 fn try_format_string(values: &[Felt252]) -> Option<(String, usize)> {
     let mut values = values.iter();
 
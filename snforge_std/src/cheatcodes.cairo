@@ -1,17 +1,22 @@
 use starknet::{testing::cheatcode, ContractAddress, ClassHash, contract_address_const};
 
 mod events;
+mod calls;
+mod messages_to_l1;
 mod l1_handler;
 mod contract_class;
 mod tx_info;
 mod fork;
 mod storage;
+mod invariant;
 
 #[derive(Drop, Serde)]
 enum CheatTarget {
     All: (),
     One: ContractAddress,
-    Multiple: Array<ContractAddress>
+    Multiple: Array<ContractAddress>,
+    // All contracts of the class, including the ones deployed later
+    Class: ClassHash
 }
 
 fn test_selector() -> felt252 {
@@ -78,32 +83,171 @@ fn stop_elect(target: CheatTarget) {
     cheatcode::<'stop_elect'>(inputs.span());
 }
 
+#[derive(Copy, Drop, Serde)]
+enum CheatSpan {
+    Indefinite: (),
+    Number: usize
+}
+
+/// Contracts a mock applies to
+#[derive(Copy, Drop, Serde)]
+enum MockTarget {
+    Contract: ContractAddress,
+    // All contracts of the class, including the ones deployed later
+    Class: ClassHash
+}
+
+/// Calldata of the calls a mock applies to
+#[derive(Drop, Serde)]
+enum MockCalldata {
+    Any: (),
+    Exact: Span<felt252>,
+    Prefix: Span<felt252>
+}
+
+/// Mocks the function of the contract for the calls with matching calldata
+/// - `target` - contract or class whose function is mocked, mocks of a contract take precedence over mocks of its class
+/// - `function_selector` - selector of the mocked function
+/// - `calldata` - calldata of the calls the mock applies to, the most specific matching mock is used
+/// - `ret_data` - data returned by the mocked calls
+/// - `span` - number of calls the mock applies to
+fn mock_call<T, impl TSerde: core::serde::Serde<T>, impl TDestruct: Destruct<T>>(
+    target: MockTarget,
+    function_selector: felt252,
+    calldata: MockCalldata,
+    ret_data: T,
+    span: CheatSpan
+) {
+    let mut serialized_ret_data = array![];
+    serialize_ret_data(ref serialized_ret_data, ret_data);
+
+    _mock_call(target, function_selector, calldata, 1, serialized_ret_data, span);
+}
+
+/// Mocks the function of the contract to return the next data of the sequence on each call,
+/// the last data is returned once the sequence is used up
+/// - `target` - contract or class whose function is mocked, mocks of a contract take precedence over mocks of its class
+/// - `function_selector` - selector of the mocked function
+/// - `calldata` - calldata of the calls the mock applies to, the most specific matching mock is used
+/// - `ret_data` - data returned by the successive mocked calls, can't be empty
+/// - `span` - number of calls the mock applies to
+fn mock_call_sequence<T, impl TSerde: core::serde::Serde<T>, impl TDrop: Drop<T>>(
+    target: MockTarget,
+    function_selector: felt252,
+    calldata: MockCalldata,
+    mut ret_data: Array<T>,
+    span: CheatSpan
+) {
+    let ret_data_count = ret_data.len();
+    let mut serialized_ret_data = array![];
+    loop {
+        match ret_data.pop_front() {
+            Option::Some(data) => serialize_ret_data(ref serialized_ret_data, data),
+            Option::None => { break; }
+        };
+    };
+
+    _mock_call(
+        target,
+        function_selector,
+        calldata,
+        ret_data_count,
+        serialized_ret_data,
+        span
+    );
+}
+
+/// Mocks the function of the contract to panic with `panic_data` for the calls with matching calldata,
+/// the panic is seen by the caller (e.g. a safe dispatcher) the same way as a panic of the function would be
+/// - `target` - contract or class whose function is mocked, mocks of a contract take precedence over mocks of its class
+/// - `function_selector` - selector of the mocked function
+/// - `calldata` - calldata of the calls the mock applies to, the most specific matching mock is used
+/// - `panic_data` - data the mocked calls panic with
+/// - `span` - number of calls the mock applies to
+fn mock_call_panic(
+    target: MockTarget,
+    function_selector: felt252,
+    calldata: MockCalldata,
+    panic_data: Array<felt252>,
+    span: CheatSpan
+) {
+    validate_span(span);
+
+    let mut inputs = array![];
+    target.serialize(ref inputs);
+    inputs.append(function_selector);
+    calldata.serialize(ref inputs);
+    panic_data.serialize(ref inputs);
+    span.serialize(ref inputs);
+
+    cheatcode::<'mock_call_panic'>(inputs.span());
+}
+
 fn start_mock_call<T, impl TSerde: core::serde::Serde<T>, impl TDestruct: Destruct<T>>(
     contract_address: ContractAddress, function_selector: felt252, ret_data: T
 ) {
-    let contract_address_felt: felt252 = contract_address.into();
-    let mut inputs = array![contract_address_felt, function_selector];
+    mock_call(
+        MockTarget::Contract(contract_address),
+        function_selector,
+        MockCalldata::Any,
+        ret_data,
+        CheatSpan::Indefinite
+    );
+}
 
-    let mut ret_data_arr = ArrayTrait::new();
-    ret_data.serialize(ref ret_data_arr);
+fn _mock_call(
+    target: MockTarget,
+    function_selector: felt252,
+    calldata: MockCalldata,
+    ret_data_count: usize,
+    serialized_ret_data: Array<felt252>,
+    span: CheatSpan
+) {
+    assert(ret_data_count > 0, 'ret_data must not be empty');
+    validate_span(span);
 
-    let ret_data_len = ret_data_arr.len();
+    let mut inputs = array![];
+    target.serialize(ref inputs);
+    inputs.append(function_selector);
+    calldata.serialize(ref inputs);
+    inputs.append(ret_data_count.into());
+    inputs.append_span(serialized_ret_data.span());
+    span.serialize(ref inputs);
 
-    inputs.append(ret_data_len.into());
+    cheatcode::<'mock_call'>(inputs.span());
+}
 
-    let mut i = 0;
-    loop {
-        if ret_data_len == i {
-            break ();
-        }
-        inputs.append(*ret_data_arr[i]);
-        i += 1;
+fn validate_span(span: CheatSpan) {
+    match span {
+        CheatSpan::Indefinite => {},
+        CheatSpan::Number(n) => { assert(n > 0, 'span must be greater than 0'); },
     };
+}
 
-    cheatcode::<'start_mock_call'>(inputs.span());
+// Appends the serialized data preceded by its length
+fn serialize_ret_data<T, impl TSerde: core::serde::Serde<T>, impl TDestruct: Destruct<T>>(
+    ref output: Array<felt252>, data: T
+) {
+    let mut data_arr = array![];
+    data.serialize(ref data_arr);
+    output.append(data_arr.len().into());
+    output.append_span(data_arr.span());
 }
 
 fn stop_mock_call(contract_address: ContractAddress, function_selector: felt252) {
-    let contract_address_felt: felt252 = contract_address.into();
-    cheatcode::<'stop_mock_call'>(array![contract_address_felt, function_selector].span());
+    _stop_mock_call(MockTarget::Contract(contract_address), function_selector);
+}
+
+/// Cancels all mocks of the function of the class made with `MockTarget::Class`
+/// - `class_hash` - class hash whose function is mocked
+/// - `function_selector` - selector of the mocked function
+fn stop_class_mock_call(class_hash: ClassHash, function_selector: felt252) {
+    _stop_mock_call(MockTarget::Class(class_hash), function_selector);
+}
+
+fn _stop_mock_call(target: MockTarget, function_selector: felt252) {
+    let mut inputs = array![];
+    target.serialize(ref inputs);
+    inputs.append(function_selector);
+    cheatcode::<'stop_mock_call'>(inputs.span());
 }
@@ -0,0 +1,89 @@
+use starknet::testing::cheatcode;
+use starknet::ContractAddress;
+use snforge_std::cheatcodes::events::SpyOn;
+use snforge_std::trace::{CallType, CallResult};
+
+#[derive(Drop, Serde, PartialEq)]
+struct Call {
+    to: ContractAddress,
+    caller: ContractAddress,
+    selector: felt252,
+    calldata: Array<felt252>,
+    call_type: CallType,
+    result: CallResult
+}
+
+#[derive(Drop, Serde)]
+struct CallSpy {
+    _id: felt252,
+    calls: Array<Call>,
+}
+
+/// Creates `CallSpy` recording the calls made to the contracts defined under `spy_on`
+/// - `selectors` - selectors of the recorded calls, calls of all selectors are recorded if empty
+fn spy_calls(spy_on: SpyOn, selectors: Array<felt252>) -> CallSpy {
+    let mut inputs = array![];
+    spy_on.serialize(ref inputs);
+    selectors.serialize(ref inputs);
+    let output = cheatcode::<'spy_calls'>(inputs.span());
+
+    CallSpy { _id: *output[0], calls: array![] }
+}
+
+trait CallFetcher {
+    fn fetch_calls(ref self: CallSpy);
+}
+
+impl CallFetcherImpl of CallFetcher {
+    fn fetch_calls(ref self: CallSpy) {
+        let mut output = cheatcode::<'fetch_calls'>(array![self._id].span());
+        let mut calls = Serde::<Array<Call>>::deserialize(ref output).unwrap();
+
+        loop {
+            match calls.pop_front() {
+                Option::Some(call) => self.calls.append(call),
+                Option::None => { break; }
+            };
+        };
+    }
+}
+
+trait CallAssertions {
+    fn assert_called(
+        self: @CallSpy,
+        to: ContractAddress,
+        selector: felt252,
+        calldata: Array<felt252>,
+        times: usize
+    );
+    fn assert_not_called(
+        self: @CallSpy, to: ContractAddress, selector: felt252, calldata: Array<felt252>
+    );
+}
+
+impl CallAssertionsImpl of CallAssertions {
+    /// Panics with the recorded calls of the function unless the call was recorded exactly `times` times
+    fn assert_called(
+        self: @CallSpy,
+        to: ContractAddress,
+        selector: felt252,
+        calldata: Array<felt252>,
+        times: usize
+    ) {
+        let mut inputs = array![*self._id, to.into(), selector];
+        calldata.serialize(ref inputs);
+        inputs.append(times.into());
+        let output = cheatcode::<'assert_called'>(inputs.span());
+
+        if *output[0] == 1 {
+            let mut panic_data = output.slice(1, output.len() - 1);
+            panic(Serde::<Array<felt252>>::deserialize(ref panic_data).unwrap());
+        }
+    }
+
+    fn assert_not_called(
+        self: @CallSpy, to: ContractAddress, selector: felt252, calldata: Array<felt252>
+    ) {
+        self.assert_called(to, selector, calldata, 0);
+    }
+}
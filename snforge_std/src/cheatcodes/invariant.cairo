@@ -0,0 +1,21 @@
+use starknet::{testing::cheatcode, ContractAddress};
+
+/// Registers a deployed contract whose external functions are called by `next_call`
+/// - `contract_address` - address of the contract, it has to be a contract of the tested package
+fn target_contract(contract_address: ContractAddress) {
+    cheatcode::<'target_contract'>(array![contract_address.into()].span());
+}
+
+/// Registers an address used as the caller of calls made by `next_call`.
+/// Callers are random addresses if no sender is registered.
+/// - `sender` - address of the caller
+fn target_sender(sender: ContractAddress) {
+    cheatcode::<'target_sender'>(array![sender.into()].span());
+}
+
+/// Executes the next call of the random call sequence of an invariant test.
+/// Returns `false` once the sequence is finished.
+fn next_call() -> bool {
+    let outputs = cheatcode::<'next_call'>(array![].span());
+    *outputs[0] == 1
+}
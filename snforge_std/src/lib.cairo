@@ -26,10 +26,26 @@ use cheatcodes::events::EventAssertions;
 use cheatcodes::events::spy_events;
 use cheatcodes::events::event_name_hash;
 
+use cheatcodes::calls::Call;
+use cheatcodes::calls::CallSpy;
+use cheatcodes::calls::CallFetcher;
+use cheatcodes::calls::CallAssertions;
+use cheatcodes::calls::spy_calls;
+
+use cheatcodes::messages_to_l1::MessageToL1;
+use cheatcodes::messages_to_l1::MessageToL1Spy;
+use cheatcodes::messages_to_l1::MessageToL1Fetcher;
+use cheatcodes::messages_to_l1::MessageToL1Assertions;
+use cheatcodes::messages_to_l1::spy_messages_to_l1;
+
 use cheatcodes::storage::store;
 use cheatcodes::storage::load;
 use cheatcodes::storage::map_entry_address;
 
+use cheatcodes::invariant::target_contract;
+use cheatcodes::invariant::target_sender;
+use cheatcodes::invariant::next_call;
+
 use cheatcodes::CheatTarget;
 use cheatcodes::test_address;
 use cheatcodes::test_selector;
@@ -41,8 +57,15 @@ use cheatcodes::start_warp;
 use cheatcodes::stop_warp;
 use cheatcodes::start_elect;
 use cheatcodes::stop_elect;
+use cheatcodes::CheatSpan;
+use cheatcodes::MockTarget;
+use cheatcodes::MockCalldata;
+use cheatcodes::mock_call;
+use cheatcodes::mock_call_sequence;
+use cheatcodes::mock_call_panic;
 use cheatcodes::start_mock_call;
 use cheatcodes::stop_mock_call;
+use cheatcodes::stop_class_mock_call;
 use cheatcodes::replace_bytecode;
 
 mod fs;